@@ -0,0 +1,368 @@
+//! Derive macros for [clickhouse-rs](https://docs.rs/clickhouse-rs/).
+//!
+//! `#[derive(Row)]` implements `FromRow` and `IntoBlock` for a struct with named fields,
+//! mapping every field to the column of the same name.
+//!
+//! Field attributes:
+//!
+//! - `#[clickhouse(rename = "name")]` - use another column name for the field.
+//! - `#[clickhouse(skip)]` - ignore the field, it's filled with `Default::default()` when reading.
+//!
+//! `#[derive(Enum)]` maps an enum with unit variants to ClickHouse `Enum8` (or `Enum16` if
+//! the discriminants don't fit into `i8`) by variant names. It implements `EnumType`,
+//! `HasSqlType`, `FromSql` and `Into<Value>`.
+//!
+//! Enum attributes:
+//!
+//! - `#[clickhouse(enum16)]` - map the enum to `Enum16`.
+//!
+//! Variant attributes:
+//!
+//! - `#[clickhouse(rename = "name")]` - use another name for the variant.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Expr, ExprLit, ExprUnary, Fields,
+    Ident, Lit, LitStr, Result, Type, UnOp,
+};
+
+#[proc_macro_derive(Row, attributes(clickhouse))]
+pub fn derive_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Enum, attributes(clickhouse))]
+pub fn derive_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_enum(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    column: String,
+    skip: bool,
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let fields = parse_fields(&input)?;
+    let name = &input.ident;
+
+    let columns: Vec<_> = fields.iter().filter(|field| !field.skip).collect();
+    let idents: Vec<_> = columns.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = columns.iter().map(|field| &field.ty).collect();
+    let names: Vec<_> = columns.iter().map(|field| &field.column).collect();
+    let locals: Vec<_> = (0..columns.len())
+        .map(|i| format_ident!("__column_{}", i))
+        .collect();
+    let skipped: Vec<_> = fields
+        .iter()
+        .filter(|field| field.skip)
+        .map(|field| &field.ident)
+        .collect();
+
+    let mut from_row_generics = input.generics.clone();
+    from_row_generics.params.insert(0, parse_quote!('__row));
+    {
+        let where_clause = from_row_generics.make_where_clause();
+        for ty in &types {
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: ::clickhouse_rs::types::FromSql<'__row>));
+        }
+        for field in fields.iter().filter(|field| field.skip) {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: ::core::default::Default));
+        }
+    }
+    let (from_row_impl, _, from_row_where) = from_row_generics.split_for_impl();
+
+    let mut into_block_generics = input.generics.clone();
+    {
+        let where_clause = into_block_generics.make_where_clause();
+        for ty in &types {
+            where_clause
+                .predicates
+                .push(parse_quote!(Vec<#ty>: ::clickhouse_rs::types::column::ColumnFrom));
+        }
+    }
+    let (into_block_impl, _, into_block_where) = into_block_generics.split_for_impl();
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #from_row_impl ::clickhouse_rs::types::FromRow<'__row> for #name #ty_generics
+        #from_row_where
+        {
+            fn from_row<K: ::clickhouse_rs::types::ColumnType>(
+                row: &'__row ::clickhouse_rs::types::Row<'__row, K>,
+            ) -> ::clickhouse_rs::errors::Result<Self> {
+                Ok(Self {
+                    #( #idents: row.get(#names)?, )*
+                    #( #skipped: ::core::default::Default::default(), )*
+                })
+            }
+        }
+
+        impl #into_block_impl ::clickhouse_rs::types::IntoBlock for #name #ty_generics
+        #into_block_where
+        {
+            fn into_block(rows: Vec<Self>) -> ::clickhouse_rs::types::Block {
+                #( let mut #locals: Vec<#types> = Vec::with_capacity(rows.len()); )*
+                for row in rows {
+                    #( #locals.push(row.#idents); )*
+                }
+                ::clickhouse_rs::types::Block::new()
+                    #( .column(#names, #locals) )*
+            }
+        }
+    })
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<Field>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "#[derive(Row)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "#[derive(Row)] is only supported for structs",
+            ))
+        }
+    };
+
+    let mut result = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ident = field.ident.clone().unwrap();
+        let mut column = ident.to_string().trim_start_matches("r#").to_string();
+        let mut skip = false;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("clickhouse"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    column = value.value();
+                    if column.contains('`') {
+                        return Err(meta.error("column name shouldn't contain backticks"));
+                    }
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported clickhouse attribute"))
+                }
+            })?;
+        }
+
+        result.push(Field {
+            ident,
+            ty: field.ty.clone(),
+            column,
+            skip,
+        });
+    }
+
+    if result.iter().all(|field| field.skip) {
+        return Err(Error::new(
+            Span::call_site(),
+            "#[derive(Row)] requires at least one field that isn't skipped",
+        ));
+    }
+
+    Ok(result)
+}
+
+struct Variant {
+    ident: Ident,
+    name: String,
+    value: i64,
+}
+
+fn expand_enum(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let (variants, enum16) = parse_variants(&input)?;
+    let name = &input.ident;
+
+    let idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let names: Vec<_> = variants.iter().map(|variant| &variant.name).collect();
+
+    let fits_i8 = variants
+        .iter()
+        .all(|variant| i8::try_from(variant.value).is_ok());
+    let sql_type = if fits_i8 && !enum16 {
+        let values = variants
+            .iter()
+            .map(|variant| proc_macro2::Literal::i8_unsuffixed(variant.value as i8));
+        quote!(Enum8(vec![#( (#names.to_string(), #values), )*]))
+    } else {
+        let values = variants
+            .iter()
+            .map(|variant| proc_macro2::Literal::i16_unsuffixed(variant.value as i16));
+        quote!(Enum16(vec![#( (#names.to_string(), #values), )*]))
+    };
+
+    Ok(quote! {
+        impl ::clickhouse_rs::types::HasSqlType for #name {
+            fn get_sql_type() -> ::clickhouse_rs::types::SqlType {
+                ::clickhouse_rs::types::SqlType::#sql_type
+            }
+        }
+
+        impl ::clickhouse_rs::types::EnumType for #name {
+            fn from_name(name: &str) -> ::core::option::Option<Self> {
+                match name {
+                    #( #names => ::core::option::Option::Some(Self::#idents), )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #( Self::#idents => #names, )*
+                }
+            }
+        }
+
+        impl<'__value> ::clickhouse_rs::types::FromSql<'__value> for #name {
+            fn from_sql(
+                value: ::clickhouse_rs::types::ValueRef<'__value>,
+            ) -> ::clickhouse_rs::types::FromSqlResult<Self> {
+                <Self as ::clickhouse_rs::types::EnumType>::from_value_ref(value)
+            }
+        }
+
+        impl ::core::convert::From<#name> for ::clickhouse_rs::types::Value {
+            fn from(value: #name) -> Self {
+                ::clickhouse_rs::types::EnumType::to_value(&value)
+            }
+        }
+    })
+}
+
+fn parse_variants(input: &DeriveInput) -> Result<(Vec<Variant>, bool)> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "#[derive(Enum)] is only supported for enums",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "#[derive(Enum)] doesn't support generic enums",
+        ));
+    }
+
+    let mut enum16 = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("clickhouse"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("enum16") {
+                enum16 = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported clickhouse attribute"))
+            }
+        })?;
+    }
+
+    let mut result: Vec<Variant> = Vec::with_capacity(data.variants.len());
+    let mut next_value = 0_i64;
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "#[derive(Enum)] requires variants without fields",
+            ));
+        }
+
+        let ident = variant.ident.clone();
+        let mut name = ident.to_string().trim_start_matches("r#").to_string();
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("clickhouse"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    name = value.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported clickhouse attribute"))
+                }
+            })?;
+        }
+
+        let value = match &variant.discriminant {
+            Some((_, expr)) => parse_discriminant(expr)?,
+            None => next_value,
+        };
+        if i16::try_from(value).is_err() {
+            return Err(Error::new_spanned(
+                variant,
+                "enum value doesn't fit into Enum16",
+            ));
+        }
+        if result.iter().any(|other| other.name == name) {
+            return Err(Error::new_spanned(variant, "duplicate enum value name"));
+        }
+        next_value = value + 1;
+
+        result.push(Variant { ident, name, value });
+    }
+
+    if result.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "#[derive(Enum)] requires at least one variant",
+        ));
+    }
+
+    Ok((result, enum16))
+}
+
+fn parse_discriminant(expr: &Expr) -> Result<i64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_discriminant(expr).map(|value| -value),
+        _ => Err(Error::new_spanned(
+            expr,
+            "enum discriminant should be an integer literal",
+        )),
+    }
+}
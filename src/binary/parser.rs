@@ -7,7 +7,7 @@ use crate::{
     binary::{protocol, ReadEx},
     errors::{DriverError, Error, Result, ServerError},
     io::transport::TransportInfo,
-    types::{Block, Packet, ProfileInfo, Progress, ServerInfo, TableColumns},
+    types::{Block, LogRecord, Packet, ProfileInfo, Progress, ServerInfo, TableColumns},
 };
 
 /// The internal clickhouse response parser.
@@ -41,25 +41,50 @@ impl<'i, T: Read> Parser<'i, T> {
             protocol::SERVER_PROFILE_INFO => Ok(self.parse_profile_info()?),
             protocol::SERVER_TABLE_COLUMNS => Ok(self.parse_table_columns()?),
             protocol::SERVER_EXCEPTION => Ok(self.parse_exception()?),
-            protocol::SERVER_DATA | protocol::SERVER_TOTALS | protocol::SERVER_EXTREMES => {
-                Ok(self.parse_block()?)
-            }
+            protocol::SERVER_DATA => Ok(Packet::Block(self.parse_block()?)),
+            protocol::SERVER_TOTALS => Ok(Packet::Totals(self.parse_block()?)),
+            protocol::SERVER_EXTREMES => Ok(Packet::Extremes(self.parse_block()?)),
+            protocol::SERVER_LOG => Ok(self.parse_server_log()?),
+            protocol::SERVER_PROFILE_EVENTS => Ok(self.parse_profile_events()?),
             protocol::SERVER_END_OF_STREAM => Ok(Packet::Eof(())),
             _ => Err(Error::Driver(DriverError::UnknownPacket { packet })),
         }
     }
 
-    fn parse_block(&mut self) -> Result<Packet<()>> {
+    fn parse_block(&mut self) -> Result<Block> {
+        self.parse_block_(self.info.compress)
+    }
+
+    fn parse_block_(&mut self, compress: bool) -> Result<Block> {
         match self.info.timezone {
             None => Err(Error::Driver(DriverError::UnexpectedPacket)),
             Some(tz) => {
                 self.reader.skip_string()?;
-                let block = Block::load(&mut self.reader, tz, self.info.compress)?;
-                Ok(Packet::Block(block))
+                Block::load(&mut self.reader, tz, compress, self.info.revision)
             }
         }
     }
 
+    fn parse_server_log(&mut self) -> Result<Packet<()>> {
+        // Server logs are never compressed.
+        let block = self.parse_block_(false)?;
+        let records = LogRecord::from_block(&block)?;
+
+        trace!("[process]      <- server log: {} records", records.len());
+        Ok(Packet::ServerLog(records))
+    }
+
+    fn parse_profile_events(&mut self) -> Result<Packet<()>> {
+        // Profile events are never compressed.
+        let block = self.parse_block_(false)?;
+
+        trace!(
+            "[process]      <- profile events: {} rows",
+            block.row_count()
+        );
+        Ok(Packet::ProfileEvents(block))
+    }
+
     fn parse_server_info(&mut self) -> Result<Packet<()>> {
         let name = self.reader.read_string()?;
         let major_version = self.reader.read_uvarint()?;
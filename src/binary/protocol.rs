@@ -6,6 +6,15 @@ pub const DBMS_MIN_REVISION_WITH_VERSION_PATCH: u64 = 54401;
 pub const _DBMS_MIN_REVISION_WITH_SERVER_LOGS: u64 = 54406;
 pub const DBMS_MIN_REVISION_WITH_CLIENT_WRITE_INFO: u64 = 54420;
 pub const DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS: u64 = 54429;
+pub const DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET: u64 = 54441;
+pub const DBMS_MIN_REVISION_WITH_OPENTELEMETRY: u64 = 54442;
+pub const DBMS_MIN_PROTOCOL_VERSION_WITH_DISTRIBUTED_DEPTH: u64 = 54448;
+pub const DBMS_MIN_PROTOCOL_VERSION_WITH_INITIAL_QUERY_START_TIME: u64 = 54449;
+pub const _DBMS_MIN_PROTOCOL_VERSION_WITH_INCREMENTAL_PROFILE_EVENTS: u64 = 54451;
+pub const DBMS_MIN_REVISION_WITH_PARALLEL_REPLICAS: u64 = 54453;
+pub const DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION: u64 = 54454;
+pub const DBMS_MIN_PROTOCOL_VERSION_WITH_ADDENDUM: u64 = 54458;
+pub const DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS: u64 = 54459;
 
 pub const CLIENT_HELLO: u64 = 0;
 pub const CLIENT_QUERY: u64 = 1;
@@ -28,5 +37,8 @@ pub const SERVER_PROFILE_INFO: u64 = 6;
 pub const SERVER_TOTALS: u64 = 7;
 pub const SERVER_EXTREMES: u64 = 8;
 pub const _SERVER_TABLES_STATUS_RESPONSE: u64 = 9;
-pub const _SERVER_LOG: u64 = 10;
+pub const SERVER_LOG: u64 = 10;
 pub const SERVER_TABLE_COLUMNS: u64 = 11;
+pub const _SERVER_PART_UUIDS: u64 = 12;
+pub const _SERVER_READ_TASK_REQUEST: u64 = 13;
+pub const SERVER_PROFILE_EVENTS: u64 = 14;
@@ -2,7 +2,7 @@ use crate::binary::Encoder;
 
 pub static CLIENT_NAME: &str = "Rust SQLDriver";
 
-pub const CLICK_HOUSE_REVISION: u64 = 54429; // DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS
+pub const CLICK_HOUSE_REVISION: u64 = 54459; // DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS
 pub const CLICK_HOUSE_DBMSVERSION_MAJOR: u64 = 1;
 pub const CLICK_HOUSE_DBMSVERSION_MINOR: u64 = 1;
 
@@ -66,6 +66,12 @@ pub enum ConnectionError {
 
     #[error("Connection broken")]
     Broken,
+
+    #[error("Timed out waiting for a connection from the pool")]
+    CheckoutTimeout,
+
+    #[error("Too many tasks are waiting for a connection from the pool")]
+    TooManyWaiters,
 }
 
 /// This type enumerates connection URL errors.
@@ -102,6 +108,9 @@ pub enum DriverError {
     #[error("Timeout error.")]
     Timeout,
 
+    #[error("Query was cancelled.")]
+    QueryCancelled,
+
     #[error("Invalid utf-8 sequence.")]
     Utf8Error(Utf8Error),
 
@@ -213,6 +222,34 @@ impl From<Error> for io::Error {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Other(msg.to_string().into())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error::Other(msg.to_string().into())
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(err: arrow_schema::ArrowError) -> Self {
+        Error::Other(err.to_string().into())
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Other(err.to_string().into())
+    }
+}
+
 impl From<Utf8Error> for Error {
     fn from(err: Utf8Error) -> Self {
         Error::Driver(DriverError::Utf8Error(err))
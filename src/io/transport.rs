@@ -1,4 +1,5 @@
 use std::{
+    cmp,
     collections::VecDeque,
     io::{self, Cursor},
     pin::Pin,
@@ -18,13 +19,14 @@ use pin_project::pin_project;
 
 use crate::{
     binary::Parser,
-    errors::{DriverError, Error, Result},
+    client_info,
+    errors::{codes, DriverError, Error, Result},
     io::{read_to_end::read_to_end, Stream as InnerStream},
     pool::{Inner, Pool},
-    types::{Block, Cmd, Packet},
+    types::{Block, Callbacks, Cmd, Packet},
 };
 use futures_core::Stream;
-use futures_util::StreamExt;
+use futures_util::{future, StreamExt};
 
 pub(crate) struct TransportInfo {
     pub(crate) timezone: Option<Tz>,
@@ -99,6 +101,13 @@ impl ClickhouseTransport {
         self.status.inside.store(value, Ordering::Release);
     }
 
+    /// Sends the command that doesn't have a response.
+    pub(crate) async fn send_cmd(mut self, req: Cmd) -> Result<Self> {
+        self.cmds.push_back(req);
+        future::poll_fn(|cx| self.send(cx)).await?;
+        Ok(self)
+    }
+
     pub(crate) async fn clear(self) -> Result<Self> {
         if !self.inconsistent {
             return Ok(self);
@@ -113,6 +122,12 @@ impl ClickhouseTransport {
                     h = Some(inner);
                 }
                 Ok(Packet::Eof(inner)) => h = Some(inner),
+                // The server answers `Cancel` with this exception when it stops
+                // the query, nothing follows it, so the connection is clean.
+                Ok(Packet::Exception(e)) if e.code == codes::QUERY_WAS_CANCELLED => {
+                    h = stream.take_transport();
+                    break;
+                }
                 Ok(Packet::Exception(e)) => return Err(Error::Server(e)),
                 Err(e) => return Err(Error::Io(e)),
                 _ => {}
@@ -166,7 +181,7 @@ impl<'p> ClickhouseTransportProj<'p> {
 
             if let Ok(Packet::Hello(_, ref packet)) = res {
                 self.info.timezone = Some(packet.timezone);
-                self.info.revision = packet.revision;
+                self.info.revision = cmp::min(packet.revision, client_info::CLICK_HOUSE_REVISION);
             }
 
             match res {
@@ -300,7 +315,10 @@ impl Stream for ClickhouseTransport {
 }
 
 impl PacketStream {
-    pub(crate) async fn read_block(mut self) -> Result<(ClickhouseTransport, Option<Block>)> {
+    pub(crate) async fn read_block(
+        mut self,
+        callbacks: &Callbacks,
+    ) -> Result<(ClickhouseTransport, Option<Block>)> {
         self.read_block = true;
 
         let mut h = None;
@@ -311,6 +329,9 @@ impl PacketStream {
                 Ok(Packet::Block(block)) => b = Some(block),
                 Ok(Packet::Exception(e)) => return Err(Error::Server(e)),
                 Ok(Packet::TableColumns(_)) => (),
+                Ok(Packet::Progress(p)) => callbacks.on_progress(p),
+                Ok(Packet::ServerLog(records)) => callbacks.on_server_log(records),
+                Ok(Packet::ProfileEvents(_)) => (),
                 Err(e) => return Err(Error::Io(e)),
                 _ => return Err(Error::Driver(DriverError::UnexpectedPacket)),
             }
@@ -322,6 +343,15 @@ impl PacketStream {
     pub(crate) fn take_transport(&mut self) -> Option<ClickhouseTransport> {
         self.inner.take()
     }
+
+    /// Sends `Cancel` to the server, the packets of the query
+    /// should be read until the end of stream after that.
+    pub(crate) fn cancel(&mut self) {
+        if let Some(ref mut inner) = self.inner {
+            inner.cmds.push_back(Cmd::Cancel);
+            self.state = PacketStreamState::Ask;
+        }
+    }
 }
 
 impl Stream for PacketStream {
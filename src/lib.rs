@@ -12,16 +12,21 @@
 //! ### Supported data types
 //!
 //! * Date
+//! * Date32
 //! * DateTime
 //! * Decimal(P, S)
 //! * Float32, Float64
 //! * String, FixedString(N)
-//! * UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64
+//! * UInt8, UInt16, UInt32, UInt64, UInt128, UInt256, Int8, Int16, Int32, Int64, Int128, Int256
 //! * Nullable(T)
 //! * Array(UInt/Int/String/Date/DateTime)
 //! * SimpleAggregateFunction(F, T)
 //! * IPv4/IPv6
 //! * UUID
+//! * Tuple(T1, T2, ...)
+//! * Point, Ring, Polygon, MultiPolygon
+//! * Variant(T1, T2, ...), Dynamic
+//! * JSON, Object('json') (read as JSON text)
 //!
 //! ### DNS
 //!
@@ -34,6 +39,8 @@
 //! - `compression` - Whether or not use compression (defaults to `none`). Possible choices:
 //!     * `none`
 //!     * `lz4`
+//!     * `lz4hc` or `lz4hc(level)` (level defaults to `9`)
+//!     * `zstd` or `zstd(level)` (level defaults to `1`)
 //!
 //! - `readonly` - Restricts permissions for read data, write data and change settings queries. (defaults to `none`). Possible choices:
 //!     * `0` - All queries are allowed.
@@ -44,8 +51,16 @@
 //! - `keepalive` - TCP keep alive timeout in milliseconds.
 //! - `nodelay` - Whether to enable `TCP_NODELAY` (defaults to `true`).
 //!
-//! - `pool_min` - Lower bound of opened connections for `Pool` (defaults to `10`).
+//! - `pool_min` - Lower bound of opened connections for `Pool`, opened upfront by `Pool::connect`
+//!   (defaults to `10`).
 //! - `pool_max` - Upper bound of opened connections for `Pool` (defaults to `20`).
+//! - `idle_timeout` - Idle connections of `Pool` are closed after this time (defaults to `none`).
+//! - `max_lifetime` - Connections of `Pool` are closed after this time since opening (defaults to `none`).
+//! - `health_check_interval` - Interval of pinging idle connections of `Pool` in background,
+//!   broken and expired ones are replaced up to `pool_min` (defaults to `none`).
+//! - `checkout_timeout` - Timeout for getting a connection from `Pool` (defaults to `none`).
+//! - `max_waiters` - Upper bound of tasks waiting for a connection from `Pool`, the rest fail
+//!   immediately (defaults to `none`).
 //!
 //! - `ping_before_query` - Ping server every time before execute any query. (defaults to `true`).
 //! - `send_retries` - Count of retry to send request to server. (defaults to `3`).
@@ -53,6 +68,13 @@
 //! - `ping_timeout` - Timeout for ping (defaults to `500 ms`).
 //!
 //! - `alt_hosts` - Comma separated list of single address host for load-balancing.
+//! - `load_balancing` - Strategy of picking a host for a new connection (defaults to `round_robin`):
+//!     * `round_robin`
+//!     * `random`
+//!     * `in_order` - the first available host
+//!     * `least_connections`
+//! - `host_quarantine` - Time a host is skipped for after a failed connect, doubles with each consecutive
+//!   failure up to 32 times (defaults to `none`).
 //!
 //! example:
 //! ```url
@@ -68,6 +90,14 @@
 //! - `async_std` — I/O based on [async-std](https://async.rs/) (doesn't work together with `tokio_io`).
 //! - `tls-native-tls` — TLS support with native-tls (allowed only with `tokio_io`).
 //! - `tls-rustls` — TLS support with rustls (allowed only with `tokio_io`).
+//! - `serde` — reading rows into `Deserialize` types with `QueryResult::fetch_all_as`,
+//!   `QueryResult::stream_as` or `Row::deserialize` and building blocks out of `Serialize` types
+//!   with `Block::from_rows`.
+//! - `arrow` — converting blocks and columns to Arrow arrays with `Block::to_record_batch` or
+//!   `Column::to_arrow` and building blocks out of record batches with `Block::from_record_batch`.
+//! - `json` — reading `JSON` and other cells as `serde_json::Value` and building `JSON` columns
+//!   out of `Vec<serde_json::Value>`.
+//! - `metrics` — reporting `Pool::status` counters and gauges to the [metrics](https://docs.rs/metrics) facade.
 //!
 //! ### Example
 //!
@@ -106,13 +136,77 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ### Mapping rows to structs
+//!
+//! `#[derive(Row)]` reads rows into structs and builds blocks out of vectors of them.
+//! Fields map to columns of the same name; use `#[clickhouse(rename = "...")]` to pick another
+//! column and `#[clickhouse(skip)]` to ignore a field.
+//!
+//! ```rust
+//! # use std::env;
+//! use clickhouse_rs::{types::FromRow, Block, Pool, Row, errors::Error};
+//!
+//! #[derive(Row)]
+//! struct Payment {
+//!     customer_id: u32,
+//!     amount: u32,
+//!     #[clickhouse(rename = "account_name")]
+//!     name: Option<String>,
+//!     #[clickhouse(skip)]
+//!     note: String,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let payments = vec![
+//!         Payment { customer_id: 1, amount: 2, name: Some("foo".into()), note: String::new() },
+//!         Payment { customer_id: 3, amount: 4, name: None, note: String::new() },
+//!     ];
+//!
+//!     # let database_url = env::var("DATABASE_URL").unwrap_or("tcp://localhost:9000?compression=lz4".into());
+//!     let pool = Pool::new(database_url);
+//!
+//!     let mut client = pool.get_handle().await?;
+//!     # client.execute("CREATE TABLE IF NOT EXISTS payment (customer_id UInt32, amount UInt32, account_name Nullable(FixedString(3))) Engine=Memory").await?;
+//!     client.insert("payment", Block::from(payments)).await?;
+//!     let block = client.query("SELECT * FROM payment").fetch_all().await?;
+//!
+//!     for row in block.rows() {
+//!         let payment = Payment::from_row(&row)?;
+//!         println!("Found payment {}: {}", payment.customer_id, payment.amount);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! `Enum8` and `Enum16` cells can be read as `&str` or `String` names. `#[derive(Enum)]` maps a
+//! Rust enum to them by variant names, so it can be used in rows, `row!` and block columns;
+//! on insert the names are checked against the enum definition of the table.
+//!
+//! ```rust
+//! use clickhouse_rs::Enum;
+//!
+//! #[derive(Enum)]
+//! enum Level {
+//!     Debug = 1,
+//!     Info = 2,
+//!     #[clickhouse(rename = "warn")]
+//!     Warning = 3,
+//! }
+//! ```
 
 #![recursion_limit = "1024"]
 
 #[cfg(all(feature = "tls-native-tls", feature = "tls-rustls"))]
 compile_error!("tls-native-tls and tls-rustls are mutually exclusive and cannot be enabled together");
 
-use std::{fmt, future::Future, time::Duration};
+use std::{
+    fmt,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures_util::{
     future, future::BoxFuture, future::FutureExt, stream, stream::BoxStream, StreamExt,
@@ -120,15 +214,17 @@ use futures_util::{
 use log::{info, warn};
 
 use crate::{
+    binary::protocol,
     connecting_stream::ConnectingStream,
     errors::{DriverError, Error, Result},
     io::ClickhouseTransport,
-    pool::PoolBinding,
+    pool::{HostGuard, PoolBinding},
     retry_guard::retry_guard,
     types::{
         block::{ChunkIterator, INSERT_BLOCK_SIZE},
         query_result::stream_blocks::BlockStream,
-        Cmd, Context, IntoOptions, OptionsSource, Packet, Query, QueryResult, SqlType,
+        Callbacks, Cmd, Context, Inserter, IntoOptions, LogRecord, OptionsSource, Packet, Progress, Query,
+        QueryResult, SqlType,
     },
 };
 pub use crate::{
@@ -136,6 +232,7 @@ pub use crate::{
     pool::Pool,
     types::{block::Block, Options, Simple},
 };
+pub use clickhouse_rs_derive::{Enum, Row};
 use crate::types::ProfileInfo;
 
 mod binary;
@@ -245,7 +342,12 @@ pub struct ClientHandle {
     inner: Option<ClickhouseTransport>,
     context: Context,
     pool: PoolBinding,
-    profile: Option<ProfileInfo>
+    profile: Option<ProfileInfo>,
+    totals: Option<Block>,
+    extremes: Option<Block>,
+    callbacks: Callbacks,
+    created: Instant,
+    host: Option<HostGuard>,
 }
 
 impl ClientHandle {
@@ -256,6 +358,51 @@ impl ClientHandle {
     pub fn get_profile_info(&self)->Option<ProfileInfo>{
         self.profile
     }
+
+    /// Returns the `WITH TOTALS` row sent by the server for the last query,
+    /// available once its stream of blocks or rows is finished.
+    pub fn totals(&self) -> Option<&Block> {
+        self.totals.as_ref()
+    }
+
+    /// Returns minimum and maximum values sent by the server for the last query
+    /// if `extremes` setting is enabled, available once its stream is finished.
+    pub fn extremes(&self) -> Option<&Block> {
+        self.extremes.as_ref()
+    }
+
+    /// Sets a callback that is invoked on every progress packet received
+    /// while executing `query`, `execute` or `insert` on this handle.
+    ///
+    /// The callback is dropped when the handle is returned to the pool.
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        self.callbacks.progress = Some(Arc::new(callback));
+    }
+
+    /// Removes the callback set by `set_progress_callback`.
+    pub fn clear_progress_callback(&mut self) {
+        self.callbacks.progress = None;
+    }
+
+    /// Sets a callback that receives server log records sent when
+    /// `send_logs_level` setting is enabled.
+    ///
+    /// Without the callback records are written to the `log` crate
+    /// with `clickhouse_rs::server_log` target.
+    pub fn set_server_log_callback<F>(&mut self, callback: F)
+    where
+        F: Fn(LogRecord) + Send + Sync + 'static,
+    {
+        self.callbacks.server_log = Some(Arc::new(callback));
+    }
+
+    /// Removes the callback set by `set_server_log_callback`.
+    pub fn clear_server_log_callback(&mut self) {
+        self.callbacks.server_log = None;
+    }
 }
 
 impl fmt::Debug for ClientHandle {
@@ -275,7 +422,7 @@ impl Client {
 
     pub(crate) async fn open(source: OptionsSource, pool: Option<Pool>) -> Result<ClientHandle> {
         let options = try_opt!(source.get());
-        let compress = options.compression;
+        let compress = options.compression.is_enabled();
         let timeout = options.connection_timeout;
 
         let context = Context {
@@ -283,13 +430,17 @@ impl Client {
             ..Context::default()
         };
 
-        with_timeout(
-            async move {
-                let addr = match &pool {
-                    None => &options.addr,
-                    Some(p) => p.get_addr(),
-                };
+        let (host, addr) = match &pool {
+            None => (None, options.addr.clone()),
+            Some(p) => {
+                let (index, addr) = p.get_addr();
+                (Some((p.inner.clone(), index)), addr.clone())
+            }
+        };
 
+        let mut result = with_timeout(
+            async move {
+                let addr = &addr;
                 info!("try to connect to {}", addr);
                 if addr.port() == Some(8123) {
                     warn!("You should use port 9000 instead of 8123 because clickhouse-rs work through the binary interface.");
@@ -306,7 +457,12 @@ impl Client {
                         None => PoolBinding::None,
                         Some(p) => PoolBinding::Detached(p),
                     },
-                    profile: None
+                    profile: None,
+                    totals: None,
+                    extremes: None,
+                    callbacks: Callbacks::default(),
+                    created: Instant::now(),
+                    host: None,
                 };
 
                 handle.hello().await?;
@@ -314,7 +470,15 @@ impl Client {
             },
             timeout,
         )
-        .await
+        .await;
+
+        if let Some((inner, index)) = host {
+            match &mut result {
+                Ok(handle) => handle.host = Some(inner.hosts.connected(index)),
+                Err(_) => inner.hosts.failed(index),
+            }
+        }
+        result
     }
 }
 
@@ -340,8 +504,15 @@ impl ClientHandle {
             }
         }
 
-        self.inner = h;
         self.context.server_info = info.unwrap();
+        self.inner = match h {
+            Some(transport)
+                if self.context.revision() >= protocol::DBMS_MIN_PROTOCOL_VERSION_WITH_ADDENDUM =>
+            {
+                Some(transport.send_cmd(Cmd::Addendum).await?)
+            }
+            h => h,
+        };
         Ok(())
     }
 
@@ -391,6 +562,7 @@ impl ClientHandle {
         QueryResult {
             client: self,
             query,
+            cancel: Default::default(),
         }
     }
 
@@ -412,7 +584,9 @@ impl ClientHandle {
             .execute_timeout
             .unwrap_or_else(|| Duration::from_secs(0));
         let context = self.context.clone();
+        let callbacks = self.callbacks.clone();
         let query = Query::from(sql);
+        query.check_external_tables()?;
         with_timeout(
             async {
                 self.wrap_future(move |c| {
@@ -430,9 +604,13 @@ impl ClientHandle {
                         while let Some(packet) = stream.next().await {
                             match packet {
                                 Ok(Packet::Eof(inner)) => h = Some(inner),
+                                Ok(Packet::Progress(p)) => callbacks.on_progress(p),
+                                Ok(Packet::ServerLog(records)) => callbacks.on_server_log(records),
                                 Ok(Packet::Block(_))
-                                | Ok(Packet::ProfileInfo(_))
-                                | Ok(Packet::Progress(_)) => (),
+                                | Ok(Packet::ProfileEvents(_))
+                                | Ok(Packet::Totals(_))
+                                | Ok(Packet::Extremes(_))
+                                | Ok(Packet::ProfileInfo(_)) => (),
                                 Ok(Packet::Exception(e)) => return Err(Error::Server(e)),
                                 Err(e) => return Err(Error::Io(e)),
                                 _ => return Err(Error::Driver(DriverError::UnexpectedPacket)),
@@ -461,12 +639,21 @@ impl ClientHandle {
         Ok(())
     }
 
+    /// Starts an insert of rows that are sent incrementally, see [`Inserter`](types::Inserter).
+    pub fn inserter<Q>(&mut self, table: Q) -> Inserter<'_>
+    where
+        Query: From<Q>,
+    {
+        Inserter::new(self, Query::from(table))
+    }
+
     async fn insert_(&mut self, query: Query, block: &Block) -> Result<ClickhouseTransport> {
         let timeout = try_opt!(self.context.options.get())
             .insert_timeout
             .unwrap_or_else(|| Duration::from_secs(0));
 
         let context = self.context.clone();
+        let callbacks = self.callbacks.clone();
 
         with_timeout(
             async {
@@ -475,16 +662,25 @@ impl ClientHandle {
                     let transport = c.get_inner();
 
                     async move {
+                        let callbacks = &callbacks;
                         let transport = transport?.clear().await?;
-                        let (transport, dst_block) =
-                            Self::send_insert_query_(transport, context.clone(), query.clone())
-                                .await?;
+                        let (transport, dst_block) = Self::send_insert_query_(
+                            transport,
+                            context.clone(),
+                            query.clone(),
+                            callbacks,
+                        )
+                        .await?;
                         let casted_block = block.cast_to(&dst_block)?;
                         let mut chunks = casted_block.chunks(INSERT_BLOCK_SIZE);
-                        let transport =
-                            Self::insert_block_(transport, context.clone(), chunks.next().unwrap())
-                                .await?;
-                        Self::insert_tail_(transport, context, query, chunks).await
+                        let transport = Self::insert_block_(
+                            transport,
+                            context.clone(),
+                            chunks.next().unwrap(),
+                            callbacks,
+                        )
+                        .await?;
+                        Self::insert_tail_(transport, context, query, chunks, callbacks).await
                     }
                 })
                 .await
@@ -499,11 +695,13 @@ impl ClientHandle {
         context: Context,
         query: Query,
         chunks: ChunkIterator<Simple>,
+        callbacks: &Callbacks,
     ) -> Result<ClickhouseTransport> {
         for chunk in chunks {
             let (transport_, _) =
-                Self::send_insert_query_(transport, context.clone(), query.clone()).await?;
-            transport = Self::insert_block_(transport_, context.clone(), chunk).await?;
+                Self::send_insert_query_(transport, context.clone(), query.clone(), callbacks)
+                    .await?;
+            transport = Self::insert_block_(transport_, context.clone(), chunk, callbacks).await?;
         }
         Ok(transport)
     }
@@ -512,9 +710,10 @@ impl ClientHandle {
         transport: ClickhouseTransport,
         context: Context,
         query: Query,
+        callbacks: &Callbacks,
     ) -> Result<(ClickhouseTransport, Block)> {
         let stream = transport.call(Cmd::SendQuery(query, context));
-        let (transport, b) = stream.read_block().await?;
+        let (transport, b) = stream.read_block(callbacks).await?;
         let dst_block = b.unwrap();
         Ok((transport, dst_block))
     }
@@ -523,12 +722,13 @@ impl ClientHandle {
         transport: ClickhouseTransport,
         context: Context,
         block: Block,
+        callbacks: &Callbacks,
     ) -> Result<ClickhouseTransport> {
         let send_cmd = Cmd::Union(
             Box::new(Cmd::SendData(block, context.clone())),
             Box::new(Cmd::SendData(Block::default(), context)),
         );
-        let (transport, _) = transport.call(send_cmd).read_block().await?;
+        let (transport, _) = transport.call(send_cmd).read_block(callbacks).await?;
         Ok(transport)
     }
 
@@ -541,7 +741,9 @@ impl ClientHandle {
             names.push(try_opt!(column_name_to_string(column.name())));
         }
         let fields = names.join(", ");
-        Ok(Query::from(table).map_sql(|table| format!("INSERT INTO {table} ({fields}) VALUES")))
+        let query = Query::from(table);
+        query.check_external_tables()?;
+        Ok(query.map_sql(|table| format!("INSERT INTO {table} ({fields}) VALUES")))
     }
 
     pub(crate) async fn wrap_future<T, R, F>(&mut self, f: F) -> Result<T>
@@ -560,32 +762,28 @@ impl ClientHandle {
 
     pub(crate) fn wrap_stream<'a, F>(&'a mut self, f: F) -> BoxStream<'a, Result<Block>>
     where
-        F: (FnOnce(&'a mut Self) -> Result<BlockStream<'a>>) + Send + 'static,
+        F: (FnOnce(&'a mut Self) -> BoxFuture<'a, Result<BlockStream<'a>>>) + Send + 'static,
     {
         let ping_before_query = match self.context.options.get() {
             Ok(val) => val.ping_before_query,
             Err(err) => return Box::pin(stream::once(future::err(err))),
         };
 
-        if ping_before_query {
-            let fut: BoxFuture<'a, BoxStream<'a, Result<Block>>> = Box::pin(async move {
-                let inner: BoxStream<'a, Result<Block>> = match self.check_connection().await {
-                    Ok(_) => match f(self) {
-                        Ok(s) => Box::pin(s),
-                        Err(err) => Box::pin(stream::once(future::err(err))),
-                    },
-                    Err(err) => Box::pin(stream::once(future::err(err))),
-                };
-                inner
-            });
+        let fut: BoxFuture<'a, BoxStream<'a, Result<Block>>> = Box::pin(async move {
+            if ping_before_query {
+                if let Err(err) = self.check_connection().await {
+                    return Box::pin(stream::once(future::err(err))) as BoxStream<'a, Result<Block>>;
+                }
+            }
 
-            Box::pin(fut.flatten_stream())
-        } else {
-            match f(self) {
+            let inner: BoxStream<'a, Result<Block>> = match f(self).await {
                 Ok(s) => Box::pin(s),
                 Err(err) => Box::pin(stream::once(future::err(err))),
-            }
-        }
+            };
+            inner
+        });
+
+        Box::pin(fut.flatten_stream())
     }
 
     /// Check connection and try to reconnect if necessary.
@@ -2,22 +2,42 @@ use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Instant,
 };
 
-use pin_project::pin_project;
+use futures_util::{future::BoxFuture, FutureExt};
 
-use crate::{errors::Result, pool::Pool, ClientHandle};
+use crate::{
+    errors::{ConnectionError, Error, Result},
+    pool::{sleep, Pool},
+    ClientHandle,
+};
 
 /// Future that resolves to a `ClientHandle`.
-#[pin_project]
+///
+/// Fails with `ConnectionError::CheckoutTimeout` if `checkout_timeout` is elapsed
+/// and with `ConnectionError::TooManyWaiters` if `max_waiters` tasks are already waiting.
 pub struct GetHandle {
-    #[pin]
     pool: Pool,
+    start: Instant,
+    waiter: Option<u64>,
+    timeout: Option<BoxFuture<'static, ()>>,
 }
 
 impl GetHandle {
     pub(crate) fn new(pool: &Pool) -> Self {
-        Self { pool: pool.clone() }
+        Self {
+            pool: pool.clone(),
+            start: Instant::now(),
+            waiter: None,
+            timeout: None,
+        }
+    }
+
+    fn cancel(&mut self) {
+        if let Some(id) = self.waiter.take() {
+            self.pool.inner.leave_queue(id, true);
+        }
     }
 }
 
@@ -25,6 +45,39 @@ impl Future for GetHandle {
     type Output = Result<ClientHandle>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.project().pool.poll(cx)
+        let this = self.get_mut();
+        match this.pool.poll(cx, &mut this.waiter) {
+            Poll::Ready(Ok(client)) => {
+                this.pool.inner.stats.checkout(this.start.elapsed());
+                Poll::Ready(Ok(client))
+            }
+            Poll::Ready(Err(err)) => {
+                this.cancel();
+                Poll::Ready(Err(err))
+            }
+            Poll::Pending => {
+                let checkout_timeout = match this.pool.inner.checkout_timeout {
+                    Some(checkout_timeout) => checkout_timeout,
+                    None => return Poll::Pending,
+                };
+                let start = this.start;
+                let timeout = this.timeout.get_or_insert_with(|| {
+                    Box::pin(sleep(checkout_timeout.saturating_sub(start.elapsed())))
+                });
+                match timeout.poll_unpin(cx) {
+                    Poll::Ready(()) => {
+                        this.cancel();
+                        Poll::Ready(Err(Error::Connection(ConnectionError::CheckoutTimeout)))
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+impl Drop for GetHandle {
+    fn drop(&mut self) {
+        self.cancel();
     }
 }
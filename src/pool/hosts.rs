@@ -0,0 +1,231 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    sync::{
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use url::Url;
+
+use crate::types::LoadBalancing;
+
+/// Back-off of a host stops growing after this many consecutive failures.
+const MAX_BACKOFF_SHIFT: u32 = 5;
+
+/// Hosts of a pool with their connection counts and quarantines.
+pub(crate) struct Hosts {
+    hosts: Vec<Arc<Host>>,
+    strategy: LoadBalancing,
+    quarantine: Option<Duration>,
+    next: AtomicUsize,
+}
+
+struct Host {
+    url: Url,
+    connections: AtomicUsize,
+    failures: AtomicU32,
+    quarantined_until: Mutex<Option<Instant>>,
+}
+
+/// Open connection to a host, keeps the host's connection count up to date.
+pub(crate) struct HostGuard {
+    host: Arc<Host>,
+}
+
+impl Drop for HostGuard {
+    fn drop(&mut self) {
+        self.host.connections.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl Host {
+    fn quarantined_until(&self) -> Option<Instant> {
+        *self
+            .quarantined_until
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn set_quarantined_until(&self, until: Option<Instant>) {
+        *self
+            .quarantined_until
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = until;
+    }
+
+    fn is_available(&self, now: Instant) -> bool {
+        self.quarantined_until().is_none_or(|until| until <= now)
+    }
+}
+
+impl Hosts {
+    pub(crate) fn new(
+        urls: Vec<Url>,
+        strategy: LoadBalancing,
+        quarantine: Option<Duration>,
+    ) -> Self {
+        let hosts = urls
+            .into_iter()
+            .map(|url| {
+                Arc::new(Host {
+                    url,
+                    connections: AtomicUsize::new(0),
+                    failures: AtomicU32::new(0),
+                    quarantined_until: Mutex::new(None),
+                })
+            })
+            .collect();
+
+        Self {
+            hosts,
+            strategy,
+            quarantine,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn url(&self, index: usize) -> &Url {
+        &self.hosts[index].url
+    }
+
+    /// Picks a host for a new connection by the strategy, skipping quarantined hosts
+    /// unless all of them are, then the one released the soonest is used.
+    pub(crate) fn select(&self) -> usize {
+        let n = self.hosts.len();
+        let start = match self.strategy {
+            LoadBalancing::RoundRobin | LoadBalancing::LeastConnections => {
+                self.next.fetch_add(1, Ordering::SeqCst) % n
+            }
+            LoadBalancing::Random => random(self.next.fetch_add(1, Ordering::SeqCst)) % n,
+            LoadBalancing::InOrder => 0,
+        };
+
+        let now = Instant::now();
+        let mut available = (0..n)
+            .map(|i| (start + i) % n)
+            .filter(|&i| self.hosts[i].is_available(now));
+
+        let index = match self.strategy {
+            LoadBalancing::LeastConnections => {
+                available.min_by_key(|&i| self.hosts[i].connections.load(Ordering::Acquire))
+            }
+            _ => available.next(),
+        };
+
+        index.unwrap_or_else(|| {
+            (0..n)
+                .min_by_key(|&i| self.hosts[i].quarantined_until())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Records a successful connect to the host, lifting its quarantine.
+    pub(crate) fn connected(&self, index: usize) -> HostGuard {
+        let host = &self.hosts[index];
+        host.connections.fetch_add(1, Ordering::AcqRel);
+        if host.failures.swap(0, Ordering::AcqRel) > 0 {
+            host.set_quarantined_until(None);
+        }
+        HostGuard { host: host.clone() }
+    }
+
+    /// Records a failed connect to the host, quarantining it if `host_quarantine` is set.
+    pub(crate) fn failed(&self, index: usize) {
+        let host = &self.hosts[index];
+        let failures = host.failures.fetch_add(1, Ordering::AcqRel) + 1;
+        if let Some(quarantine) = self.quarantine {
+            let backoff = quarantine * (1_u32 << (failures - 1).min(MAX_BACKOFF_SHIFT));
+            host.set_quarantined_until(Some(Instant::now() + backoff));
+        }
+    }
+}
+
+fn random(seed: usize) -> usize {
+    RandomState::new().hash_one(seed) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use std::{str::FromStr, time::Duration};
+
+    use url::Url;
+
+    use super::Hosts;
+    use crate::types::LoadBalancing;
+
+    fn hosts(strategy: LoadBalancing, quarantine: Option<Duration>) -> Hosts {
+        let urls = ["tcp://host1:9000", "tcp://host2:9000", "tcp://host3:9000"]
+            .iter()
+            .map(|url| Url::from_str(url).unwrap())
+            .collect();
+        Hosts::new(urls, strategy, quarantine)
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let hosts = hosts(LoadBalancing::RoundRobin, Some(Duration::from_secs(60)));
+        assert_eq!(hosts.select(), 0);
+        assert_eq!(hosts.select(), 1);
+        assert_eq!(hosts.select(), 2);
+
+        hosts.failed(1);
+        assert_eq!(hosts.select(), 0);
+        assert_eq!(hosts.select(), 2);
+        assert_eq!(hosts.select(), 2);
+
+        let _guard = hosts.connected(1);
+        assert_eq!(hosts.select(), 0);
+        assert_eq!(hosts.select(), 1);
+    }
+
+    #[test]
+    fn test_in_order() {
+        let hosts = hosts(LoadBalancing::InOrder, Some(Duration::from_secs(60)));
+        assert_eq!(hosts.select(), 0);
+        assert_eq!(hosts.select(), 0);
+
+        hosts.failed(0);
+        assert_eq!(hosts.select(), 1);
+
+        hosts.failed(1);
+        hosts.failed(2);
+        assert_eq!(hosts.select(), 0);
+    }
+
+    #[test]
+    fn test_least_connections() {
+        let hosts = hosts(LoadBalancing::LeastConnections, None);
+        let first = hosts.connected(0);
+        let _second = hosts.connected(0);
+        let _third = hosts.connected(1);
+        assert_eq!(hosts.select(), 2);
+
+        let _fourth = hosts.connected(2);
+        let _fifth = hosts.connected(2);
+        assert_eq!(hosts.select(), 1);
+
+        drop(first);
+        let _sixth = hosts.connected(1);
+        assert_eq!(hosts.select(), 0);
+    }
+
+    #[test]
+    fn test_random() {
+        let hosts = hosts(LoadBalancing::Random, Some(Duration::from_secs(60)));
+        hosts.failed(0);
+        hosts.failed(2);
+        for _ in 0..10 {
+            assert_eq!(hosts.select(), 1);
+        }
+    }
+
+    #[test]
+    fn test_no_quarantine() {
+        let hosts = hosts(LoadBalancing::InOrder, None);
+        hosts.failed(0);
+        assert_eq!(hosts.select(), 0);
+    }
+}
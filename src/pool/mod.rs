@@ -1,48 +1,160 @@
 use std::{
     fmt, mem,
-    pin::Pin,
     sync::atomic::{self, Ordering},
-    sync::Arc,
-    task::{Context, Poll, Waker},
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
-use futures_util::future::BoxFuture;
+use futures_util::future::{self, BoxFuture};
 use log::{error, warn};
 
 use crate::{
-    errors::Result,
-    types::{IntoOptions, OptionsSource},
+    errors::{ConnectionError, Error, Result},
+    types::{IntoOptions, LoadBalancing, OptionsSource},
     Client, ClientHandle,
 };
 
-pub use self::futures::GetHandle;
+pub(crate) use self::hosts::HostGuard;
+pub use self::{futures::GetHandle, stats::PoolStatus};
+use self::{hosts::Hosts, signal::Signal, stats::Stats, waiters::Waiters};
 use futures_util::FutureExt;
 use url::Url;
 
 mod futures;
+mod hosts;
+mod signal;
+mod stats;
+mod waiters;
 
 pub(crate) struct Inner {
     new: crossbeam::queue::ArrayQueue<BoxFuture<'static, Result<ClientHandle>>>,
-    idle: crossbeam::queue::ArrayQueue<ClientHandle>,
-    tasks: crossbeam::queue::SegQueue<Waker>,
+    idle: crossbeam::queue::ArrayQueue<IdleHandle>,
+    waiters: Waiters,
     ongoing: atomic::AtomicUsize,
-    hosts: Vec<Url>,
-    connections_num: atomic::AtomicUsize,
+    connecting: atomic::AtomicUsize,
+    pub(crate) hosts: Hosts,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+    health_check_interval: Option<Duration>,
+    health_check_started: atomic::AtomicBool,
+    checkout_timeout: Option<Duration>,
+    max_waiters: Option<usize>,
+    stats: Stats,
+    replenish: Arc<Signal>,
+    replenish_started: atomic::AtomicBool,
+}
+
+/// Connection kept in the pool with the moment it became idle.
+struct IdleHandle {
+    client: ClientHandle,
+    since: Instant,
 }
 
 impl Inner {
     pub(crate) fn release_conn(&self) {
         self.ongoing.fetch_sub(1, Ordering::AcqRel);
-        while let Some(task) = self.tasks.pop() {
-            task.wake()
+        self.connection_closed();
+        self.waiters.notify_one();
+        self.report_gauges();
+    }
+
+    /// Removes a waiter that got a connection or gave up waiting from the queue.
+    pub(crate) fn leave_queue(&self, id: u64, cancelled: bool) {
+        let passed_on = if cancelled {
+            self.waiters.cancel(id)
+        } else {
+            self.waiters.remove(id);
+            false
+        };
+
+        // NOTE: the connection being opened is polled only with the waker
+        // of the task that polled it last, so the next waiter takes it over
+        if !passed_on && !self.new.is_empty() {
+            self.waiters.notify_one();
         }
     }
 
+    fn connection_closed(&self) {
+        self.stats.connection_closed();
+        self.replenish.notify();
+    }
+
+    fn status(&self) -> PoolStatus {
+        let mut status = PoolStatus {
+            idle: self.idle.len(),
+            in_use: self.ongoing.load(Ordering::Acquire),
+            waiters: self.waiters.len(),
+            connecting: self.new.len() + self.connecting.load(Ordering::Acquire),
+            ..PoolStatus::default()
+        };
+        self.stats.fill(&mut status);
+        status
+    }
+
+    #[cfg(feature = "metrics")]
+    fn report_gauges(&self) {
+        stats::report_gauges(&self.status());
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    fn report_gauges(&self) {}
+
     fn conn_count(&self) -> usize {
         let is_new_some = self.new.len();
         let ongoing = self.ongoing.load(Ordering::Acquire);
+        let connecting = self.connecting.load(Ordering::Acquire);
         let idle_count = self.idle.len();
-        is_new_some + idle_count + ongoing
+        is_new_some + idle_count + ongoing + connecting
+    }
+
+    /// Counts connections missing up to `min` as being opened, the check and
+    /// the update are one atomic step so concurrent callers don't open extra ones.
+    fn reserve_connecting(&self, min: usize) -> usize {
+        let mut connecting = self.connecting.load(Ordering::Acquire);
+        loop {
+            let opened = self.new.len() + self.idle.len() + self.ongoing.load(Ordering::Acquire);
+            let missing = min.saturating_sub(opened + connecting);
+            if missing == 0 {
+                return 0;
+            }
+
+            match self.connecting.compare_exchange_weak(
+                connecting,
+                connecting + missing,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return missing,
+                Err(current) => connecting = current,
+            }
+        }
+    }
+
+    fn push_idle(&self, mut client: ClientHandle) {
+        client.pool = PoolBinding::None;
+        let _ = self.idle.push(IdleHandle {
+            client,
+            since: Instant::now(),
+        });
+    }
+
+    fn is_expired(&self, client: &ClientHandle) -> bool {
+        self.max_lifetime
+            .is_some_and(|lifetime| client.created.elapsed() >= lifetime)
+    }
+
+    fn is_stale(&self, idle: &IdleHandle) -> bool {
+        self.idle_timeout
+            .is_some_and(|timeout| idle.since.elapsed() >= timeout)
+            || self.is_expired(&idle.client)
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // lets the replenishing task see that the pool is gone
+        self.replenish.notify();
     }
 }
 
@@ -105,24 +217,16 @@ pub struct Pool {
     max: usize,
 }
 
-#[derive(Debug)]
-struct PoolInfo {
-    new_len: usize,
-    idle_len: usize,
-    tasks_len: usize,
-    ongoing: usize,
-}
-
 impl fmt::Debug for Pool {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let info = self.info();
+        let status = self.status();
         f.debug_struct("Pool")
             .field("min", &self.min)
             .field("max", &self.max)
-            .field("new connections count", &info.new_len)
-            .field("idle connections count", &info.idle_len)
-            .field("tasks count", &info.tasks_len)
-            .field("ongoing connections count", &info.ongoing)
+            .field("connecting count", &status.connecting)
+            .field("idle connections count", &status.idle)
+            .field("tasks count", &status.waiters)
+            .field("ongoing connections count", &status.in_use)
             .finish()
     }
 }
@@ -138,6 +242,13 @@ impl Pool {
         let mut min = 5;
         let mut max = 10;
         let mut hosts = vec![];
+        let mut load_balancing = LoadBalancing::default();
+        let mut host_quarantine = None;
+        let mut idle_timeout = None;
+        let mut max_lifetime = None;
+        let mut health_check_interval = None;
+        let mut checkout_timeout = None;
+        let mut max_waiters = None;
 
         match options_src.get() {
             Ok(opt) => {
@@ -145,6 +256,13 @@ impl Pool {
                 max = opt.pool_max;
                 hosts.push(opt.addr.clone());
                 hosts.extend(opt.alt_hosts.iter().cloned());
+                load_balancing = opt.load_balancing;
+                host_quarantine = opt.host_quarantine;
+                idle_timeout = opt.idle_timeout;
+                max_lifetime = opt.max_lifetime;
+                health_check_interval = opt.health_check_interval;
+                checkout_timeout = opt.checkout_timeout;
+                max_waiters = opt.max_waiters;
             }
             Err(err) => error!("{}", err),
         }
@@ -161,10 +279,19 @@ impl Pool {
         let inner = Arc::new(Inner {
             new: crossbeam::queue::ArrayQueue::new(1),
             idle: crossbeam::queue::ArrayQueue::new(max),
-            tasks: crossbeam::queue::SegQueue::new(),
+            waiters: Waiters::default(),
             ongoing: atomic::AtomicUsize::new(0),
-            connections_num: atomic::AtomicUsize::new(0),
-            hosts,
+            connecting: atomic::AtomicUsize::new(0),
+            hosts: Hosts::new(hosts, load_balancing, host_quarantine),
+            idle_timeout,
+            max_lifetime,
+            health_check_interval,
+            health_check_started: atomic::AtomicBool::new(false),
+            checkout_timeout,
+            max_waiters,
+            stats: Stats::default(),
+            replenish: Arc::default(),
+            replenish_started: atomic::AtomicBool::new(false),
         });
 
         Self {
@@ -175,13 +302,30 @@ impl Pool {
         }
     }
 
-    fn info(&self) -> PoolInfo {
-        PoolInfo {
-            new_len: self.inner.new.len(),
-            idle_len: self.inner.idle.len(),
-            tasks_len: self.inner.tasks.len(),
-            ongoing: self.inner.ongoing.load(Ordering::Acquire),
-        }
+    /// Constructs a new Pool and opens `pool_min` connections, see `warm_up`.
+    pub async fn connect<O>(options: O) -> Result<Self>
+    where
+        O: IntoOptions,
+    {
+        let pool = Self::new(options);
+        pool.warm_up().await?;
+        Ok(pool)
+    }
+
+    /// Opens connections up to `pool_min` and returns the first error if any fails.
+    ///
+    /// After that the pool opens connections in background to replace closed ones
+    /// whenever there are fewer than `pool_min`.
+    pub async fn warm_up(&self) -> Result<()> {
+        let result = self.open_to_min().await;
+        self.start_replenish();
+        self.start_health_check();
+        result
+    }
+
+    /// Returns current connection counts and lifetime counters of the pool.
+    pub fn status(&self) -> PoolStatus {
+        self.inner.status()
     }
 
     /// Returns future that resolves to `ClientHandle`.
@@ -189,29 +333,46 @@ impl Pool {
         GetHandle::new(self)
     }
 
-    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<ClientHandle>> {
+    fn poll(
+        &mut self,
+        cx: &mut Context<'_>,
+        waiter: &mut Option<u64>,
+    ) -> Poll<Result<ClientHandle>> {
+        self.start_health_check();
         self.handle_futures(cx)?;
 
-        match self.take_conn() {
-            Some(client) => Poll::Ready(Ok(client)),
-            None => {
-                let new_conn_created = {
-                    let conn_count = self.inner.conn_count();
-
-                    if conn_count < self.max && self.inner.new.push(self.new_connection()).is_ok() {
-                        true
-                    } else {
-                        self.inner.tasks.push(cx.waker().clone());
-                        false
-                    }
-                };
-                if new_conn_created {
-                    self.poll(cx)
-                } else {
-                    Poll::Pending
+        if self.inner.waiters.is_turn(*waiter) {
+            if let Some(client) = self.take_conn() {
+                if let Some(id) = waiter.take() {
+                    self.inner.leave_queue(id, false);
                 }
+                self.inner.report_gauges();
+                return Poll::Ready(Ok(client));
+            }
+
+            let conn_count = self.inner.conn_count();
+            if conn_count < self.max && self.inner.new.push(self.new_connection()).is_ok() {
+                return self.poll(cx, waiter);
             }
         }
+
+        match self
+            .inner
+            .waiters
+            .register(*waiter, cx.waker(), self.inner.max_waiters)
+        {
+            Some(id) => *waiter = Some(id),
+            None => return Poll::Ready(Err(Error::Connection(ConnectionError::TooManyWaiters))),
+        }
+
+        // NOTE: a connection could be released after the check above
+        // but before the waiter is queued, so nobody would be notified
+        let can_connect = self.inner.new.is_empty() && self.inner.conn_count() < self.max;
+        if !self.inner.idle.is_empty() || can_connect {
+            self.inner.waiters.notify_one();
+        }
+        self.inner.report_gauges();
+        Poll::Pending
     }
 
     fn new_connection(&self) -> BoxFuture<'static, Result<ClientHandle>> {
@@ -224,7 +385,9 @@ impl Pool {
         if let Some(mut new) = self.inner.new.pop() {
             match new.poll_unpin(cx) {
                 Poll::Ready(Ok(client)) => {
-                    self.inner.idle.push(client).unwrap();
+                    self.inner.stats.connection_created();
+                    self.inner.push_idle(client);
+                    self.inner.waiters.notify_one();
                 }
                 Poll::Pending => {
                     // NOTE: it is okay to drop the construction task
@@ -233,6 +396,8 @@ impl Pool {
                     let _ = self.inner.new.push(new);
                 }
                 Poll::Ready(Err(err)) => {
+                    self.inner.stats.connect_failed();
+                    self.inner.waiters.notify_one();
                     return Err(err);
                 }
             }
@@ -242,14 +407,19 @@ impl Pool {
     }
 
     fn take_conn(&mut self) -> Option<ClientHandle> {
-        if let Some(mut client) = self.inner.idle.pop() {
+        while let Some(idle) = self.inner.idle.pop() {
+            if self.inner.is_stale(&idle) {
+                self.inner.connection_closed();
+                continue;
+            }
+
+            let mut client = idle.client;
             client.pool = PoolBinding::Attached(self.clone());
             client.set_inside(false);
             self.inner.ongoing.fetch_add(1, Ordering::AcqRel);
-            Some(client)
-        } else {
-            None
+            return Some(client);
         }
+        None
     }
 
     fn return_conn(&mut self, mut client: ClientHandle) {
@@ -259,23 +429,174 @@ impl Pool {
         client.pool = PoolBinding::None;
         client.set_inside(true);
 
-        if self.inner.idle.len() < min && is_attached && client.inner.is_some() {
-            let _ = self.inner.idle.push(client);
+        if self.inner.idle.len() < min
+            && is_attached
+            && client.inner.is_some()
+            && !self.inner.is_expired(&client)
+        {
+            self.inner.push_idle(client);
+        } else {
+            self.inner.connection_closed();
         }
         self.inner.ongoing.fetch_sub(1, Ordering::AcqRel);
+        self.inner.waiters.notify_one();
+        self.inner.report_gauges();
+    }
+
+    /// Starts pinging idle connections in background if `health_check_interval` is set,
+    /// the task stops once the pool is dropped.
+    fn start_health_check(&self) {
+        let interval = match self.inner.health_check_interval {
+            Some(interval) => interval,
+            None => return,
+        };
+        if self.inner.health_check_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let inner = Arc::downgrade(&self.inner);
+        let options = self.options.clone();
+        let (min, max) = (self.min, self.max);
+        spawn(async move {
+            loop {
+                sleep(interval).await;
+                match Pool::upgrade(&inner, &options, min, max) {
+                    Some(pool) => pool.check_health().await,
+                    None => break,
+                }
+            }
+        });
+    }
+
+    fn start_replenish(&self) {
+        if self.inner.replenish_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let signal = self.inner.replenish.clone();
+        let inner = Arc::downgrade(&self.inner);
+        let options = self.options.clone();
+        let (min, max) = (self.min, self.max);
+        spawn(async move {
+            loop {
+                signal.wait().await;
+                match Pool::upgrade(&inner, &options, min, max) {
+                    Some(pool) => {
+                        if let Err(err) = pool.open_to_min().await {
+                            warn!("[replenish] can't open connection: {}", err);
+                        }
+                    }
+                    None => break,
+                }
+            }
+        });
+    }
+
+    fn upgrade(
+        inner: &Weak<Inner>,
+        options: &OptionsSource,
+        min: usize,
+        max: usize,
+    ) -> Option<Self> {
+        Some(Self {
+            options: options.clone(),
+            inner: inner.upgrade()?,
+            min,
+            max,
+        })
+    }
+
+    /// Closes stale and broken idle connections and opens new ones up to `pool_min`.
+    async fn check_health(&self) {
+        for _ in 0..self.inner.idle.len() {
+            let mut idle = match self.inner.idle.pop() {
+                Some(idle) => idle,
+                None => break,
+            };
+            if self.inner.is_stale(&idle) {
+                self.inner.connection_closed();
+                continue;
+            }
+
+            self.inner.ongoing.fetch_add(1, Ordering::AcqRel);
+            let ping = idle.client.ping().await;
+            self.inner.ongoing.fetch_sub(1, Ordering::AcqRel);
+
+            match ping {
+                Ok(()) => {
+                    let _ = self.inner.idle.push(idle);
+                    self.inner.waiters.notify_one();
+                }
+                Err(err) => {
+                    self.inner.connection_closed();
+                    warn!("[health check] idle connection is broken: {}", err)
+                }
+            }
+        }
+
+        if let Err(err) = self.open_to_min().await {
+            warn!("[health check] can't open connection: {}", err);
+        }
+    }
+
+    /// Opens idle connections concurrently up to `pool_min`, returns the first error.
+    async fn open_to_min(&self) -> Result<()> {
+        let missing = self.inner.reserve_connecting(self.min);
+
+        let results = future::join_all((0..missing).map(|_| self.open_idle())).await;
+        self.inner.report_gauges();
+        results.into_iter().collect()
+    }
 
-        while let Some(task) = self.inner.tasks.pop() {
-            task.wake()
+    /// Opens a connection counted in `connecting` and puts it into the pool.
+    async fn open_idle(&self) -> Result<()> {
+        let client = Client::open(self.options.clone(), Some(self.clone())).await;
+
+        // NOTE: the connection leaves `connecting` only after it is idle,
+        // otherwise a concurrent `open_to_min` could count it as missing
+        match client {
+            Ok(client) => {
+                self.inner.stats.connection_created();
+                self.inner.push_idle(client);
+                self.inner.connecting.fetch_sub(1, Ordering::AcqRel);
+                self.inner.waiters.notify_one();
+                Ok(())
+            }
+            Err(err) => {
+                self.inner.connecting.fetch_sub(1, Ordering::AcqRel);
+                self.inner.stats.connect_failed();
+                Err(err)
+            }
         }
     }
 
-    pub(crate) fn get_addr(&self) -> &Url {
-        let n = self.inner.hosts.len();
-        let index = self.inner.connections_num.fetch_add(1, Ordering::SeqCst);
-        &self.inner.hosts[index % n]
+    /// Picks a host for a new connection by `load_balancing`, see `Hosts::select`.
+    pub(crate) fn get_addr(&self) -> (usize, &Url) {
+        let index = self.inner.hosts.select();
+        (index, self.inner.hosts.url(index))
     }
 }
 
+#[cfg(feature = "async_std")]
+fn spawn<F: std::future::Future<Output = ()> + Send + 'static>(future: F) {
+    async_std::task::spawn(future);
+}
+
+#[cfg(not(feature = "async_std"))]
+fn spawn<F: std::future::Future<Output = ()> + Send + 'static>(future: F) {
+    tokio::spawn(future);
+}
+
+#[cfg(feature = "async_std")]
+async fn sleep(duration: Duration) {
+    async_std::task::sleep(duration).await;
+}
+
+#[cfg(not(feature = "async_std"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
 impl Drop for ClientHandle {
     fn drop(&mut self) {
         if let (pool, Some(inner)) = (self.pool.take(), self.inner.take()) {
@@ -289,6 +610,11 @@ impl Drop for ClientHandle {
                 pool: pool.clone(),
                 context,
                 profile:None,
+                totals: None,
+                extremes: None,
+                callbacks: Default::default(),
+                created: self.created,
+                host: self.host.take(),
             };
             pool.return_conn(client);
         }
@@ -300,14 +626,20 @@ impl Drop for ClientHandle {
 mod test {
     use std::{
         str::FromStr,
+        sync::atomic::Ordering,
         time::{Duration, Instant},
     };
 
     use futures_util::future;
 
-    use crate::{errors::Result, test_misc::DATABASE_URL, Block, Options};
+    use crate::{
+        errors::{ConnectionError, Error, Result},
+        test_misc::DATABASE_URL,
+        types::Context,
+        Block, ClientHandle, Options,
+    };
 
-    use super::Pool;
+    use super::{IdleHandle, Pool, PoolBinding};
     use url::Url;
 
     #[tokio::test]
@@ -319,9 +651,11 @@ mod test {
             c.ping().await?;
         }
 
-        let info = pool.info();
-        assert_eq!(info.ongoing, 0);
-        assert_eq!(info.idle_len, 1);
+        let status = pool.status();
+        assert_eq!(status.in_use, 0);
+        assert_eq!(status.idle, 1);
+        assert_eq!(status.created, 1);
+        assert_eq!(status.checkouts, 1);
         Ok(())
     }
 
@@ -337,7 +671,7 @@ mod test {
 
         let pool = Pool::new(DATABASE_URL.as_str());
         done(pool.clone()).await?;
-        assert_eq!(pool.info().idle_len, 0);
+        assert_eq!(pool.status().idle, 0);
 
         Ok(())
     }
@@ -383,7 +717,7 @@ mod test {
         #[cfg(not(feature = "_tls"))]
         assert!(spent < Duration::from_millis(2500));
 
-        assert_eq!(pool.info().idle_len, 6);
+        assert_eq!(pool.status().idle, 6);
         Ok(())
     }
 
@@ -395,10 +729,11 @@ mod test {
             let mut c = pool.get_handle().await?;
             c.insert("unexisting", block).await.unwrap_err();
         }
-        let info = pool.info();
-        assert_eq!(info.ongoing, 0);
-        assert_eq!(info.tasks_len, 0);
-        assert_eq!(info.idle_len, 0);
+        let status = pool.status();
+        assert_eq!(status.in_use, 0);
+        assert_eq!(status.waiters, 0);
+        assert_eq!(status.idle, 0);
+        assert_eq!(status.closed, 1);
         Ok(())
     }
 
@@ -409,22 +744,232 @@ mod test {
             let mut c = pool.get_handle().await?;
             c.execute("DROP TABLE unexisting").await.unwrap_err();
         }
-        let info = pool.info();
-        assert_eq!(info.ongoing, 0);
-        assert_eq!(info.tasks_len, 0);
-        assert_eq!(info.idle_len, 0);
+        let status = pool.status();
+        assert_eq!(status.in_use, 0);
+        assert_eq!(status.waiters, 0);
+        assert_eq!(status.idle, 0);
+        assert_eq!(status.closed, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_check() -> Result<()> {
+        let options = Options::from_str(DATABASE_URL.as_str())
+            .unwrap()
+            .pool_min(2)
+            .health_check_interval(Some(Duration::from_millis(100)));
+        let pool = Pool::new(options);
+        {
+            let mut c = pool.get_handle().await?;
+            c.ping().await?;
+        }
+        assert_eq!(pool.status().idle, 1);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(pool.status().idle, 2);
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_max_lifetime() -> Result<()> {
+        let options = Options::from_str(DATABASE_URL.as_str())
+            .unwrap()
+            .max_lifetime(Some(Duration::from_millis(100)));
+        let pool = Pool::new(options);
+        {
+            let mut c = pool.get_handle().await?;
+            c.ping().await?;
+        }
+        assert_eq!(pool.status().idle, 1);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        {
+            let mut c = pool.get_handle().await?;
+            c.ping().await?;
+            assert!(c.created.elapsed() < Duration::from_millis(100));
+        }
+        assert_eq!(pool.status().idle, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkout_timeout() {
+        // accepts connections but never answers the handshake
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "tcp://{}?pool_max=1&connection_timeout=10s&checkout_timeout=100ms&max_waiters=1",
+            listener.local_addr().unwrap()
+        );
+        let pool = Pool::new(url.as_str());
+
+        let start = Instant::now();
+        let (first, second) = future::join(pool.get_handle(), pool.get_handle()).await;
+        assert!(matches!(
+            first.unwrap_err(),
+            Error::Connection(ConnectionError::CheckoutTimeout)
+        ));
+        assert!(matches!(
+            second.unwrap_err(),
+            Error::Connection(ConnectionError::TooManyWaiters)
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(pool.status().waiters, 0);
+    }
+
+    #[tokio::test]
+    async fn test_checkout_timeout_hands_over_connect() {
+        use tokio::io::AsyncWriteExt;
+
+        // answers the handshake with an unknown packet after a while
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "tcp://{}?pool_max=1&connection_timeout=10s&checkout_timeout=600ms",
+            listener.local_addr().unwrap()
+        );
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(700)).await;
+            socket.write_all(&[0x7f]).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+        let pool = Pool::new(url.as_str());
+        let start = Instant::now();
+
+        let first = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.get_handle().await.map(drop) }
+        });
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let second = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.get_handle().await.map(drop) }
+        });
+
+        assert!(matches!(
+            first.await.unwrap().unwrap_err(),
+            Error::Connection(ConnectionError::CheckoutTimeout)
+        ));
+        // the second waiter takes over the connect and gets its error
+        // right away instead of waiting for its own timeout
+        let err = second.await.unwrap().unwrap_err();
+        assert!(!matches!(
+            err,
+            Error::Connection(ConnectionError::CheckoutTimeout)
+        ));
+        assert!(start.elapsed() < Duration::from_millis(1000));
+        assert_eq!(pool.status().waiters, 0);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up() -> Result<()> {
+        let options = Options::from_str(DATABASE_URL.as_str())
+            .unwrap()
+            .pool_min(3);
+        let pool = Pool::connect(options).await?;
+        let status = pool.status();
+        assert_eq!(status.idle, 3);
+        assert_eq!(status.created, 3);
+
+        {
+            let mut c = pool.get_handle().await?;
+            c.ping().await?;
+            c.pool.detach();
+        }
+        assert_eq!(pool.status().idle, 2);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let status = pool.status();
+        assert_eq!(status.idle, 3);
+        assert_eq!(status.created, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_warm_up() -> Result<()> {
+        let options = Options::from_str(DATABASE_URL.as_str())
+            .unwrap()
+            .pool_min(3)
+            .health_check_interval(Some(Duration::from_millis(100)));
+        let pool = Pool::new(options);
+        let (first, second) = future::join(pool.warm_up(), pool.warm_up()).await;
+        first?;
+        second?;
+
+        let status = pool.status();
+        assert_eq!(status.idle, 3);
+        assert_eq!(status.created, 3);
+        assert!(pool.inner.health_check_started.load(Ordering::Acquire));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_error() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let url = format!("tcp://127.0.0.1:{port}?pool_min=2");
+
+        Pool::connect(url.as_str()).await.unwrap_err();
+
+        let pool = Pool::new(url.as_str());
+        pool.warm_up().await.unwrap_err();
+        let status = pool.status();
+        assert_eq!(status.failed_connects, 2);
+        assert_eq!(status.connecting, 0);
+        assert_eq!(status.idle, 0);
+    }
+
+    fn idle_handle(age: Duration, idle: Duration) -> IdleHandle {
+        let now = Instant::now();
+        let client = ClientHandle {
+            inner: None,
+            context: Context::default(),
+            pool: PoolBinding::None,
+            profile: None,
+            totals: None,
+            extremes: None,
+            callbacks: Default::default(),
+            created: now - age,
+            host: None,
+        };
+        IdleHandle {
+            client,
+            since: now - idle,
+        }
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let options = Options::from_str("tcp://localhost:9000")
+            .unwrap()
+            .idle_timeout(Some(Duration::from_secs(10)))
+            .max_lifetime(Some(Duration::from_secs(60)));
+        let pool = Pool::new(options);
+
+        let idle_too_long = idle_handle(Duration::from_secs(11), Duration::from_secs(11));
+        assert!(pool.inner.is_stale(&idle_too_long));
+
+        let too_old = idle_handle(Duration::from_secs(61), Duration::from_secs(1));
+        assert!(pool.inner.is_stale(&too_old));
+        assert!(pool.inner.is_expired(&too_old.client));
+
+        let fresh = idle_handle(Duration::from_secs(5), Duration::from_secs(1));
+        assert!(!pool.inner.is_stale(&fresh));
+
+        let pool = Pool::new("tcp://localhost:9000");
+        assert!(!pool.inner.is_stale(&too_old));
+    }
+
     #[test]
     fn test_get_addr() {
         let options =
             Options::from_str("tcp://host1:9000?alt_hosts=host2:9000,host3:9000").unwrap();
         let pool = Pool::new(options);
 
-        assert_eq!(pool.get_addr(), &Url::from_str("tcp://host1:9000").unwrap());
-        assert_eq!(pool.get_addr(), &Url::from_str("tcp://host2:9000").unwrap());
-        assert_eq!(pool.get_addr(), &Url::from_str("tcp://host3:9000").unwrap());
-        assert_eq!(pool.get_addr(), &Url::from_str("tcp://host1:9000").unwrap())
+        for host in ["host1", "host2", "host3", "host1"] {
+            let expected = Url::from_str(&format!("tcp://{host}:9000")).unwrap();
+            assert_eq!(pool.get_addr().1, &expected);
+        }
     }
 }
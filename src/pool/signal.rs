@@ -0,0 +1,58 @@
+use std::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+    task::Poll,
+};
+
+use futures_util::{future::poll_fn, task::AtomicWaker};
+
+/// Wakes up a background task of the pool regardless of the runtime.
+#[derive(Default)]
+pub(crate) struct Signal {
+    waker: AtomicWaker,
+    is_set: AtomicBool,
+}
+
+impl Signal {
+    pub(crate) fn notify(&self) {
+        self.is_set.store(true, Ordering::Release);
+        self.waker.wake();
+    }
+
+    /// Resolves once `notify` is called, notifications made meanwhile are merged.
+    pub(crate) fn wait(&self) -> impl Future<Output = ()> + '_ {
+        poll_fn(move |cx| {
+            self.waker.register(cx.waker());
+            if self.is_set.swap(false, Ordering::AcqRel) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::task::{Context, Poll};
+
+    use futures_util::{task::noop_waker_ref, FutureExt};
+
+    use super::Signal;
+
+    #[test]
+    fn test_signal() {
+        let signal = Signal::default();
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let mut wait = Box::pin(signal.wait());
+        assert_eq!(wait.poll_unpin(&mut cx), Poll::Pending);
+
+        signal.notify();
+        signal.notify();
+        assert_eq!(wait.poll_unpin(&mut cx), Poll::Ready(()));
+
+        let mut wait = Box::pin(signal.wait());
+        assert_eq!(wait.poll_unpin(&mut cx), Poll::Pending);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Snapshot of the state and the counters of a [`Pool`](super::Pool), see `Pool::status`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// Connections ready to be checked out.
+    pub idle: usize,
+    /// Connections checked out of the pool.
+    pub in_use: usize,
+    /// `get_handle` futures waiting for a connection.
+    pub waiters: usize,
+    /// Connections being opened.
+    pub connecting: usize,
+    /// Connections opened by the pool.
+    pub created: u64,
+    /// Connections closed by the pool: broken, expired or not kept as idle.
+    pub closed: u64,
+    /// Failed attempts to open a connection.
+    pub failed_connects: u64,
+    /// Completed `get_handle` calls.
+    pub checkouts: u64,
+    /// Total time `get_handle` calls waited for a connection.
+    pub checkout_wait: Duration,
+}
+
+/// Counters of `PoolStatus`, also reported to `metrics` with the `metrics` feature.
+#[derive(Default)]
+pub(crate) struct Stats {
+    created: AtomicU64,
+    closed: AtomicU64,
+    failed_connects: AtomicU64,
+    checkouts: AtomicU64,
+    checkout_wait_nanos: AtomicU64,
+}
+
+impl Stats {
+    pub(crate) fn connection_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!("clickhouse_pool_connections_created_total").increment(1);
+    }
+
+    pub(crate) fn connection_closed(&self) {
+        self.closed.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!("clickhouse_pool_connections_closed_total").increment(1);
+    }
+
+    pub(crate) fn connect_failed(&self) {
+        self.failed_connects.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!("clickhouse_pool_connect_failures_total").increment(1);
+    }
+
+    pub(crate) fn checkout(&self, wait: Duration) {
+        let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+        self.checkouts.fetch_add(1, Ordering::Relaxed);
+        self.checkout_wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("clickhouse_pool_checkout_wait_seconds").record(wait.as_secs_f64());
+    }
+
+    pub(crate) fn fill(&self, status: &mut PoolStatus) {
+        status.created = self.created.load(Ordering::Relaxed);
+        status.closed = self.closed.load(Ordering::Relaxed);
+        status.failed_connects = self.failed_connects.load(Ordering::Relaxed);
+        status.checkouts = self.checkouts.load(Ordering::Relaxed);
+        status.checkout_wait =
+            Duration::from_nanos(self.checkout_wait_nanos.load(Ordering::Relaxed));
+    }
+}
+
+/// Sets `metrics` gauges of the pool state.
+#[cfg(feature = "metrics")]
+pub(crate) fn report_gauges(status: &PoolStatus) {
+    metrics::gauge!("clickhouse_pool_idle_connections").set(status.idle as f64);
+    metrics::gauge!("clickhouse_pool_in_use_connections").set(status.in_use as f64);
+    metrics::gauge!("clickhouse_pool_waiters").set(status.waiters as f64);
+    metrics::gauge!("clickhouse_pool_connecting").set(status.connecting as f64);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        let stats = Stats::default();
+        stats.connection_created();
+        stats.connection_created();
+        stats.connection_closed();
+        stats.connect_failed();
+        stats.checkout(Duration::from_millis(3));
+        stats.checkout(Duration::from_millis(5));
+
+        let mut status = PoolStatus {
+            idle: 1,
+            ..PoolStatus::default()
+        };
+        stats.fill(&mut status);
+        assert_eq!(
+            status,
+            PoolStatus {
+                idle: 1,
+                in_use: 0,
+                waiters: 0,
+                connecting: 0,
+                created: 2,
+                closed: 1,
+                failed_connects: 1,
+                checkouts: 2,
+                checkout_wait: Duration::from_millis(8),
+            }
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    task::Waker,
+};
+
+/// FIFO queue of `GetHandle` futures waiting for a connection.
+///
+/// A released connection wakes only the oldest waiter that isn't notified yet, and new
+/// callers don't take connections while there are queued waiters.
+#[derive(Default)]
+pub(crate) struct Waiters {
+    queue: Mutex<VecDeque<Waiter>>,
+    next_id: AtomicU64,
+}
+
+struct Waiter {
+    id: u64,
+    waker: Waker,
+    notified: bool,
+}
+
+impl Waiters {
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Waiter>> {
+        self.queue.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.queue().len()
+    }
+
+    /// Whether the caller may take a connection: either it's notified
+    /// or it isn't queued and nobody is waiting.
+    pub(crate) fn is_turn(&self, id: Option<u64>) -> bool {
+        let queue = self.queue();
+        match id {
+            None => queue.is_empty(),
+            Some(id) => queue.iter().any(|w| w.id == id && w.notified),
+        }
+    }
+
+    /// Queues the caller or refreshes its waker, returns `None` if the queue is full.
+    pub(crate) fn register(
+        &self,
+        id: Option<u64>,
+        waker: &Waker,
+        max: Option<usize>,
+    ) -> Option<u64> {
+        let mut queue = self.queue();
+        if let Some(id) = id {
+            if let Some(waiter) = queue.iter_mut().find(|w| w.id == id) {
+                waiter.waker.clone_from(waker);
+                waiter.notified = false;
+                return Some(id);
+            }
+        }
+
+        if max.is_some_and(|max| queue.len() >= max) {
+            return None;
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        queue.push_back(Waiter {
+            id,
+            waker: waker.clone(),
+            notified: false,
+        });
+        Some(id)
+    }
+
+    /// Removes the caller that got a connection from the queue.
+    pub(crate) fn remove(&self, id: u64) {
+        let mut queue = self.queue();
+        queue.retain(|w| w.id != id);
+    }
+
+    /// Removes the caller that gave up waiting, passing its notification on to the next waiter.
+    /// Returns whether the notification was passed on.
+    pub(crate) fn cancel(&self, id: u64) -> bool {
+        let mut queue = self.queue();
+        let waiter = match queue.iter().position(|w| w.id == id) {
+            Some(index) => queue.remove(index),
+            None => None,
+        };
+        let notified = waiter.is_some_and(|w| w.notified);
+        if notified {
+            Self::notify(&mut queue);
+        }
+        notified
+    }
+
+    /// Wakes the oldest waiter that isn't notified yet.
+    pub(crate) fn notify_one(&self) {
+        Self::notify(&mut self.queue());
+    }
+
+    fn notify(queue: &mut VecDeque<Waiter>) {
+        if let Some(waiter) = queue.iter_mut().find(|w| !w.notified) {
+            waiter.notified = true;
+            waiter.waker.wake_by_ref();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Wake, Waker},
+    };
+
+    use super::Waiters;
+
+    struct Counter(AtomicUsize);
+
+    impl Wake for Counter {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn waker() -> (Arc<Counter>, Waker) {
+        let counter = Arc::new(Counter(AtomicUsize::new(0)));
+        (counter.clone(), Waker::from(counter))
+    }
+
+    fn wakes(counter: &Counter) -> usize {
+        counter.0.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_fifo() {
+        let waiters = Waiters::default();
+        let (first_wakes, first) = waker();
+        let (second_wakes, second) = waker();
+
+        assert!(waiters.is_turn(None));
+        let a = waiters.register(None, &first, None);
+        let b = waiters.register(None, &second, None);
+        assert_eq!(waiters.len(), 2);
+        assert!(!waiters.is_turn(None));
+        assert!(!waiters.is_turn(a));
+
+        waiters.notify_one();
+        assert_eq!((wakes(&first_wakes), wakes(&second_wakes)), (1, 0));
+        assert!(waiters.is_turn(a));
+        assert!(!waiters.is_turn(b));
+
+        waiters.notify_one();
+        assert_eq!((wakes(&first_wakes), wakes(&second_wakes)), (1, 1));
+
+        waiters.remove(a.unwrap());
+        waiters.remove(b.unwrap());
+        assert_eq!(waiters.len(), 0);
+        assert!(waiters.is_turn(None));
+    }
+
+    #[test]
+    fn test_cancel_passes_notification() {
+        let waiters = Waiters::default();
+        let (_, first) = waker();
+        let (second_wakes, second) = waker();
+
+        let a = waiters.register(None, &first, None);
+        let b = waiters.register(None, &second, None);
+        waiters.notify_one();
+        assert!(waiters.cancel(a.unwrap()));
+        assert!(!waiters.cancel(a.unwrap()));
+
+        assert_eq!(wakes(&second_wakes), 1);
+        assert!(waiters.is_turn(b));
+    }
+
+    #[test]
+    fn test_max_waiters() {
+        let waiters = Waiters::default();
+        let (_, first) = waker();
+        let (_, second) = waker();
+
+        let a = waiters.register(None, &first, Some(1));
+        assert!(a.is_some());
+        assert_eq!(waiters.register(None, &second, Some(1)), None);
+        assert_eq!(waiters.register(a, &first, Some(1)), a);
+        assert_eq!(waiters.len(), 1);
+    }
+}
@@ -0,0 +1,682 @@
+//! Conversions between blocks and Arrow record batches, enabled by the `arrow` feature.
+
+use std::{collections::HashMap, str, sync::Arc};
+
+use arrow_array::{
+    cast::AsArray,
+    types::{
+        Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    },
+    Array, ArrayRef, BooleanArray, Date32Array, Decimal128Array, Decimal256Array, DictionaryArray,
+    FixedSizeBinaryArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
+    Int8Array, ListArray, PrimitiveArray, StringArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_buffer::{i256, NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType, Field, TimeUnit};
+use chrono_tz::Tz;
+use either::Either;
+use ethnum::I256;
+
+use crate::{
+    errors::{Error, FromSqlError, Result},
+    types::{
+        column::{ArcColumnData, ArcColumnWrapper, ColumnData},
+        decimal::NoBits,
+        Column, ColumnType, DateTimeType, Decimal, SqlType, Value, ValueRef,
+    },
+};
+
+impl<K: ColumnType> Column<K> {
+    /// Converts the column into an Arrow array.
+    ///
+    /// `LowCardinality` columns become dictionary arrays with `Int32` keys, `Date` and `Date32`
+    /// columns become `Date32` arrays and date-time columns become timestamps with the time zone
+    /// of the column. Strings should be valid UTF-8.
+    pub fn to_arrow(&self) -> Result<ArrayRef> {
+        let values = (0..self.len()).map(|i| Some(self.at(i))).collect();
+        to_array(&self.sql_type(), values)
+    }
+}
+
+/// Returns `true` if the Arrow field of a column with `sql_type` may contain nulls.
+pub(crate) fn is_nullable(sql_type: &SqlType) -> bool {
+    match sql_type {
+        SqlType::Nullable(_) => true,
+        SqlType::LowCardinality(inner) => is_nullable(inner),
+        _ => false,
+    }
+}
+
+fn invalid_type(src: impl ToString, dst: impl ToString) -> Error {
+    Error::FromSql(FromSqlError::InvalidType {
+        src: src.to_string().into(),
+        dst: dst.to_string().into(),
+    })
+}
+
+/// Builds an array out of `values`, `None` stands for null.
+fn to_array(sql_type: &SqlType, values: Vec<Option<ValueRef>>) -> Result<ArrayRef> {
+    macro_rules! primitive {
+        ( $array:ty, $variant:ident ) => {
+            Arc::new(
+                values
+                    .into_iter()
+                    .map(|value| match value {
+                        Some(ValueRef::$variant(v)) => Ok(Some(v)),
+                        None => Ok(None),
+                        Some(_) => Err(invalid_type(sql_type, stringify!($array))),
+                    })
+                    .collect::<Result<$array>>()?,
+            )
+        };
+    }
+
+    macro_rules! timestamp {
+        ( $t:ty, $scale:expr, $tz:expr ) => {
+            Arc::new(
+                values
+                    .into_iter()
+                    .map(|value| match value {
+                        Some(ValueRef::DateTime64(v, _)) => Ok(Some(v * $scale)),
+                        None => Ok(None),
+                        Some(_) => Err(invalid_type(sql_type, "Timestamp")),
+                    })
+                    .collect::<Result<PrimitiveArray<$t>>>()?
+                    .with_timezone($tz.name()),
+            )
+        };
+    }
+
+    let array: ArrayRef = match sql_type {
+        SqlType::Bool => primitive!(BooleanArray, Bool),
+        SqlType::UInt8 => primitive!(UInt8Array, UInt8),
+        SqlType::UInt16 => primitive!(UInt16Array, UInt16),
+        SqlType::UInt32 => primitive!(UInt32Array, UInt32),
+        SqlType::UInt64 => primitive!(UInt64Array, UInt64),
+        SqlType::Int8 => primitive!(Int8Array, Int8),
+        SqlType::Int16 => primitive!(Int16Array, Int16),
+        SqlType::Int32 => primitive!(Int32Array, Int32),
+        SqlType::Int64 => primitive!(Int64Array, Int64),
+        SqlType::Float32 => primitive!(Float32Array, Float32),
+        SqlType::Float64 => primitive!(Float64Array, Float64),
+        SqlType::String => Arc::new(
+            values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::String(v)) => Ok(Some(str::from_utf8(v)?)),
+                    None => Ok(None),
+                    Some(_) => Err(invalid_type(sql_type, "Utf8")),
+                })
+                .collect::<Result<StringArray>>()?,
+        ),
+        SqlType::FixedString(size) => {
+            let values = values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::String(v)) => Ok(Some(v)),
+                    None => Ok(None),
+                    Some(_) => Err(invalid_type(sql_type, "FixedSizeBinary")),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Arc::new(FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                values.into_iter(),
+                *size as i32,
+            )?)
+        }
+        SqlType::Date | SqlType::Date32 => Arc::new(
+            values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::Date(v)) => Ok(Some(i32::from(v))),
+                    Some(ValueRef::Date32(v)) => Ok(Some(v)),
+                    None => Ok(None),
+                    Some(_) => Err(invalid_type(sql_type, "Date32")),
+                })
+                .collect::<Result<Date32Array>>()?,
+        ),
+        SqlType::DateTime(DateTimeType::DateTime32 | DateTimeType::Chrono) => {
+            let mut tz = None;
+            let array = values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::DateTime(v, value_tz)) => {
+                        tz.get_or_insert(value_tz);
+                        Ok(Some(i64::from(v)))
+                    }
+                    None => Ok(None),
+                    Some(_) => Err(invalid_type(sql_type, "Timestamp")),
+                })
+                .collect::<Result<PrimitiveArray<TimestampSecondType>>>()?;
+            Arc::new(array.with_timezone(tz.unwrap_or(Tz::UTC).name()))
+        }
+        SqlType::DateTime(DateTimeType::DateTime64(precision, tz)) => match precision {
+            0 => timestamp!(TimestampSecondType, 1, tz),
+            1..=3 => timestamp!(TimestampMillisecondType, 10_i64.pow(3 - precision), tz),
+            4..=6 => timestamp!(TimestampMicrosecondType, 10_i64.pow(6 - precision), tz),
+            7..=9 => timestamp!(TimestampNanosecondType, 10_i64.pow(9 - precision), tz),
+            _ => return Err(invalid_type(sql_type, "Timestamp")),
+        },
+        SqlType::Decimal(precision, scale) => {
+            let values = values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::Decimal(v)) => Ok(Some(v.underlying)),
+                    None => Ok(None),
+                    Some(_) => Err(invalid_type(sql_type, "Decimal")),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if *precision <= 38 {
+                Arc::new(
+                    values
+                        .into_iter()
+                        .map(|v| v.map(|v| v.as_i128()))
+                        .collect::<Decimal128Array>()
+                        .with_precision_and_scale(*precision, *scale as i8)?,
+                )
+            } else {
+                Arc::new(
+                    values
+                        .into_iter()
+                        .map(|v| v.map(|v| i256::from_le_bytes(v.to_le_bytes())))
+                        .collect::<Decimal256Array>()
+                        .with_precision_and_scale(*precision, *scale as i8)?,
+                )
+            }
+        }
+        SqlType::Nullable(inner) => {
+            let values = values
+                .into_iter()
+                .map(|value| match value {
+                    Some(ValueRef::Nullable(Either::Left(_))) | None => None,
+                    Some(ValueRef::Nullable(Either::Right(v))) => Some(*v),
+                    value => value,
+                })
+                .collect();
+            to_array(inner, values)?
+        }
+        SqlType::Array(inner) => {
+            let mut lengths = Vec::with_capacity(values.len());
+            let mut validity = Vec::with_capacity(values.len());
+            let mut items = Vec::new();
+            for value in values {
+                match value {
+                    Some(ValueRef::Array(_, vs)) => {
+                        lengths.push(vs.len());
+                        validity.push(true);
+                        items.extend(vs.iter().cloned().map(Some));
+                    }
+                    None => {
+                        lengths.push(0);
+                        validity.push(false);
+                    }
+                    Some(_) => return Err(invalid_type(sql_type, "List")),
+                }
+            }
+
+            let child = to_array(inner, items)?;
+            let field = Field::new_list_field(child.data_type().clone(), is_nullable(inner));
+            let nulls = validity
+                .contains(&false)
+                .then(|| NullBuffer::from(validity));
+            Arc::new(ListArray::try_new(
+                Arc::new(field),
+                OffsetBuffer::from_lengths(lengths),
+                child,
+                nulls,
+            )?)
+        }
+        SqlType::LowCardinality(inner) => {
+            let value_type = match inner {
+                SqlType::Nullable(value_type) => value_type,
+                value_type => value_type,
+            };
+
+            let mut index = HashMap::new();
+            let mut dictionary = Vec::new();
+            let mut keys = Vec::with_capacity(values.len());
+            for value in values {
+                let value = match value {
+                    Some(ValueRef::Nullable(Either::Left(_))) | None => None,
+                    Some(ValueRef::Nullable(Either::Right(v))) => Some(*v),
+                    value => value,
+                };
+                keys.push(value.map(|value| {
+                    *index.entry(value.clone()).or_insert_with(|| {
+                        dictionary.push(Some(value));
+                        dictionary.len() as i32 - 1
+                    })
+                }));
+            }
+
+            let values = to_array(value_type, dictionary)?;
+            Arc::new(DictionaryArray::try_new(Int32Array::from(keys), values)?)
+        }
+        SqlType::SimpleAggregateFunction(_, nested) => to_array(nested, values)?,
+        _ => return Err(invalid_type(sql_type, "Arrow array")),
+    };
+
+    Ok(array)
+}
+
+/// Converts the columns of a record batch into column data, see [`Block::from_record_batch`].
+///
+/// [`Block::from_record_batch`]: crate::types::Block::from_record_batch
+pub(crate) fn batch_columns(
+    fields: &[Arc<Field>],
+    arrays: &[ArrayRef],
+) -> Result<Vec<(String, ArcColumnData)>> {
+    let mut columns = Vec::with_capacity(arrays.len());
+    for (field, array) in fields.iter().zip(arrays) {
+        let sql_type = column_type(array.as_ref())?;
+        let values = column_values(array.as_ref(), &sql_type)?;
+
+        let mut data = <dyn ColumnData>::from_type::<ArcColumnWrapper>(
+            sql_type,
+            timezone(array.data_type())?,
+            values.len(),
+        )?;
+        let target = Arc::get_mut(&mut data).unwrap();
+        for value in values {
+            target.push(value);
+        }
+
+        columns.push((field.name().clone(), data));
+    }
+    Ok(columns)
+}
+
+/// Chooses the type of a column for an array, it's `Nullable` only if the array contains nulls.
+fn column_type(array: &dyn Array) -> Result<SqlType> {
+    let sql_type = value_type(array)?;
+    if array.logical_null_count() > 0 && !matches!(sql_type, SqlType::Array(_)) {
+        return Ok(SqlType::Nullable(sql_type.into()));
+    }
+    Ok(sql_type)
+}
+
+fn value_type(array: &dyn Array) -> Result<SqlType> {
+    Ok(match array.data_type() {
+        DataType::Boolean => SqlType::Bool,
+        DataType::UInt8 => SqlType::UInt8,
+        DataType::UInt16 => SqlType::UInt16,
+        DataType::UInt32 => SqlType::UInt32,
+        DataType::UInt64 => SqlType::UInt64,
+        DataType::Int8 => SqlType::Int8,
+        DataType::Int16 => SqlType::Int16,
+        DataType::Int32 => SqlType::Int32,
+        DataType::Int64 => SqlType::Int64,
+        DataType::Float32 => SqlType::Float32,
+        DataType::Float64 => SqlType::Float64,
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Utf8View
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView => SqlType::String,
+        DataType::FixedSizeBinary(size) => SqlType::FixedString(*size as usize),
+        DataType::Date32 => {
+            let fits_date = array
+                .as_primitive::<Date32Type>()
+                .iter()
+                .flatten()
+                .all(|days| u16::try_from(days).is_ok());
+            if fits_date {
+                SqlType::Date
+            } else {
+                SqlType::Date32
+            }
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => SqlType::DateTime(DateTimeType::DateTime32),
+        DataType::Timestamp(unit, tz) => {
+            let precision = match unit {
+                TimeUnit::Millisecond => 3,
+                TimeUnit::Microsecond => 6,
+                _ => 9,
+            };
+            SqlType::DateTime(DateTimeType::DateTime64(
+                precision,
+                parse_timezone(tz.as_deref())?,
+            ))
+        }
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale)
+            if *scale >= 0 =>
+        {
+            SqlType::Decimal(*precision, *scale as u8)
+        }
+        DataType::List(_) => SqlType::Array(column_type(array.as_list::<i32>().values())?.into()),
+        DataType::LargeList(_) => {
+            SqlType::Array(column_type(array.as_list::<i64>().values())?.into())
+        }
+        DataType::Dictionary(_, _) => value_type(array.as_any_dictionary().values())?,
+        data_type => return Err(invalid_type(data_type, "ClickHouse column")),
+    })
+}
+
+fn parse_timezone(tz: Option<&str>) -> Result<Tz> {
+    match tz {
+        None => Ok(Tz::UTC),
+        Some(name) => name
+            .parse()
+            .map_err(|_| Error::Other(format!("unknown time zone `{name}`").into())),
+    }
+}
+
+/// Returns the time zone of the first timestamp type in `data_type`.
+fn timezone(data_type: &DataType) -> Result<Tz> {
+    match data_type {
+        DataType::Timestamp(_, tz) => parse_timezone(tz.as_deref()),
+        DataType::List(field) | DataType::LargeList(field) => timezone(field.data_type()),
+        DataType::Dictionary(_, value) => timezone(value),
+        _ => Ok(Tz::UTC),
+    }
+}
+
+fn column_values(array: &dyn Array, sql_type: &SqlType) -> Result<Vec<Value>> {
+    let values = array_values(array, sql_type)?;
+    match sql_type {
+        SqlType::Nullable(inner) => Ok(values
+            .into_iter()
+            .map(|value| match value {
+                None => Value::Nullable(Either::Left(inner)),
+                Some(value) => Value::Nullable(Either::Right(Box::new(value))),
+            })
+            .collect()),
+        _ => values
+            .into_iter()
+            .map(|value| {
+                value.ok_or_else(|| {
+                    Error::Other(format!("{sql_type} column can't contain nulls").into())
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Reads the values of an array as `sql_type`, `None` stands for null.
+fn array_values(array: &dyn Array, sql_type: &SqlType) -> Result<Vec<Option<Value>>> {
+    if let DataType::Dictionary(_, _) = array.data_type() {
+        let dictionary = array.as_any_dictionary();
+        let values = array_values(dictionary.values().as_ref(), sql_type)?;
+        let keys = dictionary.normalized_keys();
+        return Ok((0..array.len())
+            .map(|i| {
+                if array.is_null(i) {
+                    None
+                } else {
+                    values[keys[i]].clone()
+                }
+            })
+            .collect());
+    }
+
+    macro_rules! values {
+        ( $array:expr, $f:expr ) => {
+            $array.iter().map(|value| value.map($f)).collect()
+        };
+    }
+
+    let sql_type = match sql_type {
+        SqlType::Nullable(inner) => inner,
+        sql_type => sql_type,
+    };
+
+    Ok(match (sql_type, array.data_type()) {
+        (SqlType::Bool, _) => values!(array.as_boolean(), Value::Bool),
+        (SqlType::UInt8, _) => values!(array.as_primitive::<UInt8Type>(), Value::UInt8),
+        (SqlType::UInt16, _) => values!(array.as_primitive::<UInt16Type>(), Value::UInt16),
+        (SqlType::UInt32, _) => values!(array.as_primitive::<UInt32Type>(), Value::UInt32),
+        (SqlType::UInt64, _) => values!(array.as_primitive::<UInt64Type>(), Value::UInt64),
+        (SqlType::Int8, _) => values!(array.as_primitive::<Int8Type>(), Value::Int8),
+        (SqlType::Int16, _) => values!(array.as_primitive::<Int16Type>(), Value::Int16),
+        (SqlType::Int32, _) => values!(array.as_primitive::<Int32Type>(), Value::Int32),
+        (SqlType::Int64, _) => values!(array.as_primitive::<Int64Type>(), Value::Int64),
+        (SqlType::Float32, _) => values!(array.as_primitive::<Float32Type>(), Value::Float32),
+        (SqlType::Float64, _) => values!(array.as_primitive::<Float64Type>(), Value::Float64),
+        (SqlType::String, DataType::Utf8) => values!(array.as_string::<i32>(), Value::from),
+        (SqlType::String, DataType::LargeUtf8) => values!(array.as_string::<i64>(), Value::from),
+        (SqlType::String, DataType::Utf8View) => values!(array.as_string_view(), Value::from),
+        (SqlType::String, DataType::Binary) => values!(array.as_binary::<i32>(), Value::from),
+        (SqlType::String, DataType::LargeBinary) => {
+            values!(array.as_binary::<i64>(), Value::from)
+        }
+        (SqlType::String, DataType::BinaryView) => values!(array.as_binary_view(), Value::from),
+        (SqlType::FixedString(_), _) => values!(array.as_fixed_size_binary(), Value::from),
+        (SqlType::Date, _) => values!(array.as_primitive::<Date32Type>(), |days| {
+            Value::Date(days as u16)
+        }),
+        (SqlType::Date32, _) => values!(array.as_primitive::<Date32Type>(), Value::Date32),
+        (SqlType::DateTime(DateTimeType::DateTime32), DataType::Timestamp(_, tz)) => {
+            let tz = parse_timezone(tz.as_deref())?;
+            array
+                .as_primitive::<TimestampSecondType>()
+                .iter()
+                .map(|value| {
+                    value
+                        .map(|v| match u32::try_from(v) {
+                            Ok(v) => Ok(Value::DateTime(v, tz)),
+                            Err(_) => Err(Error::FromSql(FromSqlError::OutOfRange)),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<_>>()?
+        }
+        (
+            SqlType::DateTime(DateTimeType::DateTime64(precision, tz)),
+            DataType::Timestamp(unit, _),
+        ) => {
+            let values: Vec<Option<i64>> = match unit {
+                TimeUnit::Second => array.as_primitive::<TimestampSecondType>().iter().collect(),
+                TimeUnit::Millisecond => array
+                    .as_primitive::<TimestampMillisecondType>()
+                    .iter()
+                    .collect(),
+                TimeUnit::Microsecond => array
+                    .as_primitive::<TimestampMicrosecondType>()
+                    .iter()
+                    .collect(),
+                TimeUnit::Nanosecond => array
+                    .as_primitive::<TimestampNanosecondType>()
+                    .iter()
+                    .collect(),
+            };
+            values
+                .into_iter()
+                .map(|value| value.map(|v| Value::DateTime64(v, (*precision, *tz))))
+                .collect()
+        }
+        (SqlType::Decimal(precision, scale), DataType::Decimal128(_, _)) => {
+            values!(
+                array.as_primitive::<arrow_array::types::Decimal128Type>(),
+                |v| { Value::Decimal(decimal(I256::from(v), *precision, *scale)) }
+            )
+        }
+        (SqlType::Decimal(precision, scale), DataType::Decimal256(_, _)) => {
+            values!(
+                array.as_primitive::<arrow_array::types::Decimal256Type>(),
+                |v| {
+                    let underlying = I256::from_le_bytes(v.to_le_bytes());
+                    Value::Decimal(decimal(underlying, *precision, *scale))
+                }
+            )
+        }
+        (SqlType::Array(inner), DataType::List(_)) => array
+            .as_list::<i32>()
+            .iter()
+            .map(|value| value.map(|items| list_value(&items, inner)).transpose())
+            .collect::<Result<_>>()?,
+        (SqlType::Array(inner), DataType::LargeList(_)) => array
+            .as_list::<i64>()
+            .iter()
+            .map(|value| value.map(|items| list_value(&items, inner)).transpose())
+            .collect::<Result<_>>()?,
+        (sql_type, data_type) => return Err(invalid_type(data_type, sql_type)),
+    })
+}
+
+fn list_value(items: &ArrayRef, inner: &'static SqlType) -> Result<Value> {
+    Ok(Value::Array(
+        inner,
+        Arc::new(column_values(items.as_ref(), inner)?),
+    ))
+}
+
+fn decimal(underlying: I256, precision: u8, scale: u8) -> Decimal {
+    Decimal {
+        underlying,
+        nobits: NoBits::from_precision(precision).unwrap_or(NoBits::N256),
+        precision,
+        scale,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arrow_array::{
+        cast::AsArray, types::Int32Type, Array, Int64Array, ListArray, RecordBatch, StringArray,
+    };
+    use arrow_schema::DataType;
+    use chrono::prelude::*;
+    use chrono_tz::Tz;
+    use ethnum::I256;
+    use std::sync::Arc;
+
+    use crate::types::{Block, Decimal, SqlType};
+
+    #[test]
+    fn test_record_batch_roundtrip() {
+        let tz = Tz::Europe__Moscow;
+        let block = Block::new()
+            .column("id", vec![1_u32, 2, 3])
+            .column("name", vec!["foo", "bar", "baz"])
+            .column("amount", vec![Some(1.5_f64), None, Some(3.0)])
+            .column(
+                "tags",
+                vec![vec!["a".to_string()], vec![], vec!["b".into(), "c".into()]],
+            )
+            .column(
+                "day",
+                vec![
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2021, 6, 15).unwrap(),
+                    NaiveDate::from_ymd_opt(1970, 1, 2).unwrap(),
+                ],
+            )
+            .column(
+                "created",
+                vec![
+                    tz.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap(),
+                    tz.with_ymd_and_hms(2020, 1, 2, 12, 0, 0).unwrap(),
+                    tz.with_ymd_and_hms(2020, 1, 3, 12, 0, 0).unwrap(),
+                ],
+            )
+            .column(
+                "price",
+                vec![
+                    Decimal::of(1.25_f64, 2),
+                    Decimal::of(2.5_f64, 2),
+                    Decimal::of(-3.75_f64, 2),
+                ],
+            );
+
+        let batch = block.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), 7);
+
+        let schema = batch.schema();
+        assert!(!schema.field(0).is_nullable());
+        assert!(schema.field(2).is_nullable());
+        assert_eq!(schema.field(4).data_type(), &DataType::Date32);
+        assert_eq!(
+            schema.field(5).data_type(),
+            &DataType::Timestamp(arrow_schema::TimeUnit::Second, Some("Europe/Moscow".into()))
+        );
+        assert_eq!(schema.field(6).data_type(), &DataType::Decimal128(18, 2));
+
+        let names = batch.column(1).as_string::<i32>();
+        assert_eq!(names.value(1), "bar");
+        assert!(batch.column(2).is_null(1));
+
+        let actual = Block::from_record_batch(&batch).unwrap();
+        assert_eq!(actual, block);
+    }
+
+    #[test]
+    fn test_decimal256_roundtrip() {
+        let big = I256::from(i128::MAX) * 1000;
+        let block = Block::new().column("d", vec![Decimal::new(big, 2), Decimal::new(-big, 2)]);
+
+        let batch = block.to_record_batch().unwrap();
+        assert_eq!(
+            batch.schema().field(0).data_type(),
+            &DataType::Decimal256(76, 2)
+        );
+
+        let actual = Block::from_record_batch(&batch).unwrap();
+        assert_eq!(actual, block);
+    }
+
+    #[test]
+    fn test_low_cardinality_to_dictionary() {
+        let block = Block::new().column("color", vec!["red", "green", "red", "red"]);
+        let block = Block::concat(&[block]);
+        let column = block.get_column("color").unwrap();
+        let column = column
+            .clone()
+            .cast_to(SqlType::LowCardinality(SqlType::String.into()))
+            .unwrap();
+
+        let array = column.to_arrow().unwrap();
+        let dictionary = array.as_dictionary::<Int32Type>();
+        assert_eq!(dictionary.values().len(), 2);
+        assert_eq!(dictionary.keys().values().to_vec(), vec![0, 1, 0, 0],);
+    }
+
+    #[test]
+    fn test_from_record_batch_nullable_and_list() {
+        let ids = Int64Array::from(vec![Some(1), None, Some(3)]);
+        let names = StringArray::from(vec!["a", "b", "c"]);
+        let lists = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![]),
+            Some(vec![Some(3)]),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(ids) as _),
+            ("name", Arc::new(names) as _),
+            ("list", Arc::new(lists) as _),
+        ])
+        .unwrap();
+
+        let block = Block::from_record_batch(&batch).unwrap();
+        let types: Vec<_> = block.columns().iter().map(|c| c.sql_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                SqlType::Nullable(SqlType::Int64.into()),
+                SqlType::String,
+                SqlType::Array(SqlType::Int32.into()),
+            ]
+        );
+
+        let id: Option<i64> = block.get(1, "id").unwrap();
+        assert_eq!(id, None);
+        let list: Vec<i32> = block.get(0, "list").unwrap();
+        assert_eq!(list, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_from_record_batch_nullable_list_items() {
+        let lists =
+            ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![Some(1), None])]);
+        let batch = RecordBatch::try_from_iter(vec![("list", Arc::new(lists) as _)]).unwrap();
+
+        let block = Block::from_record_batch(&batch).unwrap();
+        assert_eq!(
+            block.columns()[0].sql_type(),
+            SqlType::Array(SqlType::Nullable(SqlType::Int32.into()).into())
+        );
+    }
+}
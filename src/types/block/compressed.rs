@@ -7,15 +7,19 @@ use std::{
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use clickhouse_rs_cityhash_sys::{city_hash_128, UInt128};
-use lz4::liblz4::LZ4_decompress_safe;
+use lz4::liblz4::{LZ4_compressBound, LZ4_compress_HC, LZ4_compress_default, LZ4_decompress_safe};
 
 use crate::{
-    binary::ReadEx,
+    binary::{Encoder, ReadEx},
     errors::{Error, Result},
+    types::options::CompressionMethod,
 };
 
 const DBMS_MAX_COMPRESSED_SIZE: u32 = 0x4000_0000; // 1GB
 
+const LZ4_METHOD: u8 = 0x82;
+const ZSTD_METHOD: u8 = 0x90;
+
 pub(crate) struct CompressedReader<'a, R> {
     reader: &'a mut R,
     cursor: io::Cursor<Vec<u8>>,
@@ -71,7 +75,7 @@ where
     };
 
     let method: u8 = reader.read_scalar()?;
-    if method != 0x82 {
+    if method != LZ4_METHOD && method != ZSTD_METHOD {
         let message: String = format!("unsupported compression method {method}");
         return Err(raise_error(message));
     }
@@ -86,7 +90,7 @@ where
     buffer.resize(compressed as usize, 0_u8);
     {
         let mut cursor = io::Cursor::new(&mut buffer);
-        cursor.write_u8(0x82)?;
+        cursor.write_u8(method)?;
         cursor.write_u32::<LittleEndian>(compressed)?;
         cursor.write_u32::<LittleEndian>(original)?;
     }
@@ -96,6 +100,13 @@ where
         return Err(raise_error("data was corrupted".to_string()));
     }
 
+    if method == ZSTD_METHOD {
+        return match zstd::bulk::decompress(&buffer[9..], original as usize) {
+            Ok(data) if data.len() == original as usize => Ok(data),
+            _ => Err(raise_error("can't decompress data".to_string())),
+        };
+    }
+
     let data = vec![0_u8; original as usize];
     let status = unsafe {
         LZ4_decompress_safe(
@@ -113,6 +124,52 @@ where
     Ok(data)
 }
 
+pub(crate) fn compress_buffer(encoder: &mut Encoder, data: &[u8], method: CompressionMethod) {
+    let mut buf = vec![0_u8; 9];
+    let method = match method {
+        CompressionMethod::Zstd(level) => {
+            buf.extend(zstd::bulk::compress(data, level).unwrap());
+            ZSTD_METHOD
+        }
+        CompressionMethod::Lz4Hc(level) => {
+            lz4_compress(data, &mut buf, Some(level));
+            LZ4_METHOD
+        }
+        CompressionMethod::Lz4 | CompressionMethod::None => {
+            lz4_compress(data, &mut buf, None);
+            LZ4_METHOD
+        }
+    };
+
+    let buf_len = buf.len() as u32;
+    {
+        let mut cursor = io::Cursor::new(&mut buf);
+        cursor.write_u8(method).unwrap();
+        cursor.write_u32::<LittleEndian>(buf_len).unwrap();
+        cursor.write_u32::<LittleEndian>(data.len() as u32).unwrap();
+    }
+
+    let hash = city_hash_128(&buf);
+    encoder.write(hash.lo);
+    encoder.write(hash.hi);
+    encoder.write_bytes(buf.as_ref());
+}
+
+fn lz4_compress(data: &[u8], buf: &mut Vec<u8>, level: Option<i32>) {
+    let size;
+    unsafe {
+        buf.resize(9 + LZ4_compressBound(data.len() as c_int) as usize, 0_u8);
+        let src = data.as_ptr() as *const c_char;
+        let dst = (buf.as_mut_ptr() as *mut c_char).add(9);
+        let capacity = (buf.len() - 9) as c_int;
+        size = match level {
+            Some(level) => LZ4_compress_HC(src, dst, data.len() as c_int, capacity, level),
+            None => LZ4_compress_default(src, dst, data.len() as c_int, capacity),
+        };
+    }
+    buf.resize(9 + size as usize, 0_u8);
+}
+
 fn raise_error(message: String) -> Error {
     message.into()
 }
@@ -139,4 +196,23 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let data: Vec<u8> = (0..1000_u32).map(|i| (i % 7) as u8).collect();
+
+        for method in [
+            CompressionMethod::Lz4,
+            CompressionMethod::Lz4Hc(9),
+            CompressionMethod::Zstd(1),
+        ] {
+            let mut encoder = Encoder::new();
+            compress_buffer(&mut encoder, &data, method);
+            let source = encoder.get_buffer();
+
+            let mut cursor = io::Cursor::new(&source[..]);
+            let actual = decompress_buffer(&mut cursor, Vec::new()).unwrap();
+            assert_eq!(actual, data, "{method:?}");
+        }
+    }
 }
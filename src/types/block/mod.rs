@@ -1,30 +1,24 @@
-use std::{
-    cmp,
-    default::Default,
-    fmt,
-    io::{Cursor, Read},
-    marker::PhantomData,
-    os::raw::c_char,
-};
+use std::{cmp, default::Default, fmt, io::Read, marker::PhantomData, sync::Arc};
 
-use byteorder::{LittleEndian, WriteBytesExt};
 use chrono_tz::Tz;
-use clickhouse_rs_cityhash_sys::city_hash_128;
-use lz4::liblz4::{LZ4_compressBound, LZ4_compress_default};
+use ethnum::{I256, U256};
 
 use crate::{
     binary::{protocol, Encoder, ReadEx},
     errors::{Error, FromSqlError, Result},
     types::{
-        column::{self, ArcColumnWrapper, Column, ColumnFrom},
-        ColumnType, Complex, FromSql, Simple, SqlType,
+        column::{
+            self, array::ArrayColumnData, datetime64::DEFAULT_TZ, ArcColumnWrapper, Column,
+            ColumnData, ColumnFrom,
+        },
+        ColumnType, Complex, CompressionMethod, FromSql, Simple, SqlType, Value, ValueRef,
     },
 };
 
 pub use self::{
     block_info::BlockInfo,
     builder::{RCons, RNil, RowBuilder},
-    row::{Row, Rows},
+    row::{FromRow, Row, Rows},
 };
 pub(crate) use self::{chunk_iterator::ChunkIterator, row::BlockRef};
 
@@ -64,12 +58,14 @@ sliceable! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
-    i128: Int128
+    i128: Int128,
+    I256: Int256
 }
 
 /// Represents Clickhouse Block
@@ -138,6 +134,17 @@ impl ColumnIdx for String {
     }
 }
 
+/// Builds a [`Block`] out of a vector of structs, see `#[derive(Row)]`.
+pub trait IntoBlock: Sized {
+    fn into_block(rows: Vec<Self>) -> Block;
+}
+
+impl<T: IntoBlock> From<Vec<T>> for Block {
+    fn from(rows: Vec<T>) -> Self {
+        T::into_block(rows)
+    }
+}
+
 impl Block {
     /// Constructs a new, empty `Block`.
     pub fn new() -> Self {
@@ -153,19 +160,61 @@ impl Block {
         }
     }
 
-    pub(crate) fn load<R>(reader: &mut R, tz: Tz, compress: bool) -> Result<Self>
+    /// Constructs a `Block` out of serializable rows, fields become columns.
+    ///
+    /// Column types are inferred from the serialized values.
+    #[cfg(feature = "serde")]
+    pub fn from_rows<T: serde::Serialize>(rows: &[T]) -> Result<Self> {
+        let mut block = Self::with_capacity(rows.len());
+        for (name, data) in crate::types::row_serde::serialize_columns(rows)? {
+            block.columns.push(column::new_column(&name, data));
+        }
+        Ok(block)
+    }
+
+    /// Constructs a `Block` out of an Arrow record batch.
+    ///
+    /// Column types are chosen from the Arrow data types, a column is `Nullable` only if it
+    /// contains nulls and dictionary arrays are decoded into their values.
+    #[cfg(feature = "arrow")]
+    pub fn from_record_batch(batch: &arrow_array::RecordBatch) -> Result<Self> {
+        let schema = batch.schema();
+        let mut block = Self::with_capacity(batch.num_rows());
+        for (name, data) in crate::types::arrow::batch_columns(schema.fields(), batch.columns())? {
+            block.columns.push(column::new_column(&name, data));
+        }
+        Ok(block)
+    }
+
+    /// Adds the columns of a `Nested` structure, every row contains a vector of structs.
+    ///
+    /// Each column of `T` becomes a separate `Array` column named `name.column`,
+    /// see `#[derive(Row)]`.
+    pub fn nested_column<T: IntoBlock>(mut self, name: &str, values: Vec<Vec<T>>) -> Self {
+        let lengths: Vec<usize> = values.iter().map(Vec::len).collect();
+        let items = T::into_block(values.into_iter().flatten().collect());
+
+        for column in items.columns {
+            let data = ArrayColumnData::from_lengths(column.data, &lengths);
+            let column_name = format!("{name}.{}", column.name);
+            self.append_column(column::new_column(&column_name, Arc::new(data)));
+        }
+        self
+    }
+
+    pub(crate) fn load<R>(reader: &mut R, tz: Tz, compress: bool, revision: u64) -> Result<Self>
     where
         R: Read + ReadEx,
     {
         if compress {
             let mut cr = compressed::make(reader);
-            Self::raw_load(&mut cr, tz)
+            Self::raw_load(&mut cr, tz, revision)
         } else {
-            Self::raw_load(reader, tz)
+            Self::raw_load(reader, tz, revision)
         }
     }
 
-    fn raw_load<R>(reader: &mut R, tz: Tz) -> Result<Block<Simple>>
+    fn raw_load<R>(reader: &mut R, tz: Tz, revision: u64) -> Result<Block<Simple>>
     where
         R: ReadEx,
     {
@@ -176,7 +225,7 @@ impl Block {
         let num_rows = reader.read_uvarint()?;
 
         for _ in 0..num_columns {
-            let column = Column::read(reader, num_rows as usize, tz)?;
+            let column = Column::read(reader, num_rows as usize, tz, revision)?;
             block.append_column(column);
         }
 
@@ -215,13 +264,21 @@ impl<K: ColumnType> Block<K> {
     }
 
     /// Get the value of a particular cell of the block.
+    ///
+    /// Cells of `Variant` and `Dynamic` columns are converted from the value they hold.
     pub fn get<'a, T, I>(&'a self, row: usize, col: I) -> Result<T>
     where
         T: FromSql<'a>,
         I: ColumnIdx + Copy,
     {
         let column_index = col.get_index(self.columns())?;
-        T::from_sql(self.columns[column_index].at(row))
+        let value = self.columns[column_index].at(row);
+        if let ValueRef::Variant(_, Some(inner)) = &value {
+            if let Ok(v) = T::from_sql(inner.as_ref().clone()) {
+                return Ok(v);
+            }
+        }
+        T::from_sql(value)
     }
 
     /// Add new column into this block
@@ -232,6 +289,51 @@ impl<K: ColumnType> Block<K> {
         self.column(name, values)
     }
 
+    /// Reads the `Nested` structure `name` of a row, it's assembled out of `name.*` array columns.
+    ///
+    /// Every element is decoded from a row with the columns named without the `name.` prefix,
+    /// see `#[derive(Row)]`. Tuples take the columns in order.
+    pub fn get_nested<T>(&self, row: usize, name: &str) -> Result<Vec<T>>
+    where
+        T: for<'b> FromRow<'b>,
+    {
+        let prefix = format!("{name}.");
+        let mut nested = Block::new();
+
+        for column in &self.columns {
+            let Some(column_name) = column.name().strip_prefix(&prefix) else {
+                continue;
+            };
+
+            let ValueRef::Array(item_type, items) = column.at(row) else {
+                return Err(Error::FromSql(FromSqlError::InvalidType {
+                    src: column.sql_type().to_string(),
+                    dst: "Nested".into(),
+                }));
+            };
+
+            if !nested.columns.is_empty() && nested.row_count() != items.len() {
+                let message = format!("Nested columns of \"{name}\" have different sizes.");
+                return Err(message.into());
+            }
+
+            let tz = column.data.get_timezone().unwrap_or(*DEFAULT_TZ);
+            let mut data =
+                <dyn ColumnData>::from_type::<ArcColumnWrapper>(item_type.clone(), tz, items.len())?;
+            let target = Arc::get_mut(&mut data).unwrap();
+            for item in items.iter() {
+                target.push(Value::from(item.clone()));
+            }
+            nested.columns.push(column::new_column(column_name, data));
+        }
+
+        if nested.columns.is_empty() {
+            return Err(Error::FromSql(FromSqlError::OutOfRange));
+        }
+
+        nested.rows().map(|row| T::from_row(&row)).collect()
+    }
+
     /// Add new column into this block
     pub fn column<S>(mut self, name: &str, values: S) -> Self
     where
@@ -272,6 +374,30 @@ impl<K: ColumnType> Block<K> {
         let column = &self.columns[column_index];
         Ok(column)
     }
+
+    /// Converts the block into an Arrow record batch, see [`Column::to_arrow`].
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> Result<arrow_array::RecordBatch> {
+        use arrow_array::{RecordBatch, RecordBatchOptions};
+        use arrow_schema::{Field, Schema};
+        use std::sync::Arc;
+
+        let mut fields = Vec::with_capacity(self.columns.len());
+        let mut arrays = Vec::with_capacity(self.columns.len());
+        for column in &self.columns {
+            let array = column.to_arrow()?;
+            let nullable = crate::types::arrow::is_nullable(&column.sql_type());
+            fields.push(Field::new(column.name(), array.data_type().clone(), nullable));
+            arrays.push(array);
+        }
+
+        let options = RecordBatchOptions::new().with_row_count(Some(self.row_count()));
+        Ok(RecordBatch::try_new_with_options(
+            Arc::new(Schema::new(fields)),
+            arrays,
+            &options,
+        )?)
+    }
 }
 
 impl Block<Simple> {
@@ -326,52 +452,42 @@ impl<K: ColumnType> Block<K> {
         })
     }
 
-    pub(crate) fn write(&self, encoder: &mut Encoder, compress: bool) {
-        if compress {
+    pub(crate) fn write(&self, encoder: &mut Encoder, compress: CompressionMethod, revision: u64) {
+        if compress.is_enabled() {
             let mut tmp_encoder = Encoder::new();
-            self.write(&mut tmp_encoder, false);
+            self.write(&mut tmp_encoder, CompressionMethod::None, revision);
             let tmp = tmp_encoder.get_buffer();
-
-            let mut buf = Vec::new();
-            let size;
-            unsafe {
-                buf.resize(9 + LZ4_compressBound(tmp.len() as i32) as usize, 0_u8);
-                size = LZ4_compress_default(
-                    tmp.as_ptr() as *const c_char,
-                    (buf.as_mut_ptr() as *mut c_char).add(9),
-                    tmp.len() as i32,
-                    buf.len() as i32,
-                );
-            }
-            buf.resize(9 + size as usize, 0_u8);
-
-            let buf_len = buf.len() as u32;
-            {
-                let mut cursor = Cursor::new(&mut buf);
-                cursor.write_u8(0x82).unwrap();
-                cursor.write_u32::<LittleEndian>(buf_len).unwrap();
-                cursor.write_u32::<LittleEndian>(tmp.len() as u32).unwrap();
-            }
-
-            let hash = city_hash_128(&buf);
-            encoder.write(hash.lo);
-            encoder.write(hash.hi);
-            encoder.write_bytes(buf.as_ref());
+            compressed::compress_buffer(encoder, &tmp, compress);
         } else {
             self.info.write(encoder);
             encoder.uvarint(self.column_count() as u64);
             encoder.uvarint(self.row_count() as u64);
 
             for column in &self.columns {
-                column.write(encoder);
+                column.write(encoder, revision);
             }
         }
     }
 
-    pub(crate) fn send_data(&self, encoder: &mut Encoder, compress: bool) {
+    pub(crate) fn send_data(
+        &self,
+        encoder: &mut Encoder,
+        compress: CompressionMethod,
+        revision: u64,
+    ) {
+        self.send_table_data(encoder, "", compress, revision);
+    }
+
+    pub(crate) fn send_table_data(
+        &self,
+        encoder: &mut Encoder,
+        table: &str,
+        compress: CompressionMethod,
+        revision: u64,
+    ) {
         encoder.uvarint(protocol::CLIENT_DATA);
-        encoder.string(""); // temporary table
-        self.write(encoder, compress);
+        encoder.string(table); // temporary table
+        self.write(encoder, compress, revision);
     }
 
     pub(crate) fn chunks(self, n: usize) -> ChunkIterator<K> {
@@ -445,13 +561,14 @@ fn text_cells<K: ColumnType>(data: &Column<K>) -> Vec<String> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{row, types::column::datetime64::DEFAULT_TZ};
+    use crate::{client_info::CLICK_HOUSE_REVISION, row, types::column::datetime64::DEFAULT_TZ};
+    use std::io::Cursor;
 
     #[test]
     fn test_write_default() {
         let expected = [1_u8, 0, 2, 255, 255, 255, 255, 0, 0, 0];
         let mut encoder = Encoder::new();
-        Block::<Simple>::default().write(&mut encoder, false);
+        Block::<Simple>::default().write(&mut encoder, CompressionMethod::None, 0);
         assert_eq!(encoder.get_buffer_ref(), &expected)
     }
 
@@ -466,7 +583,7 @@ mod test {
         let block = Block::<Simple>::new().column("s", vec!["abc"]);
 
         let mut encoder = Encoder::new();
-        block.write(&mut encoder, true);
+        block.write(&mut encoder, CompressionMethod::Lz4, 0);
 
         let actual = encoder.get_buffer();
         assert_eq!(actual, expected);
@@ -483,7 +600,7 @@ mod test {
         ];
 
         let mut cursor = Cursor::new(&source[..]);
-        let actual = Block::load(&mut cursor, Tz::UTC, true).unwrap();
+        let actual = Block::load(&mut cursor, Tz::UTC, true, 0).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -492,7 +609,7 @@ mod test {
     fn test_read_empty_block() {
         let source = [1, 0, 2, 255, 255, 255, 255, 0, 0, 0];
         let mut cursor = Cursor::new(&source[..]);
-        match Block::<Simple>::load(&mut cursor, *DEFAULT_TZ, false) {
+        match Block::<Simple>::load(&mut cursor, *DEFAULT_TZ, false, 0) {
             Ok(block) => assert!(block.is_empty()),
             Err(_) => unreachable!(),
         }
@@ -585,10 +702,10 @@ mod test {
         let block = Block::<Simple>::new().column("y", vec![Some(1_u8), None]);
 
         let mut encoder = Encoder::new();
-        block.write(&mut encoder, false);
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
 
         let mut reader = Cursor::new(encoder.get_buffer_ref());
-        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false).unwrap();
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
 
         assert_eq!(block, rblock);
     }
@@ -606,4 +723,31 @@ mod test {
         let actual: Vec<String> = block.get(0, 0).unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_get_nested() {
+        let block = Block::new()
+            .column("id", vec![1_u32, 2])
+            .column("n.a", vec![vec![1_u8, 2], vec![]])
+            .column("n.b", vec![vec!["x".to_string(), "y".into()], vec![]]);
+
+        let actual: Vec<(u8, String)> = block.get_nested(0, "n").unwrap();
+        assert_eq!(actual, vec![(1, "x".to_string()), (2, "y".to_string())]);
+
+        let row = block.rows().nth(1).unwrap();
+        let actual: Vec<(u8, String)> = row.get_nested("n").unwrap();
+        assert!(actual.is_empty());
+
+        assert!(block.get_nested::<(u8, String)>(0, "m").is_err());
+        assert!(block.get_nested::<(u8,)>(0, "n").is_err());
+    }
+
+    #[test]
+    fn test_get_nested_different_sizes() {
+        let block = Block::new()
+            .column("n.a", vec![vec![1_u8, 2]])
+            .column("n.b", vec![vec![3_u8]]);
+
+        assert!(block.get_nested::<(u8, u8)>(0, "n").is_err());
+    }
 }
@@ -1,5 +1,7 @@
 use std::{marker, sync::Arc};
 
+#[cfg(feature = "serde")]
+use crate::types::{row_serde::RowDeserializer, ValueRef};
 use crate::{
     errors::Result,
     types::{block::ColumnIdx, Block, Column, ColumnType, FromSql, SqlType},
@@ -22,6 +24,17 @@ impl<'a, K: ColumnType> Row<'a, K> {
         self.block_ref.get(self.row, col)
     }
 
+    /// Get the `Nested` structure `name` of the row, see [`Block::get_nested`].
+    pub fn get_nested<T>(&self, name: &str) -> Result<Vec<T>>
+    where
+        T: for<'b> FromRow<'b>,
+    {
+        match &self.block_ref {
+            BlockRef::Borrowed(block) => block.get_nested(self.row, name),
+            BlockRef::Owned(block) => block.get_nested(self.row, name),
+        }
+    }
+
     /// Return the number of cells in the current row.
     pub fn len(&self) -> usize {
         self.block_ref.column_count()
@@ -41,6 +54,49 @@ impl<'a, K: ColumnType> Row<'a, K> {
     pub fn sql_type<I: ColumnIdx + Copy>(&self, col: I) -> Result<SqlType> {
         Ok(self.block_ref.get_column(col)?.sql_type())
     }
+
+    /// Deserialize the row into `T`, columns are matched to fields by name.
+    #[cfg(feature = "serde")]
+    pub fn deserialize<T: serde::Deserialize<'a>>(&'a self) -> Result<T> {
+        T::deserialize(RowDeserializer::new(self))
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn value(&'a self, col: usize) -> Result<ValueRef<'a>> {
+        Ok(self.block_ref.get_column(col)?.at(self.row))
+    }
+}
+
+/// Decodes a struct from a [`Row`], see `#[derive(Row)]`.
+pub trait FromRow<'a>: Sized {
+    fn from_row<K: ColumnType>(row: &'a Row<'a, K>) -> Result<Self>;
+}
+
+macro_rules! tuple_from_row {
+    ( $( $n:literal: ( $($t:ident: $i:tt),+ ) ),* ) => {
+        $(
+            impl<'a, $($t: FromSql<'a>),+> FromRow<'a> for ($($t,)+) {
+                fn from_row<K: ColumnType>(row: &'a Row<'a, K>) -> Result<Self> {
+                    if row.len() != $n {
+                        let message = format!("Expected {} columns, got {}.", $n, row.len());
+                        return Err(message.into());
+                    }
+                    Ok(($(row.get::<$t, usize>($i)?,)+))
+                }
+            }
+        )*
+    };
+}
+
+tuple_from_row! {
+    1: (A: 0),
+    2: (A: 0, B: 1),
+    3: (A: 0, B: 1, C: 2),
+    4: (A: 0, B: 1, C: 2, D: 3),
+    5: (A: 0, B: 1, C: 2, D: 3, E: 4),
+    6: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+    7: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+    8: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
 }
 
 pub(crate) enum BlockRef<'a, K: ColumnType> {
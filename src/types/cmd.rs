@@ -1,16 +1,19 @@
+use std::collections::HashMap;
+
 use log::trace;
 
 use crate::{
     binary::{protocol, Encoder},
     client_info,
-    errors::Result,
-    types::{Context, Options, Query, SettingType, Simple},
+    errors::{Error, Result},
+    types::{query::param_to_string, Context, Options, Query, SettingType, SettingValue, Simple},
     Block,
 };
 
 /// Represents Clickhouse commands.
 pub(crate) enum Cmd {
     Hello(Context),
+    Addendum,
     Ping,
     SendQuery(Query, Context),
     SendData(Block, Context),
@@ -26,6 +29,11 @@ impl Cmd {
     }
 }
 
+const SETTING_FLAG_CUSTOM: u64 = 0x02;
+
+/// Makes the server send `JSON` columns as text, the only form `JsonColumnData` reads.
+const JSON_AS_STRING_SETTING: &str = "output_format_native_write_json_as_string";
+
 #[derive(Debug, PartialOrd, PartialEq)]
 enum SettingsBinaryFormat {
     Old,
@@ -35,6 +43,7 @@ enum SettingsBinaryFormat {
 fn encode_command(cmd: &Cmd) -> Result<Vec<u8>> {
     match cmd {
         Cmd::Hello(context) => encode_hello(context),
+        Cmd::Addendum => Ok(encode_addendum()),
         Cmd::Ping => Ok(encode_ping()),
         Cmd::SendQuery(query, context) => encode_query(query, context),
         Cmd::SendData(block, context) => encode_data(block, context),
@@ -59,6 +68,14 @@ fn encode_hello(context: &Context) -> Result<Vec<u8>> {
     Ok(encoder.get_buffer())
 }
 
+fn encode_addendum() -> Vec<u8> {
+    trace!("[addendum]     -> addendum");
+
+    let mut encoder = Encoder::new();
+    encoder.string(""); // quota key
+    encoder.get_buffer()
+}
+
 fn encode_ping() -> Vec<u8> {
     trace!("[ping]         -> ping");
 
@@ -78,6 +95,8 @@ fn encode_cancel() -> Vec<u8> {
 fn encode_query(query: &Query, context: &Context) -> Result<Vec<u8>> {
     trace!("[send query] {}", query.get_sql());
 
+    let revision = context.revision();
+
     // DBMS_MIN_REVISION_WITH_CLIENT_INFO
     let mut encoder = Encoder::new();
     encoder.uvarint(protocol::CLIENT_QUERY);
@@ -89,51 +108,141 @@ fn encode_query(query: &Query, context: &Context) -> Result<Vec<u8>> {
         encoder.string("");
         encoder.string(query.get_id()); // initial_query_id;
         encoder.string("[::ffff:127.0.0.1]:0");
+        if revision >= protocol::DBMS_MIN_PROTOCOL_VERSION_WITH_INITIAL_QUERY_START_TIME {
+            encoder.write(0_u64); // initial_query_start_time_microseconds
+        }
         encoder.uvarint(1); // iface type TCP;
         encoder.string(hostname);
         encoder.string(hostname);
     }
     client_info::write(&mut encoder);
 
-    if context.server_info.revision >= protocol::DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO {
+    if revision >= protocol::DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO {
         encoder.string("");
     }
 
-    if context.server_info.revision >= protocol::DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+    if revision >= protocol::DBMS_MIN_PROTOCOL_VERSION_WITH_DISTRIBUTED_DEPTH {
         encoder.uvarint(0);
     }
 
+    if revision >= protocol::DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+        encoder.uvarint(0);
+    }
+
+    if revision >= protocol::DBMS_MIN_REVISION_WITH_OPENTELEMETRY {
+        encoder.write(0_u8); // no trace context
+    }
+
+    if revision >= protocol::DBMS_MIN_REVISION_WITH_PARALLEL_REPLICAS {
+        encoder.uvarint(0); // collaborate_with_initiator
+        encoder.uvarint(0); // count_participating_replicas
+        encoder.uvarint(0); // number_of_current_replica
+    }
+
     let options = context.options.get()?;
 
-    let settings_format = if context.server_info.revision
-        >= protocol::DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS
-    {
-        SettingsBinaryFormat::Strings
-    } else {
-        SettingsBinaryFormat::Old
-    };
+    let settings_format =
+        if revision >= protocol::DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS {
+            SettingsBinaryFormat::Strings
+        } else {
+            SettingsBinaryFormat::Old
+        };
 
-    serialize_settings(&mut encoder, &options, settings_format);
+    serialize_settings(&mut encoder, &options, query, settings_format);
+
+    if revision >= protocol::DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET {
+        encoder.string("");
+    }
 
     encoder.uvarint(protocol::STATE_COMPLETE);
 
-    encoder.uvarint(if options.compression {
+    encoder.uvarint(if options.compression.is_enabled() {
         protocol::COMPRESS_ENABLE
     } else {
         protocol::COMPRESS_DISABLE
     });
 
-    let options = context.options.get()?;
-
     encoder.string(query.get_sql());
-    Block::<Simple>::default().send_data(&mut encoder, options.compression);
+
+    if revision >= protocol::DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS {
+        serialize_params(&mut encoder, query);
+    } else if !query.get_params().is_empty() {
+        return Err(Error::Other(
+            "Query parameters are not supported by the server.".into(),
+        ));
+    }
+
+    for (name, table) in query.get_external_tables() {
+        table.send_table_data(&mut encoder, name, options.compression, revision);
+    }
+
+    Block::<Simple>::default().send_data(&mut encoder, options.compression, revision);
 
     Ok(encoder.get_buffer())
 }
 
-fn serialize_settings(encoder: &mut Encoder, options: &Options, format: SettingsBinaryFormat) {
+/// Parameters are sent as custom settings with quoted values.
+fn serialize_params(encoder: &mut Encoder, query: &Query) {
+    for (name, value) in query.get_params() {
+        encoder.string(name);
+        encoder.uvarint(SETTING_FLAG_CUSTOM);
+        encoder.string(quote(&param_to_string(value)));
+    }
+
+    encoder.string(""); // end of parameters marker
+}
+
+fn quote(source: &str) -> String {
+    let mut result = String::with_capacity(source.len() + 2);
+    result.push('\'');
+    for ch in source.chars() {
+        if ch == '\\' || ch == '\'' {
+            result.push('\\');
+        }
+        result.push(ch);
+    }
+    result.push('\'');
+    result
+}
+
+/// Settings the driver sends on its own, the user ones take precedence over them.
+fn driver_settings(
+    options: &Options,
+    format: &SettingsBinaryFormat,
+) -> HashMap<String, SettingValue> {
+    let mut settings = options.compression.settings();
+
+    // Older servers fail on unknown settings, newer ones ignore the unimportant ones.
+    if *format >= SettingsBinaryFormat::Strings {
+        settings.insert(
+            JSON_AS_STRING_SETTING.into(),
+            SettingValue {
+                value: SettingType::Bool(true),
+                is_important: false,
+            },
+        );
+    }
+    settings
+}
+
+/// Query settings take precedence over the connection-wide ones.
+fn serialize_settings(
+    encoder: &mut Encoder,
+    options: &Options,
+    query: &Query,
+    format: SettingsBinaryFormat,
+) {
+    let query_settings = query.get_settings();
+    let driver_settings = driver_settings(options, &format);
+    let settings = driver_settings
+        .iter()
+        .filter(|(name, _)| !options.settings.contains_key(*name))
+        .chain(options.settings.iter())
+        .filter(|(name, _)| !query_settings.contains_key(*name))
+        .chain(query_settings);
+
     if format < SettingsBinaryFormat::Strings {
-        for (name, value) in &options.settings {
+        for (name, value) in settings {
             encoder.string(name);
             match &value.value {
                 SettingType::String(val) => encoder.string(val),
@@ -145,7 +254,7 @@ fn serialize_settings(encoder: &mut Encoder, options: &Options, format: Settings
             }
         }
     } else {
-        for (name, value) in &options.settings {
+        for (name, value) in settings {
             encoder.string(name);
             encoder.write(value.is_important);
             encoder.string(value.to_string());
@@ -158,7 +267,7 @@ fn serialize_settings(encoder: &mut Encoder, options: &Options, format: Settings
 fn encode_data(block: &Block, context: &Context) -> Result<Vec<u8>> {
     let mut encoder = Encoder::new();
     let options = context.options.get()?;
-    block.send_data(&mut encoder, options.compression);
+    block.send_data(&mut encoder, options.compression, context.revision());
     Ok(encoder.get_buffer())
 }
 
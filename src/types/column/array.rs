@@ -40,6 +40,19 @@ impl ArrayColumnData {
 
         Ok(ArrayColumnData { inner, offsets })
     }
+
+    /// Splits values of `inner` into consecutive arrays of the given lengths.
+    pub(crate) fn from_lengths(inner: ArcColumnData, lengths: &[usize]) -> Self {
+        let mut offsets = List::with_capacity(lengths.len());
+        let mut offset = 0_u64;
+        for length in lengths {
+            offset += *length as u64;
+            offsets.push(offset);
+        }
+        debug_assert_eq!(offset as usize, inner.len());
+
+        ArrayColumnData { inner, offsets }
+    }
 }
 
 impl ColumnData for ArrayColumnData {
@@ -143,7 +156,10 @@ mod test {
     use std::io::Cursor;
 
     use super::*;
-    use crate::types::{column::datetime64::DEFAULT_TZ, Block, Simple};
+    use crate::{
+        client_info::CLICK_HOUSE_REVISION,
+        types::{column::datetime64::DEFAULT_TZ, Block, CompressionMethod, Simple},
+    };
 
     #[test]
     fn test_write_and_read() {
@@ -153,10 +169,10 @@ mod test {
         );
 
         let mut encoder = Encoder::new();
-        block.write(&mut encoder, false);
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
 
         let mut reader = Cursor::new(encoder.get_buffer_ref());
-        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false).unwrap();
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
 
         assert_eq!(block, rblock);
     }
@@ -15,8 +15,9 @@ use crate::{
             list::List,
             nullable::NullableColumnData,
             numeric::save_data,
-            ArcColumnWrapper, ColumnFrom, ColumnWrapper,
+            ArcColumnData, ArcColumnWrapper, ColumnFrom, ColumnWrapper,
         },
+        date_converter::{days_since_epoch, fits_date},
         DateConverter, Marshal, SqlType, StatBuffer, Unmarshal, Value, ValueRef,
     },
 };
@@ -74,6 +75,19 @@ where
 
 impl ColumnFrom for Vec<NaiveDate> {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        if !source.iter().all(|d| fits_date(*d)) {
+            let mut data = List::<i32>::with_capacity(source.len());
+            for s in source {
+                data.push(days_since_epoch(s) as i32);
+            }
+
+            let column: DateColumnData<i32> = DateColumnData {
+                data,
+                tz: *DEFAULT_TZ,
+            };
+            return W::wrap(column);
+        }
+
         let mut data = List::<u16>::with_capacity(source.len());
         for s in source {
             data.push(u16::get_days(s));
@@ -87,10 +101,19 @@ impl ColumnFrom for Vec<NaiveDate> {
     }
 }
 
+/// Empty `Date` column, or `Date32` one if `wide` is set.
+fn empty_date_column(wide: bool) -> ArcColumnData {
+    if wide {
+        ArcColumnWrapper::wrap(DateColumnData::<i32>::with_capacity(0, *DEFAULT_TZ))
+    } else {
+        ArcColumnWrapper::wrap(DateColumnData::<u16>::with_capacity(0, *DEFAULT_TZ))
+    }
+}
+
 impl ColumnFrom for Vec<Vec<NaiveDate>> {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let fake: Vec<NaiveDate> = Vec::with_capacity(source.len());
-        let inner = Vec::column_from::<ArcColumnWrapper>(fake);
+        let wide = !source.iter().flatten().all(|d| fits_date(*d));
+        let inner = empty_date_column(wide);
         let sql_type = inner.sql_type();
 
         let mut data = ArrayColumnData {
@@ -101,9 +124,7 @@ impl ColumnFrom for Vec<Vec<NaiveDate>> {
         for vs in source {
             let mut inner = Vec::with_capacity(vs.len());
             for v in vs {
-                let days = u16::get_days(v);
-                let value: Value = Value::Date(days);
-                inner.push(value);
+                inner.push(Value::from(v));
             }
             data.push(Value::Array(sql_type.clone().into(), Arc::new(inner)));
         }
@@ -138,8 +159,9 @@ impl ColumnFrom for Vec<Vec<DateTime<Tz>>> {
 
 impl ColumnFrom for Vec<Option<NaiveDate>> {
     fn column_from<W: ColumnWrapper>(source: Self) -> <W as ColumnWrapper>::Wrapper {
-        let fake: Vec<NaiveDate> = Vec::with_capacity(source.len());
-        let inner = Vec::column_from::<ArcColumnWrapper>(fake);
+        let wide = !source.iter().flatten().all(|d| fits_date(*d));
+        let inner = empty_date_column(wide);
+        let sql_type = inner.sql_type();
 
         let mut data = NullableColumnData {
             inner,
@@ -148,10 +170,9 @@ impl ColumnFrom for Vec<Option<NaiveDate>> {
 
         for value in source {
             match value {
-                None => data.push(Value::Nullable(Either::Left(SqlType::Date.into()))),
+                None => data.push(Value::Nullable(Either::Left(sql_type.clone().into()))),
                 Some(d) => {
-                    let days = u16::get_days(d);
-                    let value = Value::Date(days);
+                    let value = Value::from(d);
                     data.push(Value::Nullable(Either::Right(Box::new(value))))
                 }
             }
@@ -288,6 +309,17 @@ mod test {
         assert_eq!(SqlType::Date, column.sql_type());
     }
 
+    #[test]
+    fn test_create_date32() {
+        let column = Vec::column_from::<ArcColumnWrapper>(vec![
+            NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2016, 10, 22).unwrap(),
+        ]);
+        assert_eq!("1900-01-01", format!("{:#}", column.at(0)));
+        assert_eq!("2016-10-22", format!("{:#}", column.at(1)));
+        assert_eq!(SqlType::Date32, column.sql_type());
+    }
+
     #[test]
     fn test_create_date_time() {
         let tz = *DEFAULT_TZ;
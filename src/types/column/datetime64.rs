@@ -118,8 +118,28 @@ impl ColumnData for DateTime64ColumnData {
 
 pub(crate) fn from_datetime<T: TimeZone>(time: DateTime<T>, precision: u32) -> i64 {
     let base10: i64 = 10;
-    let timestamp = time.timestamp_nanos_opt().unwrap();
-    timestamp / base10.pow(9 - precision)
+    let precision = precision.min(9);
+    let fraction = i64::from(time.timestamp_subsec_nanos()) / base10.pow(9 - precision);
+    time.timestamp() * base10.pow(precision) + fraction
+}
+
+/// Splits a `DateTime64` value into whole seconds and nanoseconds.
+///
+/// Seconds are floored, so pre-1970 values keep a non-negative fraction,
+/// and the value is never scaled to nanoseconds as a whole, so dates past
+/// 2262 don't overflow.
+#[inline(always)]
+fn split_timestamp(value: i64, precision: u32) -> Option<(i64, u32)> {
+    if precision > 9 {
+        return None;
+    }
+
+    let base10: i64 = 10;
+    let scale = base10.pow(precision);
+    let sec = value.div_euclid(scale);
+    let nsec = value.rem_euclid(scale) * base10.pow(9 - precision);
+
+    Some((sec, nsec as u32))
 }
 
 #[inline(always)]
@@ -129,34 +149,16 @@ pub(crate) fn to_datetime(value: i64, precision: u32, tz: Tz) -> DateTime<Tz> {
 
 #[inline(always)]
 pub(crate) fn to_datetime_opt(value: i64, precision: u32, tz: Tz) -> LocalResult<DateTime<Tz>> {
-    let base10: i64 = 10;
-
-    let nano = if precision < 19 {
-        value * base10.pow(9 - precision)
-    } else {
-        0_i64
-    };
-
-    let sec = nano / 1_000_000_000;
-    let nsec = nano - sec * 1_000_000_000;
-
-    tz.timestamp_opt(sec, nsec as u32)
+    match split_timestamp(value, precision) {
+        Some((sec, nsec)) => tz.timestamp_opt(sec, nsec),
+        None => LocalResult::None,
+    }
 }
 
 #[inline(always)]
 pub(crate) fn to_native_datetime_opt(value: i64, precision: u32) -> Option<NaiveDateTime> {
-    let base10: i64 = 10;
-
-    let nano = if precision < 19 {
-        value * base10.pow(9 - precision)
-    } else {
-        0_i64
-    };
-
-    let sec = nano / 1_000_000_000;
-    let nsec = nano - sec * 1_000_000_000;
-
-    NaiveDateTime::from_timestamp_opt(sec, nsec as u32)
+    let (sec, nsec) = split_timestamp(value, precision)?;
+    NaiveDateTime::from_timestamp_opt(sec, nsec)
 }
 
 #[cfg(test)]
@@ -176,4 +178,24 @@ mod test {
         let actual = from_datetime(origin, 3);
         assert_eq!(actual, 1_546_300_800_000)
     }
+
+    #[test]
+    fn test_to_datetime_before_epoch() {
+        let expected = DateTime::parse_from_rfc3339("1969-12-31T23:59:59.5-00:00").unwrap();
+        let actual = to_datetime(-5, 1, Tz::UTC);
+        assert_eq!(actual, expected);
+        assert_eq!(from_datetime(actual, 1), -5);
+
+        let naive = to_native_datetime_opt(-1_500, 3).unwrap();
+        assert_eq!(naive, expected.naive_utc() - chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    fn test_to_datetime_far_future() {
+        let expected = DateTime::parse_from_rfc3339("2299-12-31T23:59:59.123-00:00").unwrap();
+        let value = from_datetime(expected, 3);
+        assert_eq!(value, 10_413_791_999_123);
+        assert_eq!(to_datetime(value, 3, Tz::UTC), expected);
+        assert_eq!(to_native_datetime_opt(value, 3), Some(expected.naive_utc()));
+    }
 }
@@ -1,5 +1,6 @@
 use chrono_tz::Tz;
 use either::Either;
+use ethnum::I256;
 use std::sync::Arc;
 
 use crate::{
@@ -15,7 +16,7 @@ use crate::{
         },
         decimal::{Decimal, NoBits},
         from_sql::FromSql,
-        Column, ColumnType, SqlType, Value, ValueRef,
+        Column, ColumnType, Marshal, SqlType, StatBuffer, Unmarshal, Value, ValueRef,
     },
 };
 
@@ -26,6 +27,11 @@ pub(crate) struct DecimalColumnData {
     pub(crate) nobits: NoBits,
 }
 
+/// Keeps `Decimal256` values, there is no 256-bit integer column to back them.
+pub(crate) struct Decimal256Data {
+    data: Vec<I256>,
+}
+
 pub(crate) struct DecimalAdapter<K: ColumnType> {
     pub(crate) column: Column<K>,
     pub(crate) precision: u8,
@@ -41,6 +47,25 @@ pub(crate) struct NullableDecimalAdapter<K: ColumnType> {
 }
 
 impl DecimalColumnData {
+    pub(crate) fn with_capacity(precision: u8, scale: u8, capacity: usize) -> Self {
+        let nobits = NoBits::from_precision(precision).unwrap();
+        let inner: BoxColumnData = match nobits {
+            NoBits::N32 => Box::new(VectorColumnData::<i32>::with_capacity(capacity)),
+            NoBits::N64 => Box::new(VectorColumnData::<i64>::with_capacity(capacity)),
+            NoBits::N128 => Box::new(VectorColumnData::<i128>::with_capacity(capacity)),
+            NoBits::N256 => Box::new(Decimal256Data {
+                data: Vec::with_capacity(capacity),
+            }),
+        };
+
+        DecimalColumnData {
+            inner,
+            precision,
+            scale,
+            nobits,
+        }
+    }
+
     pub(crate) fn load<T: ReadEx>(
         reader: &mut T,
         precision: u8,
@@ -52,6 +77,15 @@ impl DecimalColumnData {
         let type_name = match nobits {
             NoBits::N32 => "Int32",
             NoBits::N64 => "Int64",
+            NoBits::N128 => "Int128",
+            NoBits::N256 => {
+                return Ok(DecimalColumnData {
+                    inner: Box::new(Decimal256Data::load(reader, size)?),
+                    precision,
+                    scale,
+                    nobits,
+                })
+            }
         };
         let inner =
             <dyn ColumnData>::load_data::<BoxColumnWrapper, _>(reader, type_name, size, tz)?;
@@ -65,9 +99,61 @@ impl DecimalColumnData {
     }
 }
 
+impl Decimal256Data {
+    fn load<T: ReadEx>(reader: &mut T, size: usize) -> Result<Self> {
+        let mut data = Vec::with_capacity(size);
+        for _ in 0..size {
+            let low: i128 = reader.read_scalar()?;
+            let high: i128 = reader.read_scalar()?;
+            data.push(I256::from_words(high, low));
+        }
+        Ok(Self { data })
+    }
+}
+
+fn write_decimal(encoder: &mut Encoder, nobits: NoBits, underlying: I256) {
+    match nobits {
+        NoBits::N32 => encoder.write(underlying.as_i32()),
+        NoBits::N64 => encoder.write(underlying.as_i64()),
+        NoBits::N128 => encoder.write(underlying.as_i128()),
+        NoBits::N256 => {
+            let (high, low) = underlying.into_words();
+            encoder.write(low);
+            encoder.write(high);
+        }
+    }
+}
+
+fn list<T, F>(data: &[I256], f: F) -> List<T>
+where
+    T: StatBuffer + Unmarshal<T> + Marshal + Copy + Sync + 'static,
+    F: Fn(I256) -> T,
+{
+    let mut list = List::with_capacity(data.len());
+    for v in data {
+        list.push(f(*v));
+    }
+    list
+}
+
+fn decimal_data(data: Vec<I256>, nobits: NoBits) -> BoxColumnData {
+    match nobits {
+        NoBits::N32 => Box::new(VectorColumnData {
+            data: list(&data, |v| v.as_i32()),
+        }),
+        NoBits::N64 => Box::new(VectorColumnData {
+            data: list(&data, |v| v.as_i64()),
+        }),
+        NoBits::N128 => Box::new(VectorColumnData {
+            data: list(&data, |v| v.as_i128()),
+        }),
+        NoBits::N256 => Box::new(Decimal256Data { data }),
+    }
+}
+
 impl ColumnFrom for Vec<Decimal> {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let mut data = List::<i64>::with_capacity(source.len());
+        let mut data = Vec::<I256>::with_capacity(source.len());
         let mut precision = 18;
         let mut opt_scale = None;
         for s in source {
@@ -83,13 +169,14 @@ impl ColumnFrom for Vec<Decimal> {
             data.push(s.internal());
         }
         let scale = opt_scale.unwrap_or(4);
-        let inner = Box::new(VectorColumnData { data });
+        let nobits = NoBits::from_precision(precision).unwrap();
+        let inner = decimal_data(data, nobits);
 
         let column = DecimalColumnData {
             inner,
             precision,
             scale,
-            nobits: NoBits::N64,
+            nobits,
         };
 
         W::wrap(column)
@@ -99,7 +186,7 @@ impl ColumnFrom for Vec<Decimal> {
 impl ColumnFrom for Vec<Option<Decimal>> {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
         let mut nulls: Vec<u8> = Vec::with_capacity(source.len());
-        let mut data = List::<i64>::with_capacity(source.len());
+        let mut data = Vec::<I256>::with_capacity(source.len());
         let mut precision = 18;
         let mut opt_scale = None;
         for os in source {
@@ -116,18 +203,19 @@ impl ColumnFrom for Vec<Option<Decimal>> {
                 data.push(s.internal());
                 nulls.push(0);
             } else {
-                data.push(0);
+                data.push(I256::ZERO);
                 nulls.push(1);
             }
         }
         let scale = opt_scale.unwrap_or(4);
-        let inner = Box::new(VectorColumnData { data });
+        let nobits = NoBits::from_precision(precision).unwrap();
+        let inner = decimal_data(data, nobits);
 
         let inner = DecimalColumnData {
             inner,
             precision,
             scale,
-            nobits: NoBits::N64,
+            nobits,
         };
 
         W::wrap(NullableColumnData {
@@ -161,6 +249,11 @@ impl ColumnData for DecimalColumnData {
                     let internal: i64 = decimal.internal();
                     self.inner.push(internal.into())
                 }
+                NoBits::N128 => {
+                    let internal: i128 = decimal.internal();
+                    self.inner.push(internal.into())
+                }
+                NoBits::N256 => self.inner.push(Value::Decimal(decimal)),
             }
         } else {
             panic!("value should be decimal ({value:?})");
@@ -168,9 +261,14 @@ impl ColumnData for DecimalColumnData {
     }
 
     fn at(&self, index: usize) -> ValueRef {
-        let underlying: i64 = match self.nobits {
-            NoBits::N32 => i64::from(i32::from(self.inner.at(index))),
-            NoBits::N64 => i64::from(self.inner.at(index)),
+        let underlying: I256 = match self.nobits {
+            NoBits::N32 => I256::from(i32::from(self.inner.at(index))),
+            NoBits::N64 => I256::from(i64::from(self.inner.at(index))),
+            NoBits::N128 => I256::from(i128::from(self.inner.at(index))),
+            NoBits::N256 => match self.inner.at(index) {
+                ValueRef::Decimal(decimal) => decimal.underlying,
+                _ => panic!("should be decimal"),
+            },
         };
 
         ValueRef::Decimal(Decimal {
@@ -207,6 +305,61 @@ impl ColumnData for DecimalColumnData {
     }
 }
 
+impl ColumnData for Decimal256Data {
+    fn sql_type(&self) -> SqlType {
+        SqlType::Decimal(76, 0)
+    }
+
+    fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
+        for underlying in &self.data[start..end] {
+            write_decimal(encoder, NoBits::N256, *underlying);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn push(&mut self, value: Value) {
+        if let Value::Decimal(decimal) = value {
+            self.data.push(decimal.underlying);
+        } else {
+            panic!("value should be decimal ({value:?})");
+        }
+    }
+
+    fn at(&self, index: usize) -> ValueRef {
+        ValueRef::Decimal(Decimal {
+            underlying: self.data[index],
+            precision: 76,
+            scale: 0,
+            nobits: NoBits::N256,
+        })
+    }
+
+    fn clone_instance(&self) -> BoxColumnData {
+        Box::new(Self {
+            data: self.data.clone(),
+        })
+    }
+
+    unsafe fn get_internal(
+        &self,
+        pointers: &[*mut *const u8],
+        level: u8,
+        _props: u32,
+    ) -> Result<()> {
+        assert_eq!(level, 0);
+        *pointers[0] = self.data.as_ptr() as *const u8;
+        *(pointers[1] as *mut usize) = self.data.len();
+        Ok(())
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        None
+    }
+}
+
 impl<K: ColumnType> ColumnData for DecimalAdapter<K> {
     fn sql_type(&self) -> SqlType {
         SqlType::Decimal(self.precision, self.scale)
@@ -215,16 +368,7 @@ impl<K: ColumnType> ColumnData for DecimalAdapter<K> {
     fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
         for i in start..end {
             if let ValueRef::Decimal(decimal) = self.at(i) {
-                match self.nobits {
-                    NoBits::N32 => {
-                        let internal: i32 = decimal.internal();
-                        encoder.write(internal);
-                    }
-                    NoBits::N64 => {
-                        let internal: i64 = decimal.internal();
-                        encoder.write(internal);
-                    }
-                }
+                write_decimal(encoder, self.nobits, decimal.underlying);
             } else {
                 panic!("should be decimal");
             }
@@ -269,16 +413,8 @@ impl<K: ColumnType> ColumnData for NullableDecimalAdapter<K> {
         encoder.write_bytes(nulls.as_ref());
 
         for value in values {
-            let underlying = if let Some(v) = value { v.underlying } else { 0 };
-
-            match self.nobits {
-                NoBits::N32 => {
-                    encoder.write(underlying as i32);
-                }
-                NoBits::N64 => {
-                    encoder.write(underlying);
-                }
-            }
+            let underlying = value.map_or(I256::ZERO, |v| v.underlying);
+            write_decimal(encoder, self.nobits, underlying);
         }
     }
 
@@ -312,3 +448,62 @@ impl<K: ColumnType> ColumnData for NullableDecimalAdapter<K> {
         self.column.data.get_timezone()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        binary::Encoder,
+        client_info::CLICK_HOUSE_REVISION,
+        types::{Block, CompressionMethod, Simple, DEFAULT_TZ},
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_and_read_256() {
+        let big = I256::from(i128::MAX) * 1000;
+        let values = vec![Decimal::new(big, 2), Decimal::new(-big, 2)];
+        let nullable = vec![Some(Decimal::new(big, 2)), None];
+
+        let block = Block::<Simple>::new()
+            .column("d", values.clone())
+            .column("n", nullable.clone());
+
+        let mut encoder = Encoder::new();
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
+
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
+
+        assert_eq!(block, rblock);
+        assert_eq!(
+            rblock.get_column("d").unwrap().sql_type(),
+            SqlType::Decimal(76, 2)
+        );
+
+        let actual: Vec<Decimal> = rblock
+            .get_column("d")
+            .unwrap()
+            .iter::<Decimal>()
+            .unwrap()
+            .collect();
+        assert_eq!(actual, values);
+
+        let actual: Option<Decimal> = rblock.get(0, "n").unwrap();
+        assert_eq!(actual, nullable[0]);
+        assert_eq!(actual.unwrap().internal::<I256>(), big);
+    }
+
+    #[test]
+    fn test_cast_to_256() {
+        let block = Block::<Simple>::new().column("d", vec![Decimal::of(1.5_f64, 2)]);
+        let column = block.get_column("d").unwrap().clone();
+
+        let column = column.cast_to(SqlType::Decimal(76, 40)).unwrap();
+        let mut encoder = Encoder::new();
+        column.data.save(&mut encoder, 0, 1);
+
+        let expected = I256::new(15) * I256::new(10).pow(39);
+        assert_eq!(encoder.get_buffer(), expected.to_le_bytes().to_vec());
+    }
+}
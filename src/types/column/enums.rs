@@ -13,12 +13,36 @@ use crate::{
             nullable::NullableColumnData,
             ArcColumnWrapper, BoxColumnWrapper, ColumnFrom, ColumnWrapper, VectorColumnData,
         },
-        enums::{Enum16, Enum8},
+        enums::{enum_value, Enum16, Enum8, EnumType},
         from_sql::FromSql,
         Column, ColumnType, SqlType, Value, ValueRef,
     },
 };
 
+/// Pairs values of the source enum with the values of the same names in the target enum.
+/// A source without names (built out of raw `Enum8`/`Enum16` values) is written as is.
+pub(crate) fn enum_mapping<T: Copy>(
+    src: &[(String, T)],
+    dst: &[(String, T)],
+    dst_type: &SqlType,
+) -> Result<Vec<(T, T)>> {
+    let mut mapping = Vec::with_capacity(src.len());
+    for (name, value) in src {
+        match dst.iter().find(|(dst_name, _)| dst_name == name) {
+            Some((_, dst_value)) => mapping.push((*value, *dst_value)),
+            None => return Err(format!("Enum value '{name}' isn't defined in {dst_type}.").into()),
+        }
+    }
+    Ok(mapping)
+}
+
+fn map_enum_value<T: Copy + PartialEq>(mapping: &[(T, T)], value: T) -> T {
+    mapping
+        .iter()
+        .find(|(src, _)| *src == value)
+        .map_or(value, |(_, dst)| *dst)
+}
+
 pub(crate) struct Enum16ColumnData {
     pub(crate) enum_values: Vec<(String, i16)>,
     pub(crate) inner: Box<dyn ColumnData + Send + Sync>,
@@ -27,11 +51,13 @@ pub(crate) struct Enum16ColumnData {
 pub(crate) struct Enum16Adapter<K: ColumnType> {
     pub(crate) column: Column<K>,
     pub(crate) enum_values: Vec<(String, i16)>,
+    pub(crate) mapping: Vec<(i16, i16)>,
 }
 
 pub(crate) struct NullableEnum16Adapter<K: ColumnType> {
     pub(crate) column: Column<K>,
     pub(crate) enum_values: Vec<(String, i16)>,
+    pub(crate) mapping: Vec<(i16, i16)>,
 }
 
 impl Enum16ColumnData {
@@ -73,7 +99,7 @@ impl ColumnData for Enum16ColumnData {
 
     fn at(&self, index: usize) -> ValueRef {
         let enum_value = i16::from(self.inner.at(index));
-        ValueRef::Enum16(self.enum_values.clone(), Enum16(enum_value))
+        ValueRef::Enum16(&self.enum_values, Enum16(enum_value))
     }
 
     fn clone_instance(&self) -> BoxColumnData {
@@ -124,8 +150,9 @@ impl<K: ColumnType> ColumnData for Enum16Adapter<K> {
     }
 
     fn at(&self, index: usize) -> ValueRef {
-        if let ValueRef::Enum16(enum_values, value) = self.column.at(index) {
-            ValueRef::Enum16(enum_values, value)
+        if let ValueRef::Enum16(_enum_values, value) = self.column.at(index) {
+            let value = map_enum_value(&self.mapping, value.internal());
+            ValueRef::Enum16(&self.enum_values, Enum16(value))
         } else {
             panic!("should be Enum");
         }
@@ -178,7 +205,8 @@ impl<K: ColumnType> ColumnData for NullableEnum16Adapter<K> {
         match value {
             None => ValueRef::Nullable(Either::Left(self.sql_type().into())),
             Some(v) => {
-                let inner = ValueRef::Enum16(self.enum_values.clone(), v);
+                let value = map_enum_value(&self.mapping, v.internal());
+                let inner = ValueRef::Enum16(&self.enum_values, Enum16(value));
                 ValueRef::Nullable(Either::Right(Box::new(inner)))
             }
         }
@@ -269,11 +297,13 @@ pub(crate) struct Enum8ColumnData {
 pub(crate) struct Enum8Adapter<K: ColumnType> {
     pub(crate) column: Column<K>,
     pub(crate) enum_values: Vec<(String, i8)>,
+    pub(crate) mapping: Vec<(i8, i8)>,
 }
 
 pub(crate) struct NullableEnum8Adapter<K: ColumnType> {
     pub(crate) column: Column<K>,
     pub(crate) enum_values: Vec<(String, i8)>,
+    pub(crate) mapping: Vec<(i8, i8)>,
 }
 
 impl Enum8ColumnData {
@@ -315,7 +345,7 @@ impl ColumnData for Enum8ColumnData {
 
     fn at(&self, index: usize) -> ValueRef {
         let enum_value = i8::from(self.inner.at(index));
-        ValueRef::Enum8(self.enum_values.clone(), Enum8(enum_value))
+        ValueRef::Enum8(&self.enum_values, Enum8(enum_value))
     }
 
     fn clone_instance(&self) -> BoxColumnData {
@@ -366,8 +396,9 @@ impl<K: ColumnType> ColumnData for Enum8Adapter<K> {
     }
 
     fn at(&self, index: usize) -> ValueRef {
-        if let ValueRef::Enum8(enum_values, value) = self.column.at(index) {
-            ValueRef::Enum8(enum_values, value)
+        if let ValueRef::Enum8(_enum_values, value) = self.column.at(index) {
+            let value = map_enum_value(&self.mapping, value.internal());
+            ValueRef::Enum8(&self.enum_values, Enum8(value))
         } else {
             panic!("should be Enum");
         }
@@ -421,7 +452,8 @@ impl<K: ColumnType> ColumnData for NullableEnum8Adapter<K> {
         match value {
             None => ValueRef::Nullable(Either::Left(self.sql_type().into())),
             Some(v) => {
-                let inner = ValueRef::Enum8(self.enum_values.clone(), v);
+                let value = map_enum_value(&self.mapping, v.internal());
+                let inner = ValueRef::Enum8(&self.enum_values, Enum8(value));
                 ValueRef::Nullable(Either::Right(Box::new(inner)))
             }
         }
@@ -503,3 +535,93 @@ impl ColumnFrom for Vec<Option<Enum8>> {
         })
     }
 }
+
+impl<T: EnumType> ColumnFrom for Vec<T> {
+    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        match T::get_sql_type() {
+            SqlType::Enum8(enum_values) => {
+                let mut data = List::<i8>::with_capacity(source.len());
+                for s in source {
+                    data.push(enum_value(&enum_values, s.name()));
+                }
+                let inner = Box::new(VectorColumnData { data });
+                W::wrap(Enum8ColumnData { enum_values, inner })
+            }
+            SqlType::Enum16(enum_values) => {
+                let mut data = List::<i16>::with_capacity(source.len());
+                for s in source {
+                    data.push(enum_value(&enum_values, s.name()));
+                }
+                let inner = Box::new(VectorColumnData { data });
+                W::wrap(Enum16ColumnData { enum_values, inner })
+            }
+            sql_type => panic!("{sql_type} isn't an enum type"),
+        }
+    }
+}
+
+impl<T: EnumType> ColumnFrom for Vec<Option<T>> {
+    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        let fake: Vec<T> = Vec::with_capacity(source.len());
+        let inner = Vec::column_from::<ArcColumnWrapper>(fake);
+
+        let mut data = NullableColumnData {
+            inner,
+            nulls: Vec::with_capacity(source.len()),
+        };
+
+        for value in source {
+            data.push(match value {
+                Some(v) => Value::Nullable(Either::Right(Box::new(v.to_value()))),
+                None => Value::Nullable(Either::Left(T::get_sql_type().into())),
+            });
+        }
+
+        W::wrap(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{row, types::Simple, Block};
+
+    fn values(items: &[(&str, i8)]) -> Vec<(String, i8)> {
+        items
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn test_cast_by_name() {
+        let src = values(&[("a", 1), ("b", 2)]);
+        let mut block = Block::<Simple>::new();
+        block
+            .push(row! { e: Value::Enum8(src.clone(), Enum8(2)) })
+            .unwrap();
+        block.push(row! { e: Value::Enum8(src, Enum8(1)) }).unwrap();
+        let column = block.get_column("e").unwrap().clone();
+
+        let dst = values(&[("b", -1), ("a", 0), ("c", 5)]);
+        let column = column.cast_to(SqlType::Enum8(dst.clone())).unwrap();
+        assert_eq!(column.at(0), ValueRef::Enum8(&dst, Enum8(-1)));
+        assert_eq!(column.at(1), ValueRef::Enum8(&dst, Enum8(0)));
+        assert_eq!(column.at(1).as_str().unwrap(), "a");
+    }
+
+    #[test]
+    fn test_cast_unknown_name() {
+        let block = Block::<Simple>::new().column("e", vec![Some(Enum8(1))]);
+        let column = block.get_column("e").unwrap().clone();
+        let dst = SqlType::Enum8(values(&[("x", 1)]));
+        assert!(column.cast_to(SqlType::Nullable(dst.into())).is_ok());
+
+        let mut block = Block::<Simple>::new();
+        let src = values(&[("a", 1), ("b", 2)]);
+        block.push(row! { e: Value::Enum8(src, Enum8(1)) }).unwrap();
+        let column = block.get_column("e").unwrap().clone();
+        let dst = SqlType::Enum8(values(&[("a", 1)]));
+        assert!(column.cast_to(dst).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use combine::{
     parser::char::{digit, spaces, string},
     sep_by1, token, Parser,
 };
+use ethnum::{I256, U256};
 
 use crate::{
     binary::ReadEx,
@@ -21,7 +22,9 @@ use crate::{
             decimal::DecimalColumnData,
             enums::{Enum16ColumnData, Enum8ColumnData},
             fixed_string::FixedStringColumnData,
+            geo::GeoColumnData,
             ip::{IpColumnData, Ipv4, Ipv6, Uuid},
+            json::JsonColumnData,
             list::List,
             low_cardinality::LowCardinalityColumnData,
             map::MapColumnData,
@@ -29,6 +32,8 @@ use crate::{
             numeric::VectorColumnData,
             simple_agg_func::SimpleAggregateFunctionColumnData,
             string::StringColumnData,
+            tuple::TupleColumnData,
+            variant::VariantColumnData,
             ArcColumnWrapper, BoxColumnWrapper, ColumnWrapper,
         },
         decimal::NoBits,
@@ -68,18 +73,28 @@ impl dyn ColumnData {
             "UInt32" => W::wrap(VectorColumnData::<u32>::load(reader, size)?),
             "UInt64" => W::wrap(VectorColumnData::<u64>::load(reader, size)?),
             "UInt128" => W::wrap(VectorColumnData::<u128>::load(reader, size)?),
+            "UInt256" => W::wrap(VectorColumnData::<U256>::load(reader, size)?),
             "Int8" | "TinyInt" => W::wrap(VectorColumnData::<i8>::load(reader, size)?),
             "Int16" | "SmallInt" => W::wrap(VectorColumnData::<i16>::load(reader, size)?),
             "Int32" | "Int" | "Integer" => W::wrap(VectorColumnData::<i32>::load(reader, size)?),
             "Int64" | "BigInt" => W::wrap(VectorColumnData::<i64>::load(reader, size)?),
             "Int128" => W::wrap(VectorColumnData::<i128>::load(reader, size)?),
+            "Int256" => W::wrap(VectorColumnData::<I256>::load(reader, size)?),
             "Float32" | "Float" => W::wrap(VectorColumnData::<f32>::load(reader, size)?),
             "Float64" | "Double" => W::wrap(VectorColumnData::<f64>::load(reader, size)?),
             "String" | "Char" | "Varchar" | "Text" | "TinyText" | "MediumText" | "LongText" | "Blob" | "TinyBlob" | "MediumBlob" | "LongBlob" => W::wrap(StringColumnData::load(reader, size)?),
             "Date" => W::wrap(DateColumnData::<u16>::load(reader, size, tz)?),
+            "Date32" => W::wrap(DateColumnData::<i32>::load(reader, size, tz)?),
             "IPv4" => W::wrap(IpColumnData::<Ipv4>::load(reader, size)?),
             "IPv6" => W::wrap(IpColumnData::<Ipv6>::load(reader, size)?),
             "UUID" => W::wrap(IpColumnData::<Uuid>::load(reader, size)?),
+            "Point" => W::wrap(GeoColumnData::load(reader, SqlType::Point, size, tz)?),
+            "Ring" => W::wrap(GeoColumnData::load(reader, SqlType::Ring, size, tz)?),
+            "Polygon" => W::wrap(GeoColumnData::load(reader, SqlType::Polygon, size, tz)?),
+            "MultiPolygon" => W::wrap(GeoColumnData::load(reader, SqlType::MultiPolygon, size, tz)?),
+            "JSON" => W::wrap(JsonColumnData::load(reader, size)?),
+            "Object('json')" => W::wrap(JsonColumnData::load_object(reader, size, tz)?),
+            "Dynamic" => W::wrap(VariantColumnData::load_dynamic(reader, size, tz)?),
             _ => {
                 if let Some(inner_type) = parse_nullable_type(type_name) {
                     W::wrap(NullableColumnData::load(reader, inner_type, size, tz)?)
@@ -107,6 +122,14 @@ impl dyn ColumnData {
                     W::wrap(SimpleAggregateFunctionColumnData::load(reader, func, inner_type, size, tz)?)
                 } else if let Some(inner_type) = parse_low_cardinality(type_name) {
                     W::wrap(LowCardinalityColumnData::load(reader, inner_type, size, tz)?)
+                } else if let Some(inner_types) = parse_tuple_type(type_name) {
+                    W::wrap(TupleColumnData::load(reader, inner_types, size, tz)?)
+                } else if let Some(inner_types) = parse_variant_type(type_name) {
+                    W::wrap(VariantColumnData::load(reader, inner_types, size, tz)?)
+                } else if type_name.starts_with("JSON(") {
+                    W::wrap(JsonColumnData::load(reader, size)?)
+                } else if type_name.starts_with("Dynamic(") {
+                    W::wrap(VariantColumnData::load_dynamic(reader, size, tz)?)
                 } else {
                     let message = format!("Unsupported column type \"{type_name}\".");
                     return Err(message.into());
@@ -127,11 +150,13 @@ impl dyn ColumnData {
             SqlType::UInt32 => W::wrap(VectorColumnData::<u32>::with_capacity(capacity)),
             SqlType::UInt64 => W::wrap(VectorColumnData::<u64>::with_capacity(capacity)),
             SqlType::UInt128 => W::wrap(VectorColumnData::<u128>::with_capacity(capacity)),
+            SqlType::UInt256 => W::wrap(VectorColumnData::<U256>::with_capacity(capacity)),
             SqlType::Int8 => W::wrap(VectorColumnData::<i8>::with_capacity(capacity)),
             SqlType::Int16 => W::wrap(VectorColumnData::<i16>::with_capacity(capacity)),
             SqlType::Int32 => W::wrap(VectorColumnData::<i32>::with_capacity(capacity)),
             SqlType::Int64 => W::wrap(VectorColumnData::<i64>::with_capacity(capacity)),
             SqlType::Int128 => W::wrap(VectorColumnData::<i128>::with_capacity(capacity)),
+            SqlType::Int256 => W::wrap(VectorColumnData::<I256>::with_capacity(capacity)),
             SqlType::String => W::wrap(StringColumnData::with_capacity(capacity)),
             SqlType::FixedString(len) => {
                 W::wrap(FixedStringColumnData::with_capacity(capacity, len))
@@ -144,6 +169,7 @@ impl dyn ColumnData {
             SqlType::Uuid => W::wrap(IpColumnData::<Uuid>::with_capacity(capacity)),
 
             SqlType::Date => W::wrap(DateColumnData::<u16>::with_capacity(capacity, timezone)),
+            SqlType::Date32 => W::wrap(DateColumnData::<i32>::with_capacity(capacity, timezone)),
             SqlType::DateTime(DateTimeType::DateTime64(precision, timezone)) => W::wrap(
                 DateTime64ColumnData::with_capacity(capacity, precision, timezone),
             ),
@@ -177,21 +203,7 @@ impl dyn ColumnData {
                 })
             }
             SqlType::Decimal(precision, scale) => {
-                let nobits = NoBits::from_precision(precision).unwrap();
-
-                let inner_type = match nobits {
-                    NoBits::N32 => SqlType::Int32,
-                    NoBits::N64 => SqlType::Int64,
-                };
-
-                W::wrap(DecimalColumnData {
-                    inner: <dyn ColumnData>::from_type::<BoxColumnWrapper>(
-                        inner_type, timezone, capacity,
-                    )?,
-                    precision,
-                    scale,
-                    nobits,
-                })
+                W::wrap(DecimalColumnData::with_capacity(precision, scale, capacity))
             }
             SqlType::Enum8(enum_values) => W::wrap(Enum8ColumnData {
                 enum_values,
@@ -223,6 +235,24 @@ impl dyn ColumnData {
                 )?,
                 size: 0,
             }),
+            SqlType::Tuple(types) => {
+                let mut inner = Vec::with_capacity(types.len());
+                for inner_type in types {
+                    inner.push(<dyn ColumnData>::from_type::<ArcColumnWrapper>(
+                        inner_type.clone(),
+                        timezone,
+                        capacity,
+                    )?);
+                }
+                W::wrap(TupleColumnData { inner })
+            }
+            SqlType::Point | SqlType::Ring | SqlType::Polygon | SqlType::MultiPolygon => {
+                W::wrap(GeoColumnData::with_capacity(sql_type, capacity)?)
+            }
+            SqlType::Json => W::wrap(JsonColumnData::with_capacity(capacity)),
+            SqlType::Variant(_) | SqlType::Dynamic => {
+                W::wrap(VariantColumnData::with_capacity(sql_type, timezone, capacity)?)
+            }
             SqlType::LowCardinality(inner) => {
                 W::wrap(
                     LowCardinalityColumnData::empty(inner, timezone, capacity)?, // LowCardinalityColumnData {
@@ -335,6 +365,12 @@ fn parse_decimal(source: &str) -> Option<(u8, u8, NoBits)> {
                 b"Decimal64" => {
                     nobits = Some(NoBits::N64);
                 }
+                b"Decimal128" => {
+                    nobits = Some(NoBits::N128);
+                }
+                b"Decimal256" => {
+                    nobits = Some(NoBits::N256);
+                }
                 _ => return None,
             }
             params_indexes.0 = Some(idx);
@@ -382,6 +418,8 @@ fn parse_decimal(source: &str) -> Option<(u8, u8, NoBits)> {
             let precision = match bits {
                 NoBits::N32 => 9,
                 NoBits::N64 => 18,
+                NoBits::N128 => 38,
+                NoBits::N256 => 76,
             };
             Some((precision, scale, bits))
         }
@@ -539,6 +577,95 @@ fn parse_low_cardinality(source: &str) -> Option<&str> {
     Some(source[lo + 1..hi].trim())
 }
 
+fn parse_tuple_type(source: &str) -> Option<Vec<&str>> {
+    if !source.starts_with("Tuple(") || !source.ends_with(')') {
+        return None;
+    }
+
+    let body = &source[6..source.len() - 1];
+    let mut types = Vec::new();
+    for item in split_top_level(body)? {
+        types.push(strip_element_name(item.trim()));
+    }
+
+    if types.iter().any(|t| t.is_empty()) {
+        return None;
+    }
+
+    Some(types)
+}
+
+fn parse_variant_type(source: &str) -> Option<Vec<&str>> {
+    if !source.starts_with("Variant(") || !source.ends_with(')') {
+        return None;
+    }
+
+    let body = &source[8..source.len() - 1];
+    let types: Vec<&str> = split_top_level(body)?.into_iter().map(str::trim).collect();
+
+    if types.iter().any(|t| t.is_empty()) {
+        return None;
+    }
+
+    Some(types)
+}
+
+/// Splits comma separated type arguments ignoring commas
+/// inside nested parentheses and quoted literals.
+pub(crate) fn split_top_level(source: &str) -> Option<Vec<&str>> {
+    let mut items = Vec::new();
+    let mut depth = 0_usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (idx, ch) in source.char_indices() {
+        if quoted {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\'' => quoted = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match ch {
+            '\'' => quoted = true,
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                items.push(&source[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || quoted {
+        return None;
+    }
+
+    items.push(&source[start..]);
+    Some(items)
+}
+
+/// Named tuple elements come as `name Type`, the name is dropped.
+fn strip_element_name(item: &str) -> &str {
+    if let Some(rest) = item.strip_prefix('`') {
+        if let Some(end) = rest.find('`') {
+            return rest[end + 1..].trim_start();
+        }
+    }
+
+    match item.find(char::is_whitespace) {
+        Some(pos) if !item[..pos].contains('(') && !item[pos..].trim_start().starts_with('(') => {
+            item[pos..].trim_start()
+        }
+        _ => item,
+    }
+}
+
 fn get_timezone(timezone: &Option<String>, tz: Tz) -> Result<Tz> {
     match timezone {
         None => Ok(tz),
@@ -554,13 +681,21 @@ mod test {
     fn test_parse_decimal() {
         assert_eq!(parse_decimal("Decimal(9, 4)"), Some((9, 4, NoBits::N32)));
         assert_eq!(parse_decimal("Decimal(10, 4)"), Some((10, 4, NoBits::N64)));
-        assert_eq!(parse_decimal("Decimal(20, 4)"), None);
+        assert_eq!(parse_decimal("Decimal(20, 4)"), Some((20, 4, NoBits::N128)));
+        assert_eq!(
+            parse_decimal("Decimal(38, 10)"),
+            Some((38, 10, NoBits::N128))
+        );
+        assert_eq!(parse_decimal("Decimal(76, 4)"), Some((76, 4, NoBits::N256)));
+        assert_eq!(parse_decimal("Decimal(77, 4)"), None);
         assert_eq!(parse_decimal("Decimal(2000, 4)"), None);
         assert_eq!(parse_decimal("Decimal(3, 4)"), None);
         assert_eq!(parse_decimal("Decimal(20, -4)"), None);
         assert_eq!(parse_decimal("Decimal(0)"), None);
         assert_eq!(parse_decimal("Decimal(1, 2, 3)"), None);
         assert_eq!(parse_decimal("Decimal64(9)"), Some((18, 9, NoBits::N64)));
+        assert_eq!(parse_decimal("Decimal128(9)"), Some((38, 9, NoBits::N128)));
+        assert_eq!(parse_decimal("Decimal256(9)"), Some((76, 9, NoBits::N256)));
     }
 
     #[test]
@@ -766,6 +901,42 @@ mod test {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_parse_tuple_type() {
+        assert_eq!(
+            parse_tuple_type("Tuple(UInt8, String)"),
+            Some(vec!["UInt8", "String"])
+        );
+        assert_eq!(
+            parse_tuple_type("Tuple(Map(UInt8, String), DateTime64(3, 'Europe/Moscow'))"),
+            Some(vec!["Map(UInt8, String)", "DateTime64(3, 'Europe/Moscow')"])
+        );
+        assert_eq!(
+            parse_tuple_type("Tuple(a UInt8, b Nullable(String))"),
+            Some(vec!["UInt8", "Nullable(String)"])
+        );
+        assert_eq!(
+            parse_tuple_type("Tuple(Enum8('a,b' = 1), UInt8)"),
+            Some(vec!["Enum8('a,b' = 1)", "UInt8"])
+        );
+        assert_eq!(parse_tuple_type("Tuple(UInt8, )"), None);
+        assert_eq!(
+            parse_tuple_type("Tuple(`a b` UInt8, c String)"),
+            Some(vec!["UInt8", "String"])
+        );
+    }
+
+    #[test]
+    fn test_parse_variant_type() {
+        assert_eq!(
+            parse_variant_type("Variant(Array(UInt64), String)"),
+            Some(vec!["Array(UInt64)", "String"])
+        );
+        assert_eq!(parse_variant_type("Variant(UInt8, )"), None);
+        assert_eq!(parse_variant_type("Tuple(UInt8)"), None);
+        assert_eq!(parse_tuple_type("Array(UInt8)"), None);
+    }
+
     #[test]
     fn test_parse_low_cardinality() {
         assert_eq!(
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+
+use crate::{
+    binary::{Encoder, ReadEx},
+    errors::Result,
+    types::{
+        column::{
+            column_data::{ArcColumnData, BoxColumnData},
+            datetime64::DEFAULT_TZ,
+            ArcColumnWrapper, ColumnData, ColumnFrom, ColumnWrapper,
+        },
+        MultiPolygon, Point, Polygon, Ring, SqlType, Value, ValueRef,
+    },
+};
+
+/// Column of `Point`, `Ring`, `Polygon` or `MultiPolygon` values.
+///
+/// Geo types are aliases of `Tuple(Float64, Float64)` and nested arrays of it,
+/// so the data is kept in the column of the underlying type.
+pub(crate) struct GeoColumnData {
+    sql_type: SqlType,
+    inner: ArcColumnData,
+}
+
+impl GeoColumnData {
+    pub(crate) fn load<R: ReadEx>(
+        reader: &mut R,
+        sql_type: SqlType,
+        size: usize,
+        tz: Tz,
+    ) -> Result<Self> {
+        let storage_type = storage_type(&sql_type);
+        let inner =
+            <dyn ColumnData>::load_data::<ArcColumnWrapper, _>(reader, &storage_type, size, tz)?;
+        Ok(Self { sql_type, inner })
+    }
+
+    pub(crate) fn with_capacity(sql_type: SqlType, capacity: usize) -> Result<Self> {
+        let inner = <dyn ColumnData>::from_type::<ArcColumnWrapper>(
+            sql_type.geo_storage_type().unwrap(),
+            *DEFAULT_TZ,
+            capacity,
+        )?;
+        Ok(Self { sql_type, inner })
+    }
+
+    pub(crate) fn wrap(sql_type: SqlType, inner: ArcColumnData) -> Self {
+        Self { sql_type, inner }
+    }
+}
+
+impl ColumnData for GeoColumnData {
+    fn sql_type(&self) -> SqlType {
+        self.sql_type.clone()
+    }
+
+    fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
+        self.inner.save(encoder, start, end);
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn push(&mut self, value: Value) {
+        loop {
+            match Arc::get_mut(&mut self.inner) {
+                None => self.inner = Arc::from(self.inner.clone_instance()),
+                Some(inner) => {
+                    inner.push(nested_value(value));
+                    break;
+                }
+            }
+        }
+    }
+
+    fn at(&self, index: usize) -> ValueRef<'_> {
+        let value = self.inner.at(index);
+        match self.sql_type {
+            SqlType::Point => ValueRef::Point(to_point(value)),
+            SqlType::Ring => ValueRef::Ring(Arc::new(to_ring(value))),
+            SqlType::Polygon => ValueRef::Polygon(Arc::new(to_polygon(value))),
+            SqlType::MultiPolygon => ValueRef::MultiPolygon(Arc::new(to_multi_polygon(value))),
+            _ => unreachable!(),
+        }
+    }
+
+    fn clone_instance(&self) -> BoxColumnData {
+        Box::new(Self {
+            sql_type: self.sql_type.clone(),
+            inner: self.inner.clone(),
+        })
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        None
+    }
+}
+
+macro_rules! geo_column_from {
+    ( $( $t:ident ),* ) => {
+        $(
+            impl ColumnFrom for Vec<$t> {
+                fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+                    let mut data =
+                        GeoColumnData::with_capacity(SqlType::$t, source.len()).unwrap();
+                    for value in source {
+                        data.push(Value::$t(Arc::new(value)));
+                    }
+                    W::wrap(data)
+                }
+            }
+        )*
+    };
+}
+
+geo_column_from! { Ring, Polygon, MultiPolygon }
+
+fn storage_type(sql_type: &SqlType) -> String {
+    sql_type
+        .geo_storage_type()
+        .unwrap()
+        .to_string()
+        .into_owned()
+}
+
+fn to_point(value: ValueRef) -> Point {
+    match value {
+        ValueRef::Tuple(vs) if vs.len() == 2 => (vs[0].clone().into(), vs[1].clone().into()),
+        _ => panic!("value should be a tuple of two Float64"),
+    }
+}
+
+fn to_vec<T>(value: ValueRef, f: fn(ValueRef) -> T) -> Vec<T> {
+    match value {
+        ValueRef::Array(_, vs) => vs.iter().cloned().map(f).collect(),
+        _ => panic!("value should be an array"),
+    }
+}
+
+fn to_ring(value: ValueRef) -> Ring {
+    to_vec(value, to_point)
+}
+
+fn to_polygon(value: ValueRef) -> Polygon {
+    to_vec(value, to_ring)
+}
+
+fn to_multi_polygon(value: ValueRef) -> MultiPolygon {
+    to_vec(value, to_polygon)
+}
+
+/// Converts a geo value into the `Tuple` or `Array` value it's stored as,
+/// other values are returned as is.
+pub(crate) fn nested_value(value: Value) -> Value {
+    fn point(p: &Point) -> Value {
+        Value::Tuple(Arc::new(vec![Value::Float64(p.0), Value::Float64(p.1)]))
+    }
+
+    fn array<T>(sql_type: SqlType, items: &[T], f: fn(&T) -> Value) -> Value {
+        let item_type = sql_type.geo_storage_type().unwrap();
+        Value::Array(item_type.into(), Arc::new(items.iter().map(f).collect()))
+    }
+
+    fn ring(r: &Ring) -> Value {
+        array(SqlType::Point, r, point)
+    }
+
+    fn polygon(p: &Polygon) -> Value {
+        array(SqlType::Ring, p, ring)
+    }
+
+    match value {
+        Value::Point(p) => point(&p),
+        Value::Ring(r) => ring(&r),
+        Value::Polygon(p) => polygon(&p),
+        Value::MultiPolygon(mp) => array(SqlType::Polygon, &mp, polygon),
+        value => value,
+    }
+}
+
+/// Same as [`nested_value`] for borrowed values.
+pub(crate) fn nested_value_ref(value: ValueRef) -> ValueRef {
+    fn point(p: &Point) -> ValueRef<'static> {
+        ValueRef::Tuple(Arc::new(vec![
+            ValueRef::Float64(p.0),
+            ValueRef::Float64(p.1),
+        ]))
+    }
+
+    fn array<T>(
+        sql_type: SqlType,
+        items: &[T],
+        f: fn(&T) -> ValueRef<'static>,
+    ) -> ValueRef<'static> {
+        let item_type = sql_type.geo_storage_type().unwrap();
+        ValueRef::Array(item_type.into(), Arc::new(items.iter().map(f).collect()))
+    }
+
+    fn ring(r: &Ring) -> ValueRef<'static> {
+        array(SqlType::Point, r, point)
+    }
+
+    fn polygon(p: &Polygon) -> ValueRef<'static> {
+        array(SqlType::Ring, p, ring)
+    }
+
+    match value {
+        ValueRef::Point(p) => point(&p),
+        ValueRef::Ring(r) => ring(&r),
+        ValueRef::Polygon(p) => polygon(&p),
+        ValueRef::MultiPolygon(mp) => array(SqlType::Polygon, &mp, polygon),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client_info::CLICK_HOUSE_REVISION,
+        types::{CompressionMethod, Simple},
+        Block,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_and_read() {
+        let zones: Vec<Polygon> = vec![
+            vec![vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]],
+            vec![
+                vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0)],
+                vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0)],
+            ],
+        ];
+
+        let block = Block::<Simple>::new().column("zone", zones.clone());
+
+        let mut encoder = Encoder::new();
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
+
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
+
+        assert_eq!(block, rblock);
+        assert_eq!(
+            rblock.get_column("zone").unwrap().sql_type(),
+            SqlType::Polygon
+        );
+
+        let actual: Polygon = rblock.get(1, "zone").unwrap();
+        assert_eq!(actual, zones[1]);
+    }
+
+    #[test]
+    fn test_cast_from_tuple() {
+        let block = Block::<Simple>::new().column("point", vec![(1.0_f64, 2.0_f64), (3.0, 4.0)]);
+        let column = block.get_column("point").unwrap().clone();
+
+        let column = column.cast_to(SqlType::Point).unwrap();
+        assert_eq!(column.sql_type(), SqlType::Point);
+        assert_eq!(column.at(1), ValueRef::Point((3.0, 4.0)));
+    }
+}
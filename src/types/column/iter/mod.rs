@@ -2,6 +2,7 @@
 
 use chrono::prelude::*;
 use chrono_tz::Tz;
+use ethnum::{I256, U256};
 use std::{
     collections::HashMap,
     hash::Hash,
@@ -21,6 +22,7 @@ use crate::{
             low_cardinality::{LowCardinalityIndex, LowCardinalityInternals},
             StringPool,
         },
+        date_converter::date_from_days,
         decimal::NoBits,
         Column, ColumnType, Complex, Decimal, Simple, SqlType,
     },
@@ -80,7 +82,9 @@ simple_num_iterable! {
     f64: Float64,
 
     i128: Int128,
-    u128: UInt128
+    I256: Int256,
+    u128: UInt128,
+    U256: UInt256
 }
 
 macro_rules! iterator {
@@ -181,9 +185,14 @@ pub struct UuidIterator<'a> {
     _marker: marker::PhantomData<&'a ()>,
 }
 
+enum DateInnerIterator {
+    Date(*const u16),
+    Date32(*const i32),
+}
+
 pub struct DateIterator<'a> {
     lc_index: Option<*const LowCardinalityIndex>,
-    ptr: *const u16,
+    inner: DateInnerIterator,
     index: usize,
     len: usize,
     tz: Tz,
@@ -309,7 +318,7 @@ impl<'a> DecimalIterator<'a> {
     unsafe fn next_unchecked_<T>(&mut self) -> Decimal
     where
         T: Copy + Sized,
-        i64: From<T>,
+        I256: From<T>,
     {
         let current_value = *(self.ptr as *const T);
         self.ptr = (self.ptr as *const T).offset(1) as *const u8;
@@ -327,6 +336,8 @@ impl<'a> DecimalIterator<'a> {
         match self.nobits {
             NoBits::N32 => self.next_unchecked_::<i32>(),
             NoBits::N64 => self.next_unchecked_::<i64>(),
+            NoBits::N128 => self.next_unchecked_::<i128>(),
+            NoBits::N256 => self.next_unchecked_::<I256>(),
         }
     }
 
@@ -336,6 +347,8 @@ impl<'a> DecimalIterator<'a> {
             match self.nobits {
                 NoBits::N32 => self.ptr = (self.ptr as *const i32).add(n) as *const u8,
                 NoBits::N64 => self.ptr = (self.ptr as *const i64).add(n) as *const u8,
+                NoBits::N128 => self.ptr = (self.ptr as *const i128).add(n) as *const u8,
+                NoBits::N256 => self.ptr = (self.ptr as *const I256).add(n) as *const u8,
             }
         }
     }
@@ -347,6 +360,8 @@ impl<'a> ExactSizeIterator for DecimalIterator<'a> {
         let size = match self.nobits {
             NoBits::N32 => mem::size_of::<i32>(),
             NoBits::N64 => mem::size_of::<i64>(),
+            NoBits::N128 => mem::size_of::<i128>(),
+            NoBits::N256 => mem::size_of::<I256>(),
         };
         (self.end as usize - self.ptr as usize) / size
     }
@@ -452,14 +467,22 @@ impl<'a> DateIterator<'a> {
             .map(|ix| unsafe { (*ix).get_by_index(self.index) })
             .unwrap_or(self.index);
 
-        let current_value = *self.ptr.add(index);
         self.index += 1;
 
-        let time = self
-            .tz
-            .timestamp_opt(i64::from(current_value) * 24 * 3600, 0)
-            .unwrap();
-        time.date_naive()
+        match self.inner {
+            DateInnerIterator::Date(ptr) => {
+                let current_value = *ptr.add(index);
+                let time = self
+                    .tz
+                    .timestamp_opt(i64::from(current_value) * 24 * 3600, 0)
+                    .unwrap();
+                time.date_naive()
+            }
+            DateInnerIterator::Date32(ptr) => {
+                let current_value = *ptr.add(index);
+                date_from_days(i64::from(current_value))
+            }
+        }
     }
 
     #[inline(always)]
@@ -983,6 +1006,8 @@ impl<'a> Iterable<'a, Simple> for Decimal {
             match nobits {
                 NoBits::N32 => (ptr as *const u32).add(size) as *const u8,
                 NoBits::N64 => (ptr as *const u64).add(size) as *const u8,
+                NoBits::N128 => (ptr as *const u128).add(size) as *const u8,
+                NoBits::N256 => (ptr as *const I256).add(size) as *const u8,
             }
         };
 
@@ -1153,8 +1178,9 @@ impl<'a> Iterable<'a, Simple> for NaiveDate {
         let mut dt_inter;
         let len;
         let lc_index;
+        let date_type;
         match column_type {
-            SqlType::LowCardinality(SqlType::Date) => {
+            SqlType::LowCardinality(inner @ (SqlType::Date | SqlType::Date32)) => {
                 let mut lc_inter = LowCardinalityInternals::default();
                 dt_inter = DateTimeInternals::default();
                 unsafe {
@@ -1163,12 +1189,14 @@ impl<'a> Iterable<'a, Simple> for NaiveDate {
                     len = (*lc_inter.index).len();
                 }
                 lc_index = Some(lc_inter.index);
+                date_type = inner.clone();
             }
-            SqlType::Date => {
+            SqlType::Date | SqlType::Date32 => {
                 dt_inter = date_iter(column, props)?;
                 len = dt_inter.len;
                 lc_index = None;
                 assert!(dt_inter.precision.is_none());
+                date_type = column_type;
             }
             _ => {
                 return Err(Error::FromSql(FromSqlError::InvalidType {
@@ -1177,10 +1205,17 @@ impl<'a> Iterable<'a, Simple> for NaiveDate {
                 }));
             }
         };
+
+        let inner = if date_type == SqlType::Date32 {
+            DateInnerIterator::Date32(dt_inter.begin as *const i32)
+        } else {
+            DateInnerIterator::Date(dt_inter.begin as *const u16)
+        };
+
         Ok(DateIterator {
             lc_index,
             index: 0,
-            ptr: dt_inter.begin as *const u16,
+            inner,
             len,
             tz: dt_inter.tz,
             _marker: marker::PhantomData,
@@ -0,0 +1,313 @@
+use chrono_tz::Tz;
+use either::Either;
+
+use crate::{
+    binary::{Encoder, ReadEx},
+    errors::Result,
+    types::{
+        column::{
+            column_data::BoxColumnData, factory::split_top_level, BoxColumnWrapper, ColumnData,
+        },
+        SqlType, Value, ValueRef,
+    },
+};
+
+#[cfg(feature = "json")]
+use crate::types::column::{ColumnFrom, ColumnWrapper};
+
+/// `JSON` written as a column of JSON strings,
+/// see `output_format_native_write_json_as_string` server setting.
+const OBJECT_SERIALIZATION_STRING: u64 = 1;
+
+const DEPRECATED_OBJECT_TUPLE: u8 = 0;
+const DEPRECATED_OBJECT_STRING: u8 = 1;
+
+/// Column of `JSON` (or legacy `Object('json')`) values kept as JSON text.
+pub(crate) struct JsonColumnData {
+    data: Vec<String>,
+}
+
+impl JsonColumnData {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn load<R: ReadEx>(reader: &mut R, size: usize) -> Result<Self> {
+        let version: u64 = reader.read_scalar()?;
+        if version != OBJECT_SERIALIZATION_STRING {
+            let message = format!(
+                "Unsupported JSON serialization version {version}, \
+                 set `output_format_native_write_json_as_string = 1` to read JSON columns."
+            );
+            return Err(message.into());
+        }
+        Self::load_strings(reader, size)
+    }
+
+    /// Loads legacy `Object('json')` column, that is sent as a named `Tuple` of its paths.
+    pub(crate) fn load_object<R: ReadEx>(reader: &mut R, size: usize, tz: Tz) -> Result<Self> {
+        let kind: u8 = reader.read_scalar()?;
+        match kind {
+            DEPRECATED_OBJECT_STRING => Self::load_strings(reader, size),
+            DEPRECATED_OBJECT_TUPLE => {
+                let type_name = reader.read_string()?;
+                let shape = Shape::parse(&type_name);
+                let inner = <dyn ColumnData>::load_data::<BoxColumnWrapper, _>(
+                    reader, &type_name, size, tz,
+                )?;
+
+                let mut data = Self::with_capacity(size);
+                for index in 0..size {
+                    let mut text = String::new();
+                    write_json(&mut text, &shape, inner.at(index));
+                    data.data.push(text);
+                }
+                Ok(data)
+            }
+            _ => {
+                let message = format!("Unsupported Object serialization kind {kind}.");
+                Err(message.into())
+            }
+        }
+    }
+
+    fn load_strings<R: ReadEx>(reader: &mut R, size: usize) -> Result<Self> {
+        let mut data = Self::with_capacity(size);
+        for _ in 0..size {
+            data.data.push(reader.read_string()?);
+        }
+        Ok(data)
+    }
+}
+
+impl ColumnData for JsonColumnData {
+    fn sql_type(&self) -> SqlType {
+        SqlType::Json
+    }
+
+    fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
+        encoder.write(OBJECT_SERIALIZATION_STRING);
+        for text in &self.data[start..end] {
+            encoder.string(text);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn push(&mut self, value: Value) {
+        let text = match value {
+            Value::Json(text) => text.as_ref().clone(),
+            Value::String(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            _ => panic!("can't push {} value into JSON column", SqlType::from(value)),
+        };
+        self.data.push(text);
+    }
+
+    fn at(&self, index: usize) -> ValueRef<'_> {
+        ValueRef::Json(&self.data[index])
+    }
+
+    fn clone_instance(&self) -> BoxColumnData {
+        Box::new(Self {
+            data: self.data.clone(),
+        })
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        None
+    }
+}
+
+#[cfg(feature = "json")]
+impl ColumnFrom for Vec<serde_json::Value> {
+    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+        let data = source.iter().map(ToString::to_string).collect();
+        W::wrap(JsonColumnData { data })
+    }
+}
+
+/// Layout of JSON document described by the type of `Object('json')` column.
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Scalar,
+    Object(Vec<(String, Shape)>),
+    Array(Box<Shape>),
+}
+
+impl Shape {
+    fn parse(type_name: &str) -> Self {
+        let type_name = type_name.trim();
+
+        if let Some(inner) = strip_type(type_name, "Nullable") {
+            return Self::parse(inner);
+        }
+
+        if let Some(inner) = strip_type(type_name, "Array") {
+            return Shape::Array(Box::new(Self::parse(inner)));
+        }
+
+        if let Some(items) = strip_type(type_name, "Tuple").and_then(split_top_level) {
+            let fields: Option<Vec<_>> = items
+                .into_iter()
+                .map(|item| split_element(item.trim()))
+                .map(|element| element.map(|(name, t)| (name, Self::parse(t))))
+                .collect();
+            if let Some(fields) = fields {
+                return Shape::Object(fields);
+            }
+        }
+
+        Shape::Scalar
+    }
+}
+
+fn strip_type<'a>(type_name: &'a str, name: &str) -> Option<&'a str> {
+    type_name
+        .strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Splits named tuple element `name Type` (the name can be quoted with backticks).
+fn split_element(item: &str) -> Option<(String, &str)> {
+    if let Some(rest) = item.strip_prefix('`') {
+        let end = rest.find('`')?;
+        return Some((rest[..end].to_string(), rest[end + 1..].trim()));
+    }
+
+    let pos = item.find(char::is_whitespace)?;
+    let name = &item[..pos];
+    if name.contains('(') {
+        return None;
+    }
+    Some((name.to_string(), item[pos..].trim()))
+}
+
+fn write_json(out: &mut String, shape: &Shape, value: ValueRef) {
+    match (shape, value) {
+        (_, ValueRef::Nullable(Either::Left(_))) => out.push_str("null"),
+        (_, ValueRef::Nullable(Either::Right(v))) => write_json(out, shape, *v),
+        (Shape::Object(fields), ValueRef::Tuple(vs)) => {
+            out.push('{');
+            for (i, ((name, field), v)) in fields.iter().zip(vs.iter()).enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_str(out, name);
+                out.push(':');
+                write_json(out, field, v.clone());
+            }
+            out.push('}');
+        }
+        (_, ValueRef::Array(_, vs)) | (_, ValueRef::Tuple(vs)) => {
+            let item = match shape {
+                Shape::Array(item) => item.as_ref(),
+                _ => &Shape::Scalar,
+            };
+            out.push('[');
+            for (i, v) in vs.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json(out, item, v.clone());
+            }
+            out.push(']');
+        }
+        (_, ValueRef::Float32(v)) if !v.is_finite() => out.push_str("null"),
+        (_, ValueRef::Float64(v)) if !v.is_finite() => out.push_str("null"),
+        (
+            _,
+            v @ (ValueRef::Bool(_)
+            | ValueRef::UInt8(_)
+            | ValueRef::UInt16(_)
+            | ValueRef::UInt32(_)
+            | ValueRef::UInt64(_)
+            | ValueRef::UInt128(_)
+            | ValueRef::UInt256(_)
+            | ValueRef::Int8(_)
+            | ValueRef::Int16(_)
+            | ValueRef::Int32(_)
+            | ValueRef::Int64(_)
+            | ValueRef::Int128(_)
+            | ValueRef::Int256(_)
+            | ValueRef::Float32(_)
+            | ValueRef::Float64(_)
+            | ValueRef::Decimal(_)),
+        ) => out.push_str(&v.to_string()),
+        (_, ValueRef::Json(v)) => out.push_str(v),
+        (_, v) => write_json_str(out, &v.to_string()),
+    }
+}
+
+fn write_json_str(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_and_read() {
+        let mut data = JsonColumnData::with_capacity(2);
+        data.push(Value::Json(r#"{"a":1}"#.to_string().into()));
+        data.push(Value::from(r#"{"b":[1,2]}"#));
+
+        let mut encoder = Encoder::new();
+        data.save(&mut encoder, 0, 2);
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rdata = JsonColumnData::load(&mut reader, 2).unwrap();
+
+        assert_eq!(rdata.at(0), ValueRef::Json(r#"{"a":1}"#));
+        assert_eq!(rdata.at(1), ValueRef::Json(r#"{"b":[1,2]}"#));
+    }
+
+    #[test]
+    fn test_load_object() {
+        let mut encoder = Encoder::new();
+        encoder.write(DEPRECATED_OBJECT_TUPLE);
+        encoder.string("Tuple(a Int8, `b c` Tuple(d String), e Array(Tuple(f UInt8)))");
+        // a
+        encoder.write(1_i8);
+        // b c.d
+        encoder.string("x \"y\"");
+        // e
+        encoder.write(2_u64);
+        encoder.write(3_u8);
+        encoder.write(4_u8);
+
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let data = JsonColumnData::load_object(&mut reader, 1, Tz::UTC).unwrap();
+
+        assert_eq!(
+            data.at(0),
+            ValueRef::Json(r#"{"a":1,"b c":{"d":"x \"y\""},"e":[{"f":3},{"f":4}]}"#)
+        );
+    }
+
+    #[test]
+    fn test_parse_shape() {
+        assert_eq!(Shape::parse("String"), Shape::Scalar);
+        assert_eq!(
+            Shape::parse("Array(Nullable(Tuple(a Int8)))"),
+            Shape::Array(Box::new(Shape::Object(vec![("a".into(), Shape::Scalar)])))
+        );
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
         column::{
             column_data::{ArcColumnData, BoxColumnData},
             list::List,
-            ArcColumnWrapper, ColumnData, ColumnFrom, ColumnWrapper,
+            ArcColumnWrapper, ColumnData, ColumnFrom, ColumnWrapper, VectorColumnData,
         },
         HasSqlType, Marshal, SqlType, StatBuffer, Unmarshal, Value, ValueRef,
     },
@@ -175,12 +175,13 @@ where
 {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
         let fake_keys: Vec<String> = Vec::with_capacity(source.len());
-        let fake_values: Vec<V> = Vec::with_capacity(source.len());
 
         let keys = Vec::column_from::<ArcColumnWrapper>(fake_keys);
         let key_type = keys.sql_type();
 
-        let values = Vec::column_from::<ArcColumnWrapper>(fake_values);
+        let values = ArcColumnWrapper::wrap(VectorColumnData::<V> {
+            data: List::with_capacity(source.len()),
+        });
         let value_type = values.sql_type();
 
         let mut data = MapColumnData {
@@ -222,13 +223,14 @@ where
     Value: From<V>,
 {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let fake_keys: Vec<K> = Vec::with_capacity(source.len());
-        let fake_values: Vec<V> = Vec::with_capacity(source.len());
-
-        let keys = Vec::column_from::<ArcColumnWrapper>(fake_keys);
+        let keys = ArcColumnWrapper::wrap(VectorColumnData::<K> {
+            data: List::with_capacity(source.len()),
+        });
         let key_type = keys.sql_type();
 
-        let values = Vec::column_from::<ArcColumnWrapper>(fake_values);
+        let values = ArcColumnWrapper::wrap(VectorColumnData::<V> {
+            data: List::with_capacity(source.len()),
+        });
         let value_type = values.sql_type();
 
         let mut data = MapColumnData {
@@ -277,8 +279,9 @@ where
 mod test {
     use super::*;
     use crate::{
+        client_info::CLICK_HOUSE_REVISION,
         row,
-        types::{column::datetime64::DEFAULT_TZ, Simple},
+        types::{column::datetime64::DEFAULT_TZ, CompressionMethod, Simple},
         Block,
     };
     use std::{collections::HashMap, io::Cursor};
@@ -290,10 +293,10 @@ mod test {
         let block = Block::<Simple>::new().column("vals", vec![source]);
 
         let mut encoder = Encoder::new();
-        block.write(&mut encoder, false);
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
 
         let mut reader = Cursor::new(encoder.get_buffer_ref());
-        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false).unwrap();
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
 
         assert_eq!(block, rblock);
     }
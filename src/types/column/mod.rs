@@ -6,16 +6,20 @@ use std::{
 };
 
 use chrono_tz::Tz;
+use either::Either;
 
 use crate::{
-    binary::{Encoder, ReadEx},
+    binary::{protocol, Encoder, ReadEx},
     errors::{Error, FromSqlError, Result},
     types::{
         column::{
-            column_data::ArcColumnData,
             decimal::{DecimalAdapter, NullableDecimalAdapter},
-            enums::{Enum16Adapter, Enum8Adapter, NullableEnum16Adapter, NullableEnum8Adapter},
+            enums::{
+                enum_mapping, Enum16Adapter, Enum8Adapter, NullableEnum16Adapter,
+                NullableEnum8Adapter,
+            },
             fixed_string::{FixedStringAdapter, NullableFixedStringAdapter},
+            geo::GeoColumnData,
             ip::{IpColumnData, Ipv4, Ipv6},
             iter::Iterable,
             low_cardinality::LowCardinalityColumnData,
@@ -28,10 +32,13 @@ use crate::{
 };
 
 use self::chunk::ChunkColumnData;
-pub(crate) use self::{column_data::ColumnData, string_pool::StringPool};
+pub(crate) use self::{
+    column_data::{ArcColumnData, ColumnData},
+    string_pool::StringPool,
+};
 pub use self::{concat::ConcatColumnData, numeric::VectorColumnData};
 
-mod array;
+pub(crate) mod array;
 pub(crate) mod chrono_datetime;
 mod chunk;
 mod column_data;
@@ -42,7 +49,9 @@ mod decimal;
 mod enums;
 mod factory;
 pub(crate) mod fixed_string;
+pub(crate) mod geo;
 mod ip;
+mod json;
 pub mod iter;
 mod list;
 mod low_cardinality;
@@ -52,7 +61,9 @@ mod numeric;
 mod simple_agg_func;
 mod string;
 mod string_pool;
+mod tuple;
 mod util;
+mod variant;
 
 /// Represents Clickhouse Column
 pub struct Column<K: ColumnType> {
@@ -177,9 +188,22 @@ impl<K: ColumnType> Column<K> {
 }
 
 impl<K: ColumnType> Column<K> {
-    pub(crate) fn read<R: ReadEx>(reader: &mut R, size: usize, tz: Tz) -> Result<Column<K>> {
+    pub(crate) fn read<R: ReadEx>(
+        reader: &mut R,
+        size: usize,
+        tz: Tz,
+        revision: u64,
+    ) -> Result<Column<K>> {
         let name = reader.read_string()?;
         let type_name = reader.read_string()?;
+        if revision >= protocol::DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION {
+            let has_custom: u8 = reader.read_scalar()?;
+            if has_custom != 0 {
+                let message =
+                    format!("Custom serialization of column \"{name}\" is not supported.");
+                return Err(message.into());
+            }
+        }
         let data =
             <dyn ColumnData>::load_data::<ArcColumnWrapper, _>(reader, &type_name, size, tz)?;
         let column = Self {
@@ -205,9 +229,12 @@ impl<K: ColumnType> Column<K> {
         self.data.at(index)
     }
 
-    pub(crate) fn write(&self, encoder: &mut Encoder) {
+    pub(crate) fn write(&self, encoder: &mut Encoder, revision: u64) {
         encoder.string(&self.name);
         encoder.string(self.data.sql_type().to_string().as_ref());
+        if revision >= protocol::DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION {
+            encoder.write(0_u8); // has_custom
+        }
         let len = self.data.len();
         self.data.save(encoder, 0, len);
     }
@@ -311,11 +338,13 @@ impl<K: ColumnType> Column<K> {
                     _marker: marker::PhantomData,
                 })
             }
-            (SqlType::Enum8(enum_values), SqlType::Enum8(_)) => {
+            (SqlType::Enum8(enum_values), SqlType::Enum8(src_values)) => {
                 let name = self.name().to_owned();
+                let mapping = enum_mapping(&src_values, &enum_values, &dst_type)?;
                 let adapter = Enum8Adapter {
                     column: self,
                     enum_values,
+                    mapping,
                 };
                 Ok(Column {
                     name,
@@ -323,11 +352,13 @@ impl<K: ColumnType> Column<K> {
                     _marker: marker::PhantomData,
                 })
             }
-            (SqlType::Enum16(enum_values), SqlType::Enum16(_)) => {
+            (SqlType::Enum16(enum_values), SqlType::Enum16(src_values)) => {
                 let name = self.name().to_owned();
+                let mapping = enum_mapping(&src_values, &enum_values, &dst_type)?;
                 let adapter = Enum16Adapter {
                     column: self,
                     enum_values,
+                    mapping,
                 };
                 Ok(Column {
                     name,
@@ -337,13 +368,15 @@ impl<K: ColumnType> Column<K> {
             }
             (
                 SqlType::Nullable(SqlType::Enum8(enum_values)),
-                SqlType::Nullable(SqlType::Enum8(_)),
+                SqlType::Nullable(SqlType::Enum8(src_values)),
             ) => {
                 let name = self.name().to_owned();
+                let mapping = enum_mapping(src_values, enum_values, &dst_type)?;
                 let enum_values = enum_values.clone();
                 let adapter = NullableEnum8Adapter {
                     column: self,
                     enum_values,
+                    mapping,
                 };
                 Ok(Column {
                     name,
@@ -353,13 +386,15 @@ impl<K: ColumnType> Column<K> {
             }
             (
                 SqlType::Nullable(SqlType::Enum16(enum_values)),
-                SqlType::Nullable(SqlType::Enum16(_)),
+                SqlType::Nullable(SqlType::Enum16(src_values)),
             ) => {
                 let name = self.name().to_owned();
+                let mapping = enum_mapping(src_values, enum_values, &dst_type)?;
                 let enum_values = enum_values.clone();
                 let adapter = NullableEnum16Adapter {
                     column: self,
                     enum_values,
+                    mapping,
                 };
                 Ok(Column {
                     name,
@@ -432,6 +467,68 @@ impl<K: ColumnType> Column<K> {
                     _marker: marker::PhantomData,
                 })
             }
+            (SqlType::Date32, SqlType::Date)
+            | (SqlType::Nullable(SqlType::Date32), SqlType::Nullable(SqlType::Date)) => {
+                let name = self.name().to_owned();
+                let tz = self.data.get_timezone().unwrap_or(Tz::Zulu);
+
+                let n = self.len();
+                let mut data = <dyn ColumnData>::from_type::<BoxColumnWrapper>(dst_type, tz, n)?;
+                for i in 0..n {
+                    data.push(self.at(i).into());
+                }
+
+                Ok(Column {
+                    name,
+                    data: data.into(),
+                    _marker: marker::PhantomData,
+                })
+            }
+            (SqlType::Nullable(_), SqlType::Nullable(_))
+            | (SqlType::Array(_), SqlType::Array(_))
+                if (0..self.len()).all(|i| is_empty_value(self.at(i))) =>
+            {
+                let name = self.name().to_owned();
+                let tz = self.data.get_timezone().unwrap_or(Tz::Zulu);
+
+                let n = self.len();
+                let mut data =
+                    <dyn ColumnData>::from_type::<BoxColumnWrapper>(dst_type.clone(), tz, n)?;
+                for _ in 0..n {
+                    data.push(Value::default(dst_type.clone()));
+                }
+
+                Ok(Column {
+                    name,
+                    data: data.into(),
+                    _marker: marker::PhantomData,
+                })
+            }
+            (SqlType::Nullable(inner), src_type) if *inner == src_type => {
+                let name = self.name().to_owned();
+                let tz = self.data.get_timezone().unwrap_or(Tz::Zulu);
+
+                let n = self.len();
+                let mut data = <dyn ColumnData>::from_type::<BoxColumnWrapper>(dst_type, tz, n)?;
+                for i in 0..n {
+                    let value = Value::from(self.at(i));
+                    data.push(Value::Nullable(Either::Right(Box::new(value))));
+                }
+
+                Ok(Column {
+                    name,
+                    data: data.into(),
+                    _marker: marker::PhantomData,
+                })
+            }
+            (dst_type, src_type) if dst_type.geo_storage_type() == Some(src_type.clone()) => {
+                let name = self.name().to_owned();
+                Ok(Column {
+                    name,
+                    data: Arc::new(GeoColumnData::wrap(dst_type, self.data)),
+                    _marker: marker::PhantomData,
+                })
+            }
             (SqlType::SimpleAggregateFunction(func, nested), _) => {
                 let inner_column = self.cast_to(nested.clone())?;
                 Ok(Column {
@@ -495,6 +592,16 @@ impl<K: ColumnType> Column<K> {
     }
 }
 
+/// Returns `true` for `NULL` and empty arrays, they have the same representation in any column
+/// of the same kind.
+fn is_empty_value(value: ValueRef) -> bool {
+    match value {
+        ValueRef::Nullable(Either::Left(_)) => true,
+        ValueRef::Array(_, values) => values.is_empty(),
+        _ => false,
+    }
+}
+
 pub(crate) fn new_column<K: ColumnType>(
     name: &str,
     data: Arc<(dyn ColumnData + Sync + Send + 'static)>,
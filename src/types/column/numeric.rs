@@ -8,7 +8,7 @@ use crate::{
         column::{
             array::ArrayColumnData, nullable::NullableColumnData, ArcColumnWrapper, ColumnWrapper,
         },
-        HasSqlType, Marshal, SqlType, StatBuffer, Unmarshal, Value, ValueRef,
+        HasSqlType, Marshal, SqlType, StatBuffer, Unmarshal, Value, ValueRef, I256, U256,
     },
 };
 
@@ -35,57 +35,42 @@ where
     pub(crate) data: List<T>,
 }
 
-impl<T> ColumnFrom for Vec<T>
-where
-    T: StatBuffer
-        + Unmarshal<T>
-        + Marshal
-        + Copy
-        + Into<Value>
-        + From<Value>
-        + Send
-        + Sync
-        + HasSqlType
-        + 'static,
-{
-    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let mut data = List::with_capacity(source.len());
-        for s in source {
-            data.push(s);
-        }
-        W::wrap(VectorColumnData { data })
-    }
+macro_rules! column_from_numeric {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl ColumnFrom for Vec<$t> {
+                fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+                    let mut data = List::with_capacity(source.len());
+                    for s in source {
+                        data.push(s);
+                    }
+                    W::wrap(VectorColumnData { data })
+                }
+            }
+
+            impl ColumnFrom for Vec<Option<$t>> {
+                fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+                    let fake: Vec<$t> = Vec::with_capacity(source.len());
+                    let inner = Vec::column_from::<ArcColumnWrapper>(fake);
+
+                    let mut data = NullableColumnData {
+                        inner,
+                        nulls: Vec::with_capacity(source.len()),
+                    };
+
+                    for value in source {
+                        data.push(value.into());
+                    }
+
+                    W::wrap(data)
+                }
+            }
+        )*
+    };
 }
 
-impl<T> ColumnFrom for Vec<Option<T>>
-where
-    Value: From<T>,
-    T: StatBuffer
-        + Unmarshal<T>
-        + Marshal
-        + Copy
-        + Into<Value>
-        + From<Value>
-        + Send
-        + Sync
-        + HasSqlType
-        + 'static,
-{
-    fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let fake: Vec<T> = Vec::with_capacity(source.len());
-        let inner = Vec::column_from::<ArcColumnWrapper>(fake);
-
-        let mut data = NullableColumnData {
-            inner,
-            nulls: Vec::with_capacity(source.len()),
-        };
-
-        for value in source {
-            data.push(value.into());
-        }
-
-        W::wrap(data)
-    }
+column_from_numeric! {
+    u8, u16, u32, u64, u128, U256, i8, i16, i32, i64, i128, I256, f32, f64, bool
 }
 
 impl<T> ColumnFrom for Vec<Vec<T>>
@@ -103,8 +88,9 @@ where
         + 'static,
 {
     fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
-        let fake: Vec<T> = Vec::with_capacity(source.len());
-        let inner = Vec::column_from::<ArcColumnWrapper>(fake);
+        let inner = ArcColumnWrapper::wrap(VectorColumnData::<T> {
+            data: List::with_capacity(source.len()),
+        });
         let sql_type = inner.sql_type();
 
         let mut data = ArrayColumnData {
@@ -212,12 +198,14 @@ where
             Value::UInt32(x) => ValueRef::UInt32(x),
             Value::UInt64(x) => ValueRef::UInt64(x),
             Value::UInt128(x) => ValueRef::UInt128(x),
+            Value::UInt256(x) => ValueRef::UInt256(x),
 
             Value::Int8(x) => ValueRef::Int8(x),
             Value::Int16(x) => ValueRef::Int16(x),
             Value::Int32(x) => ValueRef::Int32(x),
             Value::Int64(x) => ValueRef::Int64(x),
             Value::Int128(x) => ValueRef::Int128(x),
+            Value::Int256(x) => ValueRef::Int256(x),
 
             Value::Float32(x) => ValueRef::Float32(x),
             Value::Float64(x) => ValueRef::Float64(x),
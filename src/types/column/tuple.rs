@@ -0,0 +1,192 @@
+use std::{marker, sync::Arc};
+
+use chrono_tz::Tz;
+
+use crate::{
+    binary::{Encoder, ReadEx},
+    errors::Result,
+    types::{
+        column::{
+            column_data::{ArcColumnData, BoxColumnData},
+            datetime64::DEFAULT_TZ,
+            ArcColumnWrapper, Column, ColumnData, ColumnFrom, ColumnWrapper, Simple,
+        },
+        HasSqlType, SqlType, Value, ValueRef,
+    },
+};
+
+pub(crate) struct TupleColumnData {
+    pub(crate) inner: Vec<ArcColumnData>,
+}
+
+impl TupleColumnData {
+    pub(crate) fn load<R: ReadEx>(
+        reader: &mut R,
+        types: Vec<&str>,
+        size: usize,
+        tz: Tz,
+    ) -> Result<Self> {
+        let mut inner = Vec::with_capacity(types.len());
+        for type_name in types {
+            inner.push(<dyn ColumnData>::load_data::<ArcColumnWrapper, _>(
+                reader, type_name, size, tz,
+            )?);
+        }
+        Ok(TupleColumnData { inner })
+    }
+}
+
+impl ColumnData for TupleColumnData {
+    fn sql_type(&self) -> SqlType {
+        let types = self
+            .inner
+            .iter()
+            .map(|column| column.sql_type().into())
+            .collect();
+        SqlType::Tuple(types)
+    }
+
+    fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
+        for column in &self.inner {
+            column.save(encoder, start, end);
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.inner.first() {
+            None => 0,
+            Some(column) => column.len(),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        if let Value::Tuple(vs) = value {
+            assert_eq!(
+                vs.len(),
+                self.inner.len(),
+                "tuple should have {} elements",
+                self.inner.len()
+            );
+            for (column, v) in self.inner.iter_mut().zip(vs.iter()) {
+                loop {
+                    match Arc::get_mut(column) {
+                        None => *column = Arc::from(column.clone_instance()),
+                        Some(inner_column) => {
+                            inner_column.push(v.clone());
+                            break;
+                        }
+                    }
+                }
+            }
+        } else {
+            panic!("value should be a tuple")
+        }
+    }
+
+    fn at(&self, index: usize) -> ValueRef<'_> {
+        let vs = self.inner.iter().map(|column| column.at(index)).collect();
+        ValueRef::Tuple(Arc::new(vs))
+    }
+
+    fn clone_instance(&self) -> BoxColumnData {
+        Box::new(Self {
+            inner: self.inner.clone(),
+        })
+    }
+
+    fn cast_to(&self, _this: &ArcColumnData, target: &SqlType) -> Option<ArcColumnData> {
+        if let SqlType::Tuple(inner_targets) = target {
+            if inner_targets.len() != self.inner.len() {
+                return None;
+            }
+
+            let mut inner = Vec::with_capacity(self.inner.len());
+            for (data, inner_target) in self.inner.iter().zip(inner_targets.iter()) {
+                let column: Column<Simple> = Column {
+                    name: String::new(),
+                    data: data.clone(),
+                    _marker: marker::PhantomData,
+                };
+                inner.push(column.cast_to((*inner_target).clone()).ok()?.data);
+            }
+            return Some(Arc::new(TupleColumnData { inner }));
+        }
+        None
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        self.inner.iter().find_map(|column| column.get_timezone())
+    }
+}
+
+macro_rules! tuple_column_from {
+    ( $( ( $($t:ident: $i:tt),+ ) ),* ) => {
+        $(
+            impl<$($t),+> ColumnFrom for Vec<($($t,)+)>
+            where
+                $($t: Into<Value> + HasSqlType,)+
+            {
+                fn column_from<W: ColumnWrapper>(source: Self) -> W::Wrapper {
+                    let inner = vec![$(
+                        <dyn ColumnData>::from_type::<ArcColumnWrapper>(
+                            $t::get_sql_type(),
+                            *DEFAULT_TZ,
+                            source.len(),
+                        )
+                        .unwrap()
+                    ),+];
+
+                    let mut data = TupleColumnData { inner };
+                    for item in source {
+                        data.push(Value::Tuple(Arc::new(vec![$(item.$i.into()),+])));
+                    }
+
+                    W::wrap(data)
+                }
+            }
+        )*
+    };
+}
+
+tuple_column_from! {
+    (A: 0),
+    (A: 0, B: 1),
+    (A: 0, B: 1, C: 2),
+    (A: 0, B: 1, C: 2, D: 3),
+    (A: 0, B: 1, C: 2, D: 3, E: 4),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client_info::CLICK_HOUSE_REVISION,
+        types::{CompressionMethod, Simple},
+        Block,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_and_read() {
+        let block = Block::<Simple>::new()
+            .column("vals", vec![(1_u8, "foo".to_string()), (2, "bar".into())]);
+
+        let mut encoder = Encoder::new();
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
+
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
+
+        assert_eq!(block, rblock);
+        assert_eq!(
+            rblock.get_column("vals").unwrap().sql_type().to_string(),
+            "Tuple(UInt8, String)"
+        );
+
+        let actual: (u8, String) = rblock.get(1, "vals").unwrap();
+        assert_eq!(actual, (2, "bar".to_string()));
+    }
+}
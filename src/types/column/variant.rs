@@ -0,0 +1,502 @@
+use std::{cmp, sync::Arc};
+
+use chrono_tz::Tz;
+use either::Either;
+
+use crate::{
+    binary::{Encoder, ReadEx},
+    errors::Result,
+    types::{
+        column::{
+            column_data::{ArcColumnData, BoxColumnData},
+            datetime64::DEFAULT_TZ,
+            string::StringColumnData,
+            ArcColumnWrapper, ColumnData,
+        },
+        SqlType, Value, ValueRef,
+    },
+};
+
+const NULL_DISCRIMINATOR: u8 = 255;
+
+const BASIC_DISCRIMINATORS_MODE: u64 = 0;
+
+const DYNAMIC_SERIALIZATION_V1: u64 = 1;
+const DYNAMIC_SERIALIZATION_V2: u64 = 2;
+
+const DEFAULT_MAX_DYNAMIC_TYPES: usize = 32;
+
+/// Name of the variant a `Dynamic` column keeps values of types beyond `max_types` in.
+const SHARED_VARIANT: &str = "SharedVariant";
+
+/// Types whose serialization starts with a prefix. Inside a `Variant` the prefixes of all
+/// variants are sent before the discriminators, which the nested column readers don't expect.
+const PREFIXED_TYPES: [&str; 5] = ["LowCardinality", "JSON", "Object", "Dynamic", "Variant"];
+
+/// Column of `Variant(T1, T2, ...)` or `Dynamic` values.
+///
+/// Each row is either `NULL` or a value of one of the variants, the variant columns only
+/// hold the rows of their own type. Variants are ordered by type name the same way the
+/// server orders them, a `Dynamic` column is a `Variant` with the list of types that grows
+/// as values are pushed.
+pub(crate) struct VariantColumnData {
+    sql_type: &'static SqlType,
+    names: Vec<String>,
+    variants: Vec<ArcColumnData>,
+    shared: Option<usize>,
+    discriminators: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl VariantColumnData {
+    pub(crate) fn load<R: ReadEx>(
+        reader: &mut R,
+        type_names: Vec<&str>,
+        size: usize,
+        tz: Tz,
+    ) -> Result<Self> {
+        let names: Vec<String> = type_names.into_iter().map(str::to_string).collect();
+        check_variant_types(&names)?;
+        read_discriminators_mode(reader)?;
+        let (discriminators, offsets, variants) = load_variants(reader, &names, size, tz)?;
+
+        let types = variants.iter().map(|v| v.sql_type().into()).collect();
+        Ok(Self {
+            sql_type: SqlType::Variant(types).into(),
+            names,
+            variants,
+            shared: None,
+            discriminators,
+            offsets,
+        })
+    }
+
+    pub(crate) fn load_dynamic<R: ReadEx>(reader: &mut R, size: usize, tz: Tz) -> Result<Self> {
+        let version: u64 = reader.read_scalar()?;
+        match version {
+            DYNAMIC_SERIALIZATION_V1 => {
+                let _max_types = reader.read_uvarint()?;
+            }
+            DYNAMIC_SERIALIZATION_V2 => {}
+            _ => {
+                let message = format!("Unsupported Dynamic serialization version {version}.");
+                return Err(message.into());
+            }
+        }
+
+        let count = reader.read_uvarint()? as usize;
+        let mut names = Vec::with_capacity(count + 1);
+        for _ in 0..count {
+            names.push(reader.read_string()?);
+        }
+        names.push(SHARED_VARIANT.to_string());
+        names.sort();
+        check_variant_types(&names)?;
+        let shared = names.iter().position(|name| name == SHARED_VARIANT);
+
+        read_discriminators_mode(reader)?;
+        let (discriminators, offsets, variants) = load_variants(reader, &names, size, tz)?;
+
+        if variants[shared.unwrap()].len() > 0 {
+            return Err("Dynamic values stored in the shared variant are not supported.".into());
+        }
+
+        Ok(Self {
+            sql_type: &SqlType::Dynamic,
+            names,
+            variants,
+            shared,
+            discriminators,
+            offsets,
+        })
+    }
+
+    pub(crate) fn with_capacity(sql_type: SqlType, timezone: Tz, capacity: usize) -> Result<Self> {
+        let mut data = Self {
+            sql_type: &SqlType::Dynamic,
+            names: Vec::new(),
+            variants: Vec::new(),
+            shared: None,
+            discriminators: Vec::with_capacity(capacity),
+            offsets: Vec::with_capacity(capacity),
+        };
+
+        match sql_type {
+            SqlType::Variant(mut types) => {
+                types.sort_by_cached_key(|t| t.to_string());
+                for t in &types {
+                    data.names.push(SqlType::to_string(t).into_owned());
+                }
+                check_variant_types(&data.names)?;
+                for t in &types {
+                    data.variants
+                        .push(<dyn ColumnData>::from_type::<ArcColumnWrapper>(
+                            (*t).clone(),
+                            timezone,
+                            capacity,
+                        )?);
+                }
+                data.sql_type = SqlType::Variant(types).into();
+            }
+            _ => {
+                data.names.push(SHARED_VARIANT.to_string());
+                data.variants
+                    .push(Arc::new(StringColumnData::with_capacity(0)));
+                data.shared = Some(0);
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn variant_index(&mut self, value: &Value) -> usize {
+        let sql_type = SqlType::from(value.clone());
+        let name = sql_type.to_string();
+
+        let found = self
+            .variants
+            .iter()
+            .enumerate()
+            .position(|(i, v)| Some(i) != self.shared && v.sql_type().to_string() == name);
+        if let Some(index) = found {
+            return index;
+        }
+
+        if self.shared.is_none() {
+            panic!(
+                "{} column can't hold a value of type {name}.",
+                self.sql_type
+            );
+        }
+
+        let index = self.names.partition_point(|n| n.as_str() < name.as_ref());
+        let variant = <dyn ColumnData>::from_type::<ArcColumnWrapper>(sql_type, *DEFAULT_TZ, 0)
+            .unwrap_or_else(|e| panic!("can't create {name} variant: {e}"));
+        self.names.insert(index, name.into_owned());
+        self.variants.insert(index, variant);
+
+        if let Some(shared) = self.shared.as_mut() {
+            if *shared >= index {
+                *shared += 1;
+            }
+        }
+        for d in self.discriminators.iter_mut() {
+            if *d != NULL_DISCRIMINATOR && *d as usize >= index {
+                *d += 1;
+            }
+        }
+
+        index
+    }
+
+    /// Rows of each variant that belong to `start..end` rows of the column.
+    fn variant_ranges(&self, start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut ranges = vec![(0, 0); self.variants.len()];
+        for (row, &d) in self.discriminators[..end].iter().enumerate() {
+            if d == NULL_DISCRIMINATOR {
+                continue;
+            }
+            let range = &mut ranges[d as usize];
+            if row < start {
+                range.0 += 1;
+            }
+            range.1 += 1;
+        }
+        ranges
+    }
+}
+
+impl ColumnData for VariantColumnData {
+    fn sql_type(&self) -> SqlType {
+        self.sql_type.clone()
+    }
+
+    fn save(&self, encoder: &mut Encoder, start: usize, end: usize) {
+        if let Some(shared) = self.shared {
+            let count = self.names.len() - 1;
+            encoder.write(DYNAMIC_SERIALIZATION_V1);
+            encoder.uvarint(cmp::max(DEFAULT_MAX_DYNAMIC_TYPES, count) as u64);
+            encoder.uvarint(count as u64);
+            for (i, name) in self.names.iter().enumerate() {
+                if i != shared {
+                    encoder.string(name);
+                }
+            }
+        }
+
+        encoder.write(BASIC_DISCRIMINATORS_MODE);
+        encoder.write_bytes(&self.discriminators[start..end]);
+        for (variant, (lo, hi)) in self.variants.iter().zip(self.variant_ranges(start, end)) {
+            variant.save(encoder, lo, hi);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.discriminators.len()
+    }
+
+    fn push(&mut self, value: Value) {
+        let value = match value {
+            Value::Variant(_, v) => v.map(|v| *v),
+            Value::Nullable(Either::Left(_)) => None,
+            Value::Nullable(Either::Right(v)) => Some(*v),
+            v => Some(v),
+        };
+
+        let value = match value {
+            None => {
+                self.discriminators.push(NULL_DISCRIMINATOR);
+                self.offsets.push(0);
+                return;
+            }
+            Some(value) => value,
+        };
+
+        let index = self.variant_index(&value);
+        let variant = &mut self.variants[index];
+        self.offsets.push(variant.len());
+        self.discriminators.push(index as u8);
+
+        loop {
+            match Arc::get_mut(variant) {
+                None => *variant = Arc::from(variant.clone_instance()),
+                Some(inner) => {
+                    inner.push(value);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn at(&self, index: usize) -> ValueRef<'_> {
+        match self.discriminators[index] {
+            NULL_DISCRIMINATOR => ValueRef::Variant(self.sql_type, None),
+            d => {
+                let value = self.variants[d as usize].at(self.offsets[index]);
+                ValueRef::Variant(self.sql_type, Some(Box::new(value)))
+            }
+        }
+    }
+
+    fn clone_instance(&self) -> BoxColumnData {
+        Box::new(Self {
+            sql_type: self.sql_type,
+            names: self.names.clone(),
+            variants: self.variants.clone(),
+            shared: self.shared,
+            discriminators: self.discriminators.clone(),
+            offsets: self.offsets.clone(),
+        })
+    }
+
+    fn get_timezone(&self) -> Option<Tz> {
+        None
+    }
+}
+
+fn read_discriminators_mode<R: ReadEx>(reader: &mut R) -> Result<()> {
+    let mode: u64 = reader.read_scalar()?;
+    if mode != BASIC_DISCRIMINATORS_MODE {
+        let message = format!("Unsupported Variant discriminators serialization mode {mode}.");
+        return Err(message.into());
+    }
+    Ok(())
+}
+
+fn check_variant_types(names: &[String]) -> Result<()> {
+    for name in names {
+        if let Some(prefixed) = find_prefixed_type(name) {
+            let message =
+                format!("{prefixed} is not supported inside Variant or Dynamic ({name}).");
+            return Err(message.into());
+        }
+    }
+    Ok(())
+}
+
+/// Finds a type with a serialization prefix anywhere in the type name, skipping quoted
+/// `Enum` names.
+fn find_prefixed_type(type_name: &str) -> Option<&'static str> {
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut word = String::new();
+
+    for ch in type_name.chars().chain(Some(' ')) {
+        if quoted {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '\'' => quoted = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        if ch.is_alphanumeric() || ch == '_' {
+            word.push(ch);
+            continue;
+        }
+
+        if let Some(prefixed) = PREFIXED_TYPES.iter().find(|t| **t == word) {
+            return Some(prefixed);
+        }
+        word.clear();
+        quoted = ch == '\'';
+    }
+
+    None
+}
+
+type LoadedVariants = (Vec<u8>, Vec<usize>, Vec<ArcColumnData>);
+
+fn load_variants<R: ReadEx>(
+    reader: &mut R,
+    names: &[String],
+    size: usize,
+    tz: Tz,
+) -> Result<LoadedVariants> {
+    let mut discriminators = vec![0; size];
+    reader.read_bytes(discriminators.as_mut())?;
+
+    let mut counts = vec![0; names.len()];
+    let mut offsets = Vec::with_capacity(size);
+    for &d in &discriminators {
+        if d == NULL_DISCRIMINATOR {
+            offsets.push(0);
+            continue;
+        }
+        match counts.get_mut(d as usize) {
+            Some(count) => {
+                offsets.push(*count);
+                *count += 1;
+            }
+            None => {
+                let message = format!("Invalid variant discriminator {d}.");
+                return Err(message.into());
+            }
+        }
+    }
+
+    let mut variants = Vec::with_capacity(names.len());
+    for (name, count) in names.iter().zip(counts) {
+        let type_name = if name == SHARED_VARIANT {
+            "String"
+        } else {
+            name.as_str()
+        };
+        variants.push(<dyn ColumnData>::load_data::<ArcColumnWrapper, _>(
+            reader, type_name, count, tz,
+        )?);
+    }
+
+    Ok((discriminators, offsets, variants))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client_info::CLICK_HOUSE_REVISION,
+        row,
+        types::{CompressionMethod, Simple},
+        Block,
+    };
+    use std::io::Cursor;
+
+    fn variant_type() -> &'static SqlType {
+        SqlType::Variant(vec![&SqlType::UInt64, &SqlType::String]).into()
+    }
+
+    #[test]
+    fn test_variant_write_and_read() {
+        let sql_type = variant_type();
+        let mut data = VariantColumnData::with_capacity(sql_type.clone(), *DEFAULT_TZ, 3).unwrap();
+        data.push(Value::UInt64(42));
+        data.push(Value::Variant(sql_type, None));
+        data.push(Value::from("foo"));
+        data.push(Value::UInt64(7));
+
+        assert_eq!(
+            data.sql_type().to_string(),
+            "Variant(String, UInt64)".to_string()
+        );
+
+        let mut encoder = Encoder::new();
+        data.save(&mut encoder, 1, 4);
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rdata =
+            VariantColumnData::load(&mut reader, vec!["String", "UInt64"], 3, *DEFAULT_TZ).unwrap();
+
+        assert_eq!(rdata.len(), 3);
+        assert_eq!(rdata.at(0), ValueRef::Variant(rdata.sql_type, None));
+        assert_eq!(
+            rdata.at(1),
+            ValueRef::Variant(rdata.sql_type, Some(Box::new(ValueRef::from("foo"))))
+        );
+        assert_eq!(
+            rdata.at(2),
+            ValueRef::Variant(rdata.sql_type, Some(Box::new(ValueRef::UInt64(7))))
+        );
+    }
+
+    #[test]
+    fn test_variant_with_prefixed_type() {
+        let mut reader = Cursor::new(vec![0_u8; 16]);
+        let err = VariantColumnData::load(
+            &mut reader,
+            vec!["Array(LowCardinality(String))", "UInt64"],
+            1,
+            *DEFAULT_TZ,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Other error: `LowCardinality is not supported inside Variant or Dynamic \
+             (Array(LowCardinality(String))).`"
+        );
+        assert_eq!(reader.position(), 0);
+
+        let sql_type = SqlType::Variant(vec![&SqlType::UInt64, &SqlType::Json]);
+        assert!(VariantColumnData::with_capacity(sql_type, *DEFAULT_TZ, 1).is_err());
+
+        assert_eq!(find_prefixed_type("Enum8('JSON' = 1, 'it\\'s' = 2)"), None);
+        assert_eq!(find_prefixed_type("Map(String, Dynamic)"), Some("Dynamic"));
+    }
+
+    #[test]
+    fn test_dynamic_write_and_read() {
+        let values = vec![
+            Value::Variant(&SqlType::Dynamic, Some(Box::new(Value::from("foo")))),
+            Value::Variant(&SqlType::Dynamic, Some(Box::new(Value::Int64(-1)))),
+            Value::Variant(&SqlType::Dynamic, None),
+            Value::Variant(&SqlType::Dynamic, Some(Box::new(Value::Float64(0.5)))),
+        ];
+
+        let mut block = Block::<Simple>::new();
+        for value in values {
+            block.push(row! { value: value }).unwrap();
+        }
+
+        let mut encoder = Encoder::new();
+        block.write(&mut encoder, CompressionMethod::None, CLICK_HOUSE_REVISION);
+
+        let mut reader = Cursor::new(encoder.get_buffer_ref());
+        let rblock = Block::load(&mut reader, *DEFAULT_TZ, false, CLICK_HOUSE_REVISION).unwrap();
+
+        assert_eq!(block, rblock);
+        assert_eq!(
+            rblock.get_column("value").unwrap().sql_type(),
+            SqlType::Dynamic
+        );
+
+        let value: String = rblock.get(0, "value").unwrap();
+        assert_eq!(value, "foo");
+        let value: i64 = rblock.get(1, "value").unwrap();
+        assert_eq!(value, -1);
+        let value: Option<f64> = rblock.get(2, "value").unwrap();
+        assert_eq!(value, None);
+        let value: Option<f64> = rblock.get(3, "value").unwrap();
+        assert_eq!(value, Some(0.5));
+    }
+}
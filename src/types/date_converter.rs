@@ -1,4 +1,4 @@
-use chrono::prelude::*;
+use chrono::{prelude::*, Duration};
 use chrono_tz::Tz;
 
 use crate::types::{DateTimeType, SqlType, Value, ValueRef};
@@ -9,12 +9,25 @@ pub trait DateConverter {
     fn date_type() -> SqlType;
 
     fn get_days(date: NaiveDate) -> u16 {
-        const UNIX_EPOCH_DAY: i64 = 719_163;
-        let gregorian_day = i64::from(date.num_days_from_ce());
-        (gregorian_day - UNIX_EPOCH_DAY) as u16
+        days_since_epoch(date) as u16
     }
 }
 
+pub(crate) fn days_since_epoch(date: NaiveDate) -> i64 {
+    const UNIX_EPOCH_DAY: i64 = 719_163;
+    let gregorian_day = i64::from(date.num_days_from_ce());
+    gregorian_day - UNIX_EPOCH_DAY
+}
+
+/// `Date` covers 1970-01-01 ..= 2149-06-06, other dates need `Date32`.
+pub(crate) fn fits_date(date: NaiveDate) -> bool {
+    (0..=i64::from(u16::MAX)).contains(&days_since_epoch(date))
+}
+
+pub(crate) fn date_from_days(days: i64) -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(days)
+}
+
 impl DateConverter for u16 {
     fn to_date(&self, _tz: Tz) -> ValueRef<'static> {
         ValueRef::Date(*self)
@@ -29,6 +42,20 @@ impl DateConverter for u16 {
     }
 }
 
+impl DateConverter for i32 {
+    fn to_date(&self, _tz: Tz) -> ValueRef<'static> {
+        ValueRef::Date32(*self)
+    }
+
+    fn get_stamp(source: Value) -> Self {
+        days_since_epoch(NaiveDate::from(source)) as Self
+    }
+
+    fn date_type() -> SqlType {
+        SqlType::Date32
+    }
+}
+
 impl DateConverter for u32 {
     fn to_date(&self, tz: Tz) -> ValueRef<'static> {
         ValueRef::DateTime(*self, tz)
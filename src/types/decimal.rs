@@ -4,47 +4,36 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-static FACTORS10: &[i64] = &[
-    1,
-    10,
-    100,
-    1000,
-    10000,
-    100_000,
-    1_000_000,
-    10_000_000,
-    100_000_000,
-    1_000_000_000,
-    10_000_000_000,
-    100_000_000_000,
-    1_000_000_000_000,
-    10_000_000_000_000,
-    100_000_000_000_000,
-    1_000_000_000_000_000,
-    10_000_000_000_000_000,
-    100_000_000_000_000_000,
-    1_000_000_000_000_000_000,
-];
+use ethnum::{AsI256, I256};
+
+/// The largest scale and precision of `Decimal256`.
+const MAX_SCALE: u8 = 76;
+
+fn factor10(exp: usize) -> I256 {
+    I256::new(10).pow(exp as u32)
+}
 
 pub trait Base {
-    fn scale(self, scale: i64) -> i64;
+    fn scale(self, scale: I256) -> I256;
 }
 
 pub trait InternalResult {
-    fn get(underlying: i64) -> Self;
+    fn get(underlying: I256) -> Self;
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum NoBits {
     N32,
     N64,
+    N128,
+    N256,
 }
 
 /// Provides arbitrary-precision floating point decimal.
 #[derive(Clone)]
 pub struct Decimal {
-    pub(crate) underlying: i64,
-    pub(crate) nobits: NoBits, // its domain is {32, 64}
+    pub(crate) underlying: I256,
+    pub(crate) nobits: NoBits, // its domain is {32, 64, 128, 256}
     pub(crate) precision: u8,
     pub(crate) scale: u8,
 }
@@ -52,7 +41,7 @@ pub struct Decimal {
 impl Default for Decimal {
     fn default() -> Self {
         Decimal {
-            underlying: 0,
+            underlying: I256::ZERO,
             precision: 9,
             scale: 4,
             nobits: NoBits::N32,
@@ -70,11 +59,18 @@ impl Hash for Decimal {
 }
 
 macro_rules! base_for {
-    ( $( $t:ty: $cast:expr ),* ) => {
+    ( float: $( $f:ty ),* ; int: $( $i:ty ),* ) => {
         $(
-            impl Base for $t {
-                fn scale(self, scale: i64) -> i64 {
-                    $cast(self * (scale as $t)) as i64
+            impl Base for $f {
+                fn scale(self, scale: I256) -> I256 {
+                    (self * scale.as_f64() as $f).as_i256()
+                }
+            }
+        )*
+        $(
+            impl Base for $i {
+                fn scale(self, scale: I256) -> I256 {
+                    I256::from(self) * scale
                 }
             }
         )*
@@ -82,28 +78,34 @@ macro_rules! base_for {
 }
 
 base_for! {
-    f32: std::convert::identity,
-    f64: std::convert::identity,
-    i8: i64::from,
-    i16: i64::from,
-    i32: i64::from,
-    i64: std::convert::identity,
-    u8: i64::from,
-    u16: i64::from,
-    u32: i64::from,
-    u64 : std::convert::identity
+    float: f32, f64;
+    int: i8, i16, i32, i64, i128, u8, u16, u32, u64
 }
 
 impl InternalResult for i32 {
     #[inline(always)]
-    fn get(underlying: i64) -> Self {
-        underlying as Self
+    fn get(underlying: I256) -> Self {
+        underlying.as_i32()
     }
 }
 
 impl InternalResult for i64 {
     #[inline(always)]
-    fn get(underlying: i64) -> Self {
+    fn get(underlying: I256) -> Self {
+        underlying.as_i64()
+    }
+}
+
+impl InternalResult for i128 {
+    #[inline(always)]
+    fn get(underlying: I256) -> Self {
+        underlying.as_i128()
+    }
+}
+
+impl InternalResult for I256 {
+    #[inline(always)]
+    fn get(underlying: I256) -> Self {
         underlying
     }
 }
@@ -114,6 +116,10 @@ impl NoBits {
             Some(NoBits::N32)
         } else if precision <= 18 {
             Some(NoBits::N64)
+        } else if precision <= 38 {
+            Some(NoBits::N128)
+        } else if precision <= 76 {
+            Some(NoBits::N256)
         } else {
             None
         }
@@ -125,14 +131,14 @@ impl PartialEq for Decimal {
         match self.scale.cmp(&other.scale) {
             Ordering::Less => {
                 let delta = other.scale() - self.scale();
-                let underlying = self.underlying * FACTORS10[delta];
-                other.underlying == underlying
+                let underlying = self.underlying.checked_mul(factor10(delta));
+                underlying == Some(other.underlying)
             }
             Ordering::Equal => self.underlying == other.underlying,
             Ordering::Greater => {
                 let delta = self.scale() - other.scale();
-                let underlying = other.underlying * FACTORS10[delta];
-                self.underlying == underlying
+                let underlying = other.underlying.checked_mul(factor10(delta));
+                underlying == Some(self.underlying)
             }
         }
     }
@@ -165,52 +171,63 @@ impl fmt::Debug for Decimal {
 
 impl From<Decimal> for f32 {
     fn from(value: Decimal) -> Self {
-        value.underlying as f32 / FACTORS10[value.scale()] as f32
+        value.underlying.as_f32() / factor10(value.scale()).as_f32()
     }
 }
 
 impl From<Decimal> for f64 {
     fn from(value: Decimal) -> Self {
-        value.underlying as f64 / FACTORS10[value.scale()] as f64
+        value.underlying.as_f64() / factor10(value.scale()).as_f64()
     }
 }
 
 impl Decimal {
     /// Method of creating a Decimal.
-    pub fn new(underlying: i64, scale: u8) -> Decimal {
-        let precision = 18;
-        if scale > precision {
-            panic!("scale can't be greater than 18");
+    ///
+    /// Values that do not fit into `Decimal(18, S)` are backed by 128 bits,
+    /// those that do not fit into `Decimal(38, S)` by 256 bits.
+    pub fn new<U: Into<I256>>(underlying: U, scale: u8) -> Decimal {
+        if scale > MAX_SCALE {
+            panic!("scale can't be greater than {MAX_SCALE}");
         }
 
-        Decimal {
-            underlying,
-            precision,
-            scale,
-            nobits: NoBits::N64,
-        }
+        Decimal::with_underlying(underlying.into(), scale)
     }
 
     pub fn of<B: Base>(source: B, scale: u8) -> Decimal {
-        let precision = 18;
-        if scale > precision {
-            panic!("scale can't be greater than 18");
+        if scale > MAX_SCALE {
+            panic!("scale can't be greater than {MAX_SCALE}");
         }
 
-        let underlying = source.scale(FACTORS10[scale as usize]);
-        if underlying > FACTORS10[precision as usize] {
-            panic!("{} > {}", underlying, FACTORS10[precision as usize]);
+        let underlying = source.scale(factor10(scale as usize));
+        let limit = factor10(MAX_SCALE as usize);
+        if underlying.unsigned_abs() >= limit.unsigned_abs() {
+            panic!("{underlying} >= {limit}");
         }
 
+        Decimal::with_underlying(underlying, scale)
+    }
+
+    fn with_underlying(underlying: I256, scale: u8) -> Decimal {
+        let fits =
+            |precision: usize| underlying.unsigned_abs() < factor10(precision).unsigned_abs();
+        let (precision, nobits) = if scale <= 18 && fits(18) {
+            (18, NoBits::N64)
+        } else if scale <= 38 && fits(38) {
+            (38, NoBits::N128)
+        } else {
+            (MAX_SCALE, NoBits::N256)
+        };
+
         Decimal {
             underlying,
             precision,
             scale,
-            nobits: NoBits::N64,
+            nobits,
         }
     }
 
-    /// Get the internal representation of decimal as [`i32`] or [`i64`].
+    /// Get the internal representation of decimal as [`i32`], [`i64`], [`i128`] or [`I256`].
     ///
     /// example:
     /// ```rust
@@ -245,12 +262,12 @@ impl Decimal {
         let underlying = match scale.cmp(&self.scale) {
             Ordering::Less => {
                 let delta = self.scale() - scale as usize;
-                self.underlying / FACTORS10[delta]
+                self.underlying / factor10(delta)
             }
             Ordering::Equal => return self,
             Ordering::Greater => {
                 let delta = scale as usize - self.scale();
-                self.underlying * FACTORS10[delta]
+                self.underlying * factor10(delta)
             }
         };
 
@@ -305,6 +322,36 @@ mod test {
         assert_eq!(internal, 20000_i64);
     }
 
+    #[test]
+    fn test_internal128() {
+        let internal: i128 = Decimal::of(2, 4).internal();
+        assert_eq!(internal, 20000_i128);
+    }
+
+    #[test]
+    fn test_new128() {
+        let d = Decimal::new(123_456_789_012_345_678_901_234_i128, 10);
+
+        assert_eq!(d.precision, 38);
+        assert_eq!(d.nobits, NoBits::N128);
+        assert_eq!(d.to_string(), "12345678901234.5678901234");
+        assert_eq!(d, Decimal::new(1_234_567_890_123_456_789_012_340_i128, 11));
+        assert_ne!(d, Decimal::new(1, 38));
+    }
+
+    #[test]
+    fn test_new256() {
+        let underlying = I256::from(i128::MAX) * 1000 + 1;
+        let d = Decimal::new(underlying, 40);
+
+        assert_eq!(d.precision, 76);
+        assert_eq!(d.nobits, NoBits::N256);
+        assert_eq!(d.internal::<I256>(), underlying);
+        assert_eq!(d.to_string(), "17.0141183460469231731687303715884105727001");
+        assert_eq!(d, Decimal::new(underlying * 10, 41));
+        assert_eq!(Decimal::new(1, 39).nobits, NoBits::N256);
+    }
+
     #[test]
     fn test_scale() {
         assert_eq!(Decimal::of(2, 4).scale(), 4);
@@ -328,7 +375,7 @@ mod test {
         let b = a.set_scale(2);
 
         assert_eq!(2, b.scale);
-        assert_eq!(1200, b.underlying);
+        assert_eq!(I256::new(1200), b.underlying);
     }
 
     #[test]
@@ -337,7 +384,7 @@ mod test {
         let b = a.set_scale(4);
 
         assert_eq!(4, b.scale);
-        assert_eq!(120_000, b.underlying);
+        assert_eq!(I256::new(120_000), b.underlying);
     }
 
     #[test]
@@ -1,8 +1,10 @@
 use std::{
-    fmt,
+    any, fmt,
     hash::{Hash, Hasher},
 };
 
+use crate::types::{FromSqlResult, HasSqlType, SqlType, Value, ValueRef};
+
 // TODO Using strings as a keys
 #[derive(Clone, Copy, Default)]
 pub struct Enum8(pub(crate) i8);
@@ -77,3 +79,53 @@ impl Enum8 {
         self.0
     }
 }
+
+/// Rust enum mapped to ClickHouse `Enum8`/`Enum16` by the names of the values,
+/// usually implemented with `#[derive(Enum)]`.
+///
+/// `HasSqlType::get_sql_type` returns the `Enum8` or `Enum16` type with the names and
+/// the values of the variants. Cells are read by name, so the values don't have to match
+/// the ones of the column, and on insert the variants are written with the values that
+/// the table's definition gives to their names.
+pub trait EnumType: HasSqlType + Sized {
+    /// Variant with the given name.
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// Name of the variant.
+    fn name(&self) -> &'static str;
+
+    /// Reads the variant named as the `Enum8`, `Enum16` or `String` cell.
+    fn from_value_ref(value: ValueRef) -> FromSqlResult<Self> {
+        let name = value.as_str()?;
+        match Self::from_name(name) {
+            Some(variant) => Ok(variant),
+            None => {
+                let message = format!("'{name}' isn't a variant of {}.", any::type_name::<Self>());
+                Err(message.into())
+            }
+        }
+    }
+
+    /// Value of the variant, typed with the enum definition.
+    fn to_value(&self) -> Value {
+        let name = self.name();
+        match Self::get_sql_type() {
+            SqlType::Enum8(values) => {
+                let value = enum_value(&values, name);
+                Value::Enum8(values, Enum8(value))
+            }
+            SqlType::Enum16(values) => {
+                let value = enum_value(&values, name);
+                Value::Enum16(values, Enum16(value))
+            }
+            sql_type => panic!("{sql_type} isn't an enum type"),
+        }
+    }
+}
+
+pub(crate) fn enum_value<T: Copy + Default>(values: &[(String, T)], name: &str) -> T {
+    values
+        .iter()
+        .find(|(value_name, _)| value_name == name)
+        .map_or_else(T::default, |(_, value)| *value)
+}
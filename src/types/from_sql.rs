@@ -1,6 +1,7 @@
 use chrono::{prelude::*, Duration};
 use chrono_tz::Tz;
 use either::Either;
+use ethnum::{I256, U256};
 use std::{
     collections::HashMap,
     hash::Hash,
@@ -10,9 +11,9 @@ use std::{
 use crate::{
     errors::{Error, FromSqlError, Result},
     types::{
-        column::datetime64::to_datetime,
+        column::{datetime64::to_datetime, geo::nested_value_ref},
         value::{decode_ipv4, decode_ipv6},
-        Decimal, Enum16, Enum8, SqlType, ValueRef,
+        Decimal, Enum16, Enum8, MultiPolygon, Point, Polygon, Ring, SqlType, ValueRef,
     },
 };
 
@@ -125,6 +126,71 @@ where
     }
 }
 
+macro_rules! from_sql_tuple_impl {
+    ( $( $n:literal: ( $($t:ident: $i:tt),+ ) ),* ) => {
+        $(
+            impl<'a, $($t: FromSql<'a>),+> FromSql<'a> for ($($t,)+) {
+                fn from_sql(value: ValueRef<'a>) -> FromSqlResult<Self> {
+                    match value {
+                        ValueRef::Tuple(vs) if vs.len() == $n => {
+                            Ok(($($t::from_sql(vs[$i].clone())?,)+))
+                        }
+                        ValueRef::Point(_) if $n == 2 => Self::from_sql(nested_value_ref(value)),
+                        _ => {
+                            let from = SqlType::from(value.clone()).to_string();
+                            Err(Error::FromSql(FromSqlError::InvalidType {
+                                src: from,
+                                dst: stringify!(($($t,)+)).into(),
+                            }))
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_sql_tuple_impl! {
+    1: (A: 0),
+    2: (A: 0, B: 1),
+    3: (A: 0, B: 1, C: 2),
+    4: (A: 0, B: 1, C: 2, D: 3),
+    5: (A: 0, B: 1, C: 2, D: 3, E: 4),
+    6: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+    7: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+    8: (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
+
+macro_rules! from_sql_geo_impl {
+    ( $( $t:ident: $item:ident ),* ) => {
+        $(
+            impl<'a> FromSql<'a> for $t {
+                fn from_sql(value: ValueRef<'a>) -> FromSqlResult<Self> {
+                    match value {
+                        ValueRef::$t(v) => Ok(v.as_ref().clone()),
+                        ValueRef::Array(_, vs) => {
+                            vs.iter().map(|v| $item::from_sql(v.clone())).collect()
+                        }
+                        _ => {
+                            let from = SqlType::from(value.clone()).to_string();
+                            Err(Error::FromSql(FromSqlError::InvalidType {
+                                src: from,
+                                dst: stringify!($t).into(),
+                            }))
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_sql_geo_impl! {
+    Ring: Point,
+    Polygon: Ring,
+    MultiPolygon: Polygon
+}
+
 impl<'a> FromSql<'a> for Ipv4Addr {
     fn from_sql(value: ValueRef<'a>) -> FromSqlResult<Self> {
         match value {
@@ -209,7 +275,7 @@ from_sql_vec_impl! {
     String: SqlType::String => |r| r.as_string(),
     &'a [u8]: SqlType::String => |r| r.as_bytes(),
     Vec<u8>: SqlType::String => |r| r.as_bytes().map(<[u8]>::to_vec),
-    NaiveDate: SqlType::Date => |r| Ok(r.into()),
+    NaiveDate: SqlType::Date | SqlType::Date32 => |r| Ok(r.into()),
     DateTime<Tz>: SqlType::DateTime(_) => |r| Ok(r.into()),
     Enum8: SqlType::Enum8(_) => |r| Ok(r.into()),
     Enum16: SqlType::Enum16(_) => |r| Ok(r.into())
@@ -263,11 +329,13 @@ from_sql_vec_impl! {
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     u16: UInt16,
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     f32: Float32,
     f64: Float64
@@ -286,6 +354,8 @@ where
                     Ok(Some(T::from_sql(value_ref)?))
                 }
             },
+            ValueRef::Variant(_, None) => Ok(None),
+            ValueRef::Variant(_, Some(u)) => Ok(Some(T::from_sql(*u)?)),
             _ => {
                 let from = SqlType::from(value.clone()).to_string();
                 Err(Error::FromSql(FromSqlError::InvalidType {
@@ -303,6 +373,9 @@ impl<'a> FromSql<'a> for NaiveDate {
             ValueRef::Date(v) => NaiveDate::from_ymd_opt(1970, 1, 1)
                 .map(|unix_epoch| unix_epoch + Duration::days(v.into()))
                 .ok_or(Error::FromSql(FromSqlError::OutOfRange)),
+            ValueRef::Date32(v) => NaiveDate::from_ymd_opt(1970, 1, 1)
+                .and_then(|unix_epoch| unix_epoch.checked_add_signed(Duration::days(v.into())))
+                .ok_or(Error::FromSql(FromSqlError::OutOfRange)),
             _ => {
                 let from = SqlType::from(value).to_string();
                 Err(Error::FromSql(FromSqlError::InvalidType {
@@ -344,12 +417,14 @@ from_sql_impl! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     f32: Float32,
     f64: Float64
@@ -357,7 +432,10 @@ from_sql_impl! {
 
 #[cfg(test)]
 mod test {
-    use crate::types::{from_sql::FromSql, DateTimeType, SqlType, ValueRef};
+    use crate::types::{
+        column::geo::nested_value_ref, from_sql::FromSql, DateTimeType, Polygon, Ring, SqlType,
+        ValueRef,
+    };
     use chrono::prelude::*;
     use chrono_tz::Tz;
     use either::Either;
@@ -381,6 +459,32 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_tuple() {
+        let v = ValueRef::Tuple(std::sync::Arc::new(vec![
+            ValueRef::from(1_u8),
+            ValueRef::from("text"),
+        ]));
+        let actual = <(u8, &str)>::from_sql(v.clone()).unwrap();
+        assert_eq!(actual, (1_u8, "text"));
+        assert!(<(u8, &str, u8)>::from_sql(v).is_err());
+    }
+
+    #[test]
+    fn test_geo() {
+        let point = <(f64, f64)>::from_sql(ValueRef::Point((1.5, 2.0))).unwrap();
+        assert_eq!(point, (1.5, 2.0));
+
+        let ring: Ring = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+        let polygon = vec![ring.clone()];
+        let v = ValueRef::Polygon(std::sync::Arc::new(polygon.clone()));
+        assert_eq!(Polygon::from_sql(v.clone()).unwrap(), polygon);
+        assert!(Ring::from_sql(v).is_err());
+
+        let nested = nested_value_ref(ValueRef::Ring(std::sync::Arc::new(ring.clone())));
+        assert_eq!(Ring::from_sql(nested).unwrap(), ring);
+    }
+
     #[test]
     fn null_to_datetime() {
         let null_value = ValueRef::Nullable(Either::Left(
@@ -0,0 +1,471 @@
+use std::{
+    mem,
+    pin::Pin,
+    task::{self, Poll},
+    time::{Duration, Instant},
+};
+
+use either::Either;
+use futures_sink::Sink;
+use futures_util::{
+    future::{self, BoxFuture},
+    ready, FutureExt,
+};
+use log::info;
+
+use crate::{
+    errors::{ConnectionError, Error, Result},
+    try_opt,
+    types::{decimal::NoBits, Block, Cmd, Query, RowBuilder, ValueRef},
+    with_timeout, ClientHandle,
+};
+
+/// Inserts an unbounded sequence of rows with a single `INSERT` query.
+///
+/// The query is sent together with the first portion of data, after that rows are buffered
+/// and sent as data packets of the same query when the buffer reaches [`Inserter::max_rows`]
+/// rows, [`Inserter::max_bytes`] bytes or is older than [`Inserter::period`]. The limits are
+/// checked only on writes, there is no timer: rows written before a pause stay buffered
+/// until the next write, [`Inserter::flush`] or [`Inserter::end`]. Flushing the sink sends
+/// the buffer regardless of the limits. The insert is complete only after [`Inserter::end`]
+/// (or closing the sink) returns.
+///
+/// ```rust,no_run
+/// # use clickhouse_rs::{Pool, row, errors::Result};
+/// # use std::time::Duration;
+/// # async fn example(pool: Pool) -> Result<()> {
+/// let mut c = pool.get_handle().await?;
+/// let mut inserter = c
+///     .inserter("events")
+///     .max_rows(100_000)
+///     .period(Duration::from_secs(1));
+///
+/// for id in 0_u64..1_000_000 {
+///     inserter.write(row! { id: id, kind: "click" }).await?;
+/// }
+/// inserter.end().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Inserter<'a> {
+    table: Query,
+    max_rows: usize,
+    max_bytes: usize,
+    period: Option<Duration>,
+    rows: Block,
+    pending: Vec<Block>,
+    pending_rows: usize,
+    pending_bytes: usize,
+    last_flush: Instant,
+    state: InserterState<'a>,
+}
+
+enum InserterState<'a> {
+    /// Waiting for data; the `INSERT` query is sent if `header` is present.
+    Idle(Session<'a>),
+    /// Sending buffered data.
+    Sending(BoxFuture<'a, Result<Session<'a>>>),
+    /// Waiting for the server to finish the insert.
+    Closing(BoxFuture<'a, Result<()>>),
+    /// The insert is finished or failed.
+    Closed,
+}
+
+struct Session<'a> {
+    client: &'a mut ClientHandle,
+    header: Option<Block>,
+}
+
+impl<'a> Inserter<'a> {
+    pub(crate) fn new(client: &'a mut ClientHandle, table: Query) -> Self {
+        Self {
+            table,
+            max_rows: usize::MAX,
+            max_bytes: usize::MAX,
+            period: None,
+            rows: Block::new(),
+            pending: Vec::new(),
+            pending_rows: 0,
+            pending_bytes: 0,
+            last_flush: Instant::now(),
+            state: InserterState::Idle(Session {
+                client,
+                header: None,
+            }),
+        }
+    }
+
+    /// Sends buffered rows when their number reaches `max_rows`.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Sends buffered rows when their approximate uncompressed size reaches `max_bytes`.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sends buffered rows when `period` has passed since the previous send.
+    ///
+    /// The period is checked only when rows are written, nothing is sent while no rows
+    /// arrive; call [`Inserter::flush`] to send them after a pause.
+    pub fn period(mut self, period: Duration) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    /// Buffers a row, buffered rows are sent if one of the limits is reached.
+    pub async fn write<R: RowBuilder>(&mut self, row: R) -> Result<()> {
+        self.push_row(row)?;
+        future::poll_fn(|cx| self.poll_ready_(cx)).await
+    }
+
+    /// Buffers a block, buffered rows are sent if one of the limits is reached.
+    pub async fn write_block(&mut self, block: Block) -> Result<()> {
+        self.push_block(block);
+        future::poll_fn(|cx| self.poll_ready_(cx)).await
+    }
+
+    /// Sends buffered rows regardless of the limits.
+    pub async fn flush(&mut self) -> Result<()> {
+        future::poll_fn(|cx| self.poll_flush_(cx)).await
+    }
+
+    /// Sends the rest of buffered rows and waits until the server finishes the insert.
+    pub async fn end(mut self) -> Result<()> {
+        future::poll_fn(|cx| self.poll_close_(cx)).await
+    }
+
+    fn push_row<R: RowBuilder>(&mut self, row: R) -> Result<()> {
+        self.rows.push(row)?;
+        let last = self.rows.row_count() - 1;
+        self.pending_bytes += self
+            .rows
+            .columns()
+            .iter()
+            .map(|column| value_size(&column.at(last)))
+            .sum::<usize>();
+        self.pending_rows += 1;
+        Ok(())
+    }
+
+    fn push_block(&mut self, block: Block) {
+        if block.is_empty() {
+            return;
+        }
+
+        self.seal_rows();
+        for column in block.columns() {
+            self.pending_bytes += (0..column.len())
+                .map(|i| value_size(&column.at(i)))
+                .sum::<usize>();
+        }
+        self.pending_rows += block.row_count();
+        self.pending.push(block);
+    }
+
+    fn seal_rows(&mut self) {
+        if !self.rows.is_empty() {
+            self.pending.push(mem::take(&mut self.rows));
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.pending_rows >= self.max_rows
+            || self.pending_bytes >= self.max_bytes
+            || matches!(self.period, Some(period) if self.last_flush.elapsed() >= period)
+    }
+
+    fn start_send(&mut self) -> Result<()> {
+        self.seal_rows();
+        let blocks = mem::take(&mut self.pending);
+        self.pending_rows = 0;
+        self.pending_bytes = 0;
+        self.last_flush = Instant::now();
+
+        match mem::replace(&mut self.state, InserterState::Closed) {
+            InserterState::Idle(session) => {
+                let table = self.table.clone();
+                self.state = InserterState::Sending(Box::pin(session.send(table, blocks)));
+                Ok(())
+            }
+            InserterState::Closed => Err(Error::Connection(ConnectionError::Broken)),
+            InserterState::Sending(_) | InserterState::Closing(_) => unreachable!(),
+        }
+    }
+
+    /// Drives the data that is being sent.
+    fn poll_sending(&mut self, cx: &mut task::Context) -> Poll<Result<()>> {
+        if let InserterState::Sending(ref mut sending) = self.state {
+            match ready!(sending.poll_unpin(cx)) {
+                Ok(session) => self.state = InserterState::Idle(session),
+                Err(err) => {
+                    self.state = InserterState::Closed;
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_ready_(&mut self, cx: &mut task::Context) -> Poll<Result<()>> {
+        ready!(self.poll_sending(cx))?;
+        if self.pending_rows > 0 && self.is_full() {
+            self.start_send()?;
+            ready!(self.poll_sending(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush_(&mut self, cx: &mut task::Context) -> Poll<Result<()>> {
+        ready!(self.poll_sending(cx))?;
+        if self.pending_rows > 0 {
+            self.start_send()?;
+            ready!(self.poll_sending(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close_(&mut self, cx: &mut task::Context) -> Poll<Result<()>> {
+        if let InserterState::Idle(_) | InserterState::Sending(_) = self.state {
+            ready!(self.poll_flush_(cx))?;
+            if let InserterState::Idle(session) =
+                mem::replace(&mut self.state, InserterState::Closed)
+            {
+                self.state = InserterState::Closing(Box::pin(session.end()));
+            }
+        }
+
+        if let InserterState::Closing(ref mut closing) = self.state {
+            let result = ready!(closing.poll_unpin(cx));
+            self.state = InserterState::Closed;
+            return Poll::Ready(result);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a> Session<'a> {
+    async fn send(mut self, table: Query, blocks: Vec<Block>) -> Result<Session<'a>> {
+        let timeout = try_opt!(self.client.context.options.get())
+            .insert_timeout
+            .unwrap_or_else(|| Duration::from_secs(0));
+        let context = self.client.context.clone();
+        let callbacks = self.client.callbacks.clone();
+
+        let header = with_timeout(
+            async {
+                let (mut transport, header) = match self.header.take() {
+                    Some(header) => (self.client.get_inner()?, header),
+                    None => {
+                        let query = ClientHandle::make_query(table, &blocks[0])?;
+                        let context = context.clone();
+                        self.client
+                            .wrap_future(move |c| {
+                                info!("[insert]     {}", query.get_sql());
+                                let transport = c.get_inner();
+
+                                async move {
+                                    let transport = transport?.clear().await?;
+                                    ClientHandle::send_insert_query_(
+                                        transport, context, query, &callbacks,
+                                    )
+                                    .await
+                                }
+                            })
+                            .await?
+                    }
+                };
+
+                for block in blocks {
+                    let block = block.cast_to(&header)?;
+                    transport = transport
+                        .send_cmd(Cmd::SendData(block, context.clone()))
+                        .await?;
+                }
+
+                // The insert isn't finished until the end of data is sent.
+                transport.inconsistent = true;
+                self.client.inner = Some(transport);
+                Ok(header)
+            },
+            timeout,
+        )
+        .await?;
+
+        self.header = Some(header);
+        Ok(self)
+    }
+
+    async fn end(self) -> Result<()> {
+        if self.header.is_none() {
+            return Ok(());
+        }
+
+        let timeout = try_opt!(self.client.context.options.get())
+            .insert_timeout
+            .unwrap_or_else(|| Duration::from_secs(0));
+        let context = self.client.context.clone();
+        let callbacks = self.client.callbacks.clone();
+
+        with_timeout(
+            async {
+                let mut transport = self.client.get_inner()?;
+                transport.inconsistent = false;
+                let (transport, _) = transport
+                    .call(Cmd::SendData(Block::default(), context))
+                    .read_block(&callbacks)
+                    .await?;
+                self.client.inner = Some(transport);
+                Ok(())
+            },
+            timeout,
+        )
+        .await
+    }
+}
+
+impl<'a> Sink<Block> for Inserter<'a> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_ready_(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, block: Block) -> Result<()> {
+        self.get_mut().push_block(block);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_flush_(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_close_(cx)
+    }
+}
+
+impl<'a, R: RowBuilder> Sink<R> for Inserter<'a> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_ready_(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, row: R) -> Result<()> {
+        self.get_mut().push_row(row)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_flush_(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut task::Context) -> Poll<Result<()>> {
+        self.get_mut().poll_close_(cx)
+    }
+}
+
+/// Approximate size of a value in the native format.
+fn value_size(value: &ValueRef) -> usize {
+    match value {
+        ValueRef::Bool(_) | ValueRef::UInt8(_) | ValueRef::Int8(_) | ValueRef::Enum8(_, _) => 1,
+        ValueRef::UInt16(_) | ValueRef::Int16(_) | ValueRef::Date(_) | ValueRef::Enum16(_, _) => 2,
+        ValueRef::UInt32(_)
+        | ValueRef::Int32(_)
+        | ValueRef::Float32(_)
+        | ValueRef::Date32(_)
+        | ValueRef::DateTime(_, _)
+        | ValueRef::Ipv4(_) => 4,
+        ValueRef::UInt64(_)
+        | ValueRef::Int64(_)
+        | ValueRef::Float64(_)
+        | ValueRef::DateTime64(_, _) => 8,
+        ValueRef::UInt128(_) | ValueRef::Int128(_) | ValueRef::Ipv6(_) | ValueRef::Uuid(_) => 16,
+        ValueRef::UInt256(_) | ValueRef::Int256(_) => 32,
+        ValueRef::Decimal(v) => match v.nobits {
+            NoBits::N32 => 4,
+            NoBits::N64 => 8,
+            NoBits::N128 => 16,
+            NoBits::N256 => 32,
+        },
+        ValueRef::String(v) => v.len() + 1,
+        ValueRef::Json(v) => v.len() + 1,
+        ValueRef::Nullable(Either::Left(_)) => 1,
+        ValueRef::Nullable(Either::Right(v)) => 1 + value_size(v),
+        ValueRef::Variant(_, None) => 1,
+        ValueRef::Variant(_, Some(v)) => 1 + value_size(v),
+        ValueRef::Array(_, vs) => 8 + vs.iter().map(value_size).sum::<usize>(),
+        ValueRef::Tuple(vs) => vs.iter().map(value_size).sum(),
+        ValueRef::Point(_) => 16,
+        ValueRef::Ring(r) => 8 + 16 * r.len(),
+        ValueRef::Polygon(p) => 8 + p.iter().map(|r| 8 + 16 * r.len()).sum::<usize>(),
+        ValueRef::MultiPolygon(mp) => {
+            8 + mp
+                .iter()
+                .map(|p| 8 + p.iter().map(|r| 8 + 16 * r.len()).sum::<usize>())
+                .sum::<usize>()
+        }
+        ValueRef::Map(_, _, map) => {
+            8 + map
+                .iter()
+                .map(|(k, v)| value_size(k) + value_size(v))
+                .sum::<usize>()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{row, test_misc::DATABASE_URL, Pool};
+
+    #[test]
+    fn test_value_size() {
+        assert_eq!(value_size(&ValueRef::UInt32(1)), 4);
+        assert_eq!(value_size(&ValueRef::String(b"foo")), 4);
+        assert_eq!(
+            value_size(&ValueRef::Nullable(Either::Right(Box::new(
+                ValueRef::UInt8(1)
+            )))),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_period() -> Result<()> {
+        let table = "clickhouse_test_inserter_period";
+        let pool = Pool::new(DATABASE_URL.as_str());
+        let mut c = pool.get_handle().await?;
+        c.execute(format!("DROP TABLE IF EXISTS {table}")).await?;
+        c.execute(format!("CREATE TABLE {table} (id UInt64) Engine=Memory"))
+            .await?;
+
+        let period = Duration::from_millis(100);
+        let mut inserter = c.inserter(table).period(period);
+        inserter.write(row! { id: 1_u64 }).await?;
+        assert_eq!(inserter.pending_rows, 1);
+
+        tokio::time::sleep(period * 2).await;
+        assert_eq!(inserter.pending_rows, 1);
+
+        inserter.write(row! { id: 2_u64 }).await?;
+        assert_eq!(inserter.pending_rows, 0);
+
+        inserter.write(row! { id: 3_u64 }).await?;
+        assert_eq!(inserter.pending_rows, 1);
+        inserter.end().await?;
+
+        let block = c
+            .query(format!("SELECT count() AS n FROM {table}"))
+            .fetch_all()
+            .await?;
+        assert_eq!(block.get::<u64, _>(0, "n")?, 3);
+        c.execute(format!("DROP TABLE {table}")).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! Conversions between values and `serde_json::Value`, enabled by the `json` feature.
+
+use std::sync::Arc;
+
+use either::Either;
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::{
+    errors::Result,
+    types::{
+        column::geo::nested_value_ref, FromSql, FromSqlResult, HasSqlType, SqlType, Value, ValueRef,
+    },
+};
+
+impl From<JsonValue> for Value {
+    fn from(value: JsonValue) -> Self {
+        Value::Json(Arc::new(value.to_string()))
+    }
+}
+
+impl HasSqlType for JsonValue {
+    fn get_sql_type() -> SqlType {
+        SqlType::Json
+    }
+}
+
+/// `JSON` cells are parsed, other values are converted to the matching JSON type:
+/// `NULL` to `null`, arrays and tuples to arrays, maps to objects, and values
+/// without JSON counterpart (dates, UUIDs, big integers, ...) to strings.
+impl<'a> FromSql<'a> for JsonValue {
+    fn from_sql(value: ValueRef<'a>) -> FromSqlResult<Self> {
+        to_json(value)
+    }
+}
+
+fn to_json(value: ValueRef) -> Result<JsonValue> {
+    Ok(match value {
+        ValueRef::Json(text) => serde_json::from_str(text)?,
+        ValueRef::Nullable(Either::Left(_)) | ValueRef::Variant(_, None) => JsonValue::Null,
+        ValueRef::Nullable(Either::Right(v)) | ValueRef::Variant(_, Some(v)) => to_json(*v)?,
+        ValueRef::Bool(v) => v.into(),
+        ValueRef::UInt8(v) => v.into(),
+        ValueRef::UInt16(v) => v.into(),
+        ValueRef::UInt32(v) => v.into(),
+        ValueRef::UInt64(v) => v.into(),
+        ValueRef::Int8(v) => v.into(),
+        ValueRef::Int16(v) => v.into(),
+        ValueRef::Int32(v) => v.into(),
+        ValueRef::Int64(v) => v.into(),
+        ValueRef::Float32(v) => float(v.into()),
+        ValueRef::Float64(v) => float(v),
+        ValueRef::Decimal(v) => float(v.into()),
+        ValueRef::String(v) => String::from_utf8_lossy(v).into_owned().into(),
+        ValueRef::Array(_, vs) | ValueRef::Tuple(vs) => JsonValue::Array(
+            vs.iter()
+                .map(|v| to_json(v.clone()))
+                .collect::<Result<_>>()?,
+        ),
+        ValueRef::Map(_, _, map) => {
+            let mut object = Map::with_capacity(map.len());
+            for (k, v) in map.iter() {
+                let key = match k {
+                    ValueRef::String(bs) => String::from_utf8_lossy(bs).into_owned(),
+                    k => k.to_string(),
+                };
+                object.insert(key, to_json(v.clone())?);
+            }
+            JsonValue::Object(object)
+        }
+        v @ (ValueRef::Point(_)
+        | ValueRef::Ring(_)
+        | ValueRef::Polygon(_)
+        | ValueRef::MultiPolygon(_)) => to_json(nested_value_ref(v))?,
+        v => v.to_string().into(),
+    })
+}
+
+fn float(v: f64) -> JsonValue {
+    Number::from_f64(v).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{row, types::Simple, Block};
+    use serde_json::json;
+
+    #[test]
+    fn test_json_column() {
+        let documents = vec![json!({"a": 1, "b": [true, null]}), json!("text")];
+
+        let mut block = Block::<Simple>::new();
+        for document in &documents {
+            block.push(row! { doc: document.clone() }).unwrap();
+        }
+
+        assert_eq!(block.get_column("doc").unwrap().sql_type(), SqlType::Json);
+        for (i, document) in documents.iter().enumerate() {
+            let actual: JsonValue = block.get(i, "doc").unwrap();
+            assert_eq!(&actual, document);
+        }
+
+        let text: String = block.get(0, "doc").unwrap();
+        assert_eq!(text, r#"{"a":1,"b":[true,null]}"#);
+    }
+
+    #[test]
+    fn test_from_values() {
+        let variant_type = SqlType::Variant(vec![&SqlType::String, &SqlType::UInt64]).into();
+        let value = ValueRef::Array(
+            variant_type,
+            Arc::new(vec![
+                ValueRef::Variant(variant_type, Some(Box::new(ValueRef::UInt64(1)))),
+                ValueRef::Variant(variant_type, None),
+                ValueRef::Variant(variant_type, Some(Box::new(ValueRef::from("x")))),
+            ]),
+        );
+        assert_eq!(JsonValue::from_sql(value).unwrap(), json!([1, null, "x"]));
+
+        let point = ValueRef::Point((1.5, 2.0));
+        assert_eq!(JsonValue::from_sql(point).unwrap(), json!([1.5, 2.0]));
+    }
+}
@@ -1,3 +1,5 @@
+use ethnum::{I256, U256};
+
 pub trait Marshal {
     fn marshal(&self, scratch: &mut [u8]);
 }
@@ -27,7 +29,7 @@ macro_rules! float_marshals {
     };
 }
 
-int_marshals! { u8, u16, u32, u64, u128, i8, i16, i32, i64, i128 }
+int_marshals! { u8, u16, u32, u64, u128, U256, i8, i16, i32, i64, i128, I256 }
 float_marshals! { f32, f64 }
 
 impl Marshal for bool {
@@ -41,6 +43,7 @@ mod test {
     use std::fmt;
 
     use crate::types::{Marshal, StatBuffer, Unmarshal};
+    use ethnum::{I256, U256};
     use rand::distributions::{Distribution, Standard};
     use rand::random;
 
@@ -110,6 +113,23 @@ mod test {
         test_some::<i128>()
     }
 
+    #[test]
+    fn test_256() {
+        let mut buffer = U256::buffer();
+        let v = U256::from_words(1, 2);
+        v.marshal(buffer.as_mut());
+        assert_eq!((buffer[0], buffer[16]), (2, 1));
+        assert_eq!(U256::unmarshal(buffer.as_ref()), v);
+
+        let mut buffer = I256::buffer();
+        let v = I256::new(-2);
+        v.marshal(buffer.as_mut());
+        let mut expected = [0xff; 32];
+        expected[0] = 0xfe;
+        assert_eq!(buffer, expected);
+        assert_eq!(I256::unmarshal(buffer.as_ref()), v);
+    }
+
     #[test]
     fn test_f32() {
         test_some::<f32>()
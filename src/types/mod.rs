@@ -1,4 +1,12 @@
-use std::{borrow::Cow, collections::HashMap, fmt, mem, pin::Pin, str::FromStr, sync::Mutex};
+use std::{
+    borrow::Cow,
+    cmp,
+    collections::HashMap,
+    fmt, mem,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 use chrono::prelude::*;
 use chrono_tz::Tz;
@@ -6,22 +14,38 @@ use hostname::get;
 
 use lazy_static::lazy_static;
 
-use crate::{errors::ServerError, types::column::datetime64::DEFAULT_TZ};
+use crate::{client_info, errors::ServerError, types::column::datetime64::DEFAULT_TZ};
 
 pub use self::{
-    block::{Block, RCons, RNil, Row, RowBuilder, Rows},
+    block::{Block, FromRow, IntoBlock, RCons, RNil, Row, RowBuilder, Rows},
     column::{Column, ColumnType, Complex, Simple},
     decimal::Decimal,
-    enums::{Enum16, Enum8},
+    enums::{Enum16, Enum8, EnumType},
     from_sql::{FromSql, FromSqlResult},
+    inserter::Inserter,
     options::Options,
-    options::{SettingType, SettingValue},
+    options::{CompressionMethod, LoadBalancing, SettingType, SettingValue},
     query::Query,
-    query_result::QueryResult,
+    query_result::{cancel_handle::CancelHandle, QueryData, QueryResult},
+    server_log::LogRecord,
     value::Value,
     value_ref::ValueRef,
 };
 
+pub use ethnum::{I256, U256};
+
+/// Client side representation of ClickHouse `Point`, a pair of `x` and `y` coordinates.
+pub type Point = (f64, f64);
+
+/// Client side representation of ClickHouse `Ring`, a polygon without holes.
+pub type Ring = Vec<Point>;
+
+/// Client side representation of ClickHouse `Polygon`, an outer ring followed by holes.
+pub type Polygon = Vec<Ring>;
+
+/// Client side representation of ClickHouse `MultiPolygon`.
+pub type MultiPolygon = Vec<Polygon>;
+
 pub(crate) use self::{
     cmd::Cmd,
     date_converter::DateConverter,
@@ -42,24 +66,78 @@ mod value_ref;
 
 pub(crate) mod block;
 mod cmd;
+mod inserter;
 
 mod date_converter;
 mod query;
 pub(crate) mod query_result;
+mod server_log;
 
 mod decimal;
 mod enums;
 mod options;
-
+#[cfg(feature = "serde")]
+mod row_serde;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "json")]
+mod json;
+
+/// Query execution progress reported by the server.
+///
+/// Every packet contains increments since the previous one, except
+/// `total_rows` which is an estimate of rows to be read.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
-pub(crate) struct Progress {
+pub struct Progress {
+    /// Number of rows read.
     pub rows: u64,
+    /// Number of bytes read.
     pub bytes: u64,
+    /// Estimated total number of rows to read.
     pub total_rows: u64,
+    /// Number of rows written.
     pub written_rows: u64,
+    /// Number of bytes written.
     pub written_bytes: u64,
 }
 
+pub(crate) type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+pub(crate) type ServerLogCallback = Arc<dyn Fn(LogRecord) + Send + Sync>;
+
+/// User callbacks for the packets that are not a part of query result.
+#[derive(Clone, Default)]
+pub(crate) struct Callbacks {
+    pub(crate) progress: Option<ProgressCallback>,
+    pub(crate) server_log: Option<ServerLogCallback>,
+}
+
+impl Callbacks {
+    pub(crate) fn on_progress(&self, progress: Progress) {
+        if let Some(ref callback) = self.progress {
+            callback(progress);
+        }
+    }
+
+    /// Passes server log records to the user callback,
+    /// or to the `log` crate if it isn't set.
+    pub(crate) fn on_server_log(&self, records: Vec<LogRecord>) {
+        for record in records {
+            match self.server_log {
+                Some(ref callback) => callback(record),
+                None => log::log!(
+                    target: "clickhouse_rs::server_log",
+                    record.level(),
+                    "[{}] {{{}}} <{}> {}",
+                    record.host_name,
+                    record.query_id,
+                    record.source,
+                    record.text
+                ),
+            }
+        }
+    }
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 pub struct ProfileInfo {
     pub rows: u64,
@@ -142,6 +220,13 @@ impl Default for Context {
     }
 }
 
+impl Context {
+    /// Protocol revision negotiated with the server.
+    pub(crate) fn revision(&self) -> u64 {
+        cmp::min(self.server_info.revision, client_info::CLICK_HOUSE_REVISION)
+    }
+}
+
 #[derive(Clone)]
 pub(crate) enum Packet<S> {
     Hello(S, ServerInfo),
@@ -151,6 +236,10 @@ pub(crate) enum Packet<S> {
     TableColumns(TableColumns),
     Exception(ServerError),
     Block(Block),
+    Totals(Block),
+    Extremes(Block),
+    ServerLog(Vec<LogRecord>),
+    ProfileEvents(Block),
     Eof(S),
 }
 
@@ -164,6 +253,10 @@ impl<S> fmt::Debug for Packet<S> {
             Packet::TableColumns(info) => write!(f, "TableColumns({info:?})"),
             Packet::Exception(e) => write!(f, "Exception({e:?})"),
             Packet::Block(b) => write!(f, "Block({b:?})"),
+            Packet::Totals(b) => write!(f, "Totals({b:?})"),
+            Packet::Extremes(b) => write!(f, "Extremes({b:?})"),
+            Packet::ServerLog(records) => write!(f, "ServerLog({records:?})"),
+            Packet::ProfileEvents(b) => write!(f, "ProfileEvents({b:?})"),
             Packet::Eof(_) => write!(f, "Eof"),
         }
     }
@@ -179,6 +272,10 @@ impl<S> Packet<S> {
             Packet::TableColumns(table_columns) => Packet::TableColumns(table_columns),
             Packet::Exception(exception) => Packet::Exception(exception),
             Packet::Block(block) => Packet::Block(block),
+            Packet::Totals(block) => Packet::Totals(block),
+            Packet::Extremes(block) => Packet::Extremes(block),
+            Packet::ServerLog(records) => Packet::ServerLog(records),
+            Packet::ProfileEvents(block) => Packet::ProfileEvents(block),
             Packet::Eof(_) => Packet::Eof(transport.take().unwrap()),
         }
     }
@@ -207,11 +304,13 @@ has_sql_type! {
     u32: SqlType::UInt32,
     u64: SqlType::UInt64,
     u128: SqlType::UInt128,
+    U256: SqlType::UInt256,
     i8: SqlType::Int8,
     i16: SqlType::Int16,
     i32: SqlType::Int32,
     i64: SqlType::Int64,
     i128: SqlType::Int128,
+    I256: SqlType::Int256,
     &str: SqlType::String,
     String: SqlType::String,
     f32: SqlType::Float32,
@@ -220,6 +319,29 @@ has_sql_type! {
     DateTime<Tz>: SqlType::DateTime(DateTimeType::DateTime32)
 }
 
+macro_rules! tuple_has_sql_type {
+    ( $( ( $($t:ident),+ ) ),* ) => {
+        $(
+            impl<$($t: HasSqlType),+> HasSqlType for ($($t,)+) {
+                fn get_sql_type() -> SqlType {
+                    SqlType::Tuple(vec![$($t::get_sql_type().into()),+])
+                }
+            }
+        )*
+    };
+}
+
+tuple_has_sql_type! {
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H)
+}
+
 impl<K, V> HasSqlType for HashMap<K, V>
 where
     K: HasSqlType,
@@ -314,16 +436,19 @@ pub enum SqlType {
     UInt32,
     UInt64,
     UInt128,
+    UInt256,
     Int8,
     Int16,
     Int32,
     Int64,
     Int128,
+    Int256,
     String,
     FixedString(usize),
     Float32,
     Float64,
     Date,
+    Date32,
     DateTime(DateTimeType),
     Ipv4,
     Ipv6,
@@ -336,6 +461,14 @@ pub enum SqlType {
     Enum16(Vec<(String, i16)>),
     SimpleAggregateFunction(SimpleAggFunc, &'static SqlType),
     Map(&'static SqlType, &'static SqlType),
+    Tuple(Vec<&'static SqlType>),
+    Point,
+    Ring,
+    Polygon,
+    MultiPolygon,
+    Json,
+    Variant(Vec<&'static SqlType>),
+    Dynamic,
 }
 
 lazy_static! {
@@ -357,6 +490,7 @@ impl From<SqlType> for &'static SqlType {
             SqlType::Float32 => &SqlType::Float32,
             SqlType::Float64 => &SqlType::Float64,
             SqlType::Date => &SqlType::Date,
+            SqlType::Date32 => &SqlType::Date32,
             _ => {
                 let mut guard = TYPES_CACHE.lock().unwrap();
                 loop {
@@ -401,17 +535,20 @@ impl SqlType {
             SqlType::UInt32 => "UInt32".into(),
             SqlType::UInt64 => "UInt64".into(),
             SqlType::UInt128 => "UInt128".into(),
+            SqlType::UInt256 => "UInt256".into(),
             SqlType::Int8 => "Int8".into(),
             SqlType::Int16 => "Int16".into(),
             SqlType::Int32 => "Int32".into(),
             SqlType::Int64 => "Int64".into(),
             SqlType::Int128 => "Int128".into(),
+            SqlType::Int256 => "Int256".into(),
             SqlType::String => "String".into(),
             SqlType::FixedString(str_len) => format!("FixedString({str_len})").into(),
             SqlType::LowCardinality(inner) => format!("LowCardinality({})", &inner).into(),
             SqlType::Float32 => "Float32".into(),
             SqlType::Float64 => "Float64".into(),
             SqlType::Date => "Date".into(),
+            SqlType::Date32 => "Date32".into(),
             SqlType::DateTime(DateTimeType::DateTime64(precision, tz)) => {
                 format!("DateTime64({precision}, '{tz:?}')").into()
             }
@@ -441,9 +578,37 @@ impl SqlType {
                 format!("Enum16({})", a.join(",")).into()
             }
             SqlType::Map(k, v) => format!("Map({}, {})", &k, &v).into(),
+            SqlType::Tuple(types) => {
+                let a: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+                format!("Tuple({})", a.join(", ")).into()
+            }
+            SqlType::Point => "Point".into(),
+            SqlType::Ring => "Ring".into(),
+            SqlType::Polygon => "Polygon".into(),
+            SqlType::MultiPolygon => "MultiPolygon".into(),
+            SqlType::Json => "JSON".into(),
+            SqlType::Variant(types) => {
+                let a: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+                format!("Variant({})", a.join(", ")).into()
+            }
+            SqlType::Dynamic => "Dynamic".into(),
         }
     }
 
+    /// Returns the `Tuple` or `Array` type that a geo type is stored as.
+    pub(crate) fn geo_storage_type(&self) -> Option<SqlType> {
+        let inner = match self {
+            SqlType::Point => {
+                return Some(SqlType::Tuple(vec![&SqlType::Float64, &SqlType::Float64]))
+            }
+            SqlType::Ring => SqlType::Point,
+            SqlType::Polygon => SqlType::Ring,
+            SqlType::MultiPolygon => SqlType::Polygon,
+            _ => return None,
+        };
+        Some(SqlType::Array(inner.geo_storage_type()?.into()))
+    }
+
     pub(crate) fn level(&self) -> u8 {
         match self {
             SqlType::Nullable(inner) => 1 + inner.level(),
@@ -483,3 +648,24 @@ fn test_to_string() {
     let actual = SqlType::Nullable(&SqlType::UInt8).to_string();
     assert_eq!(expected, actual)
 }
+
+#[test]
+fn test_tuple_to_string() {
+    let expected: Cow<'static, str> = "Tuple(UInt8, Nullable(String))".into();
+    let actual = SqlType::Tuple(vec![
+        &SqlType::UInt8,
+        SqlType::Nullable(&SqlType::String).into(),
+    ])
+    .to_string();
+    assert_eq!(expected, actual)
+}
+
+#[test]
+fn test_geo_storage_type() {
+    assert_eq!(SqlType::Polygon.to_string(), "Polygon");
+    assert_eq!(
+        SqlType::Polygon.geo_storage_type().unwrap().to_string(),
+        "Array(Array(Tuple(Float64, Float64)))"
+    );
+    assert_eq!(SqlType::Tuple(vec![]).geo_storage_type(), None);
+}
@@ -15,6 +15,10 @@ const DEFAULT_MIN_CONNS: usize = 10;
 
 const DEFAULT_MAX_CONNS: usize = 20;
 
+const DEFAULT_LZ4HC_LEVEL: i32 = 9;
+
+const DEFAULT_ZSTD_LEVEL: i32 = 1;
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 enum State {
@@ -232,6 +236,67 @@ impl fmt::Display for SettingValue {
     }
 }
 
+/// Compression method used for data blocks sent to and received from the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CompressionMethod {
+    /// No compression.
+    #[default]
+    None,
+    /// LZ4 (`compression=lz4`).
+    Lz4,
+    /// LZ4HC with a compression level (`compression=lz4hc` or `compression=lz4hc(12)`).
+    Lz4Hc(i32),
+    /// ZSTD with a compression level (`compression=zstd` or `compression=zstd(3)`).
+    Zstd(i32),
+}
+
+/// Strategy of picking a host out of `addr` and `alt_hosts` for a new connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LoadBalancing {
+    /// Hosts in turn (`load_balancing=round_robin`).
+    #[default]
+    RoundRobin,
+    /// A random host (`load_balancing=random`).
+    Random,
+    /// The first available host in the listed order (`load_balancing=in_order`).
+    InOrder,
+    /// The host with the fewest open connections (`load_balancing=least_connections`).
+    LeastConnections,
+}
+
+impl CompressionMethod {
+    pub(crate) fn is_enabled(self) -> bool {
+        self != CompressionMethod::None
+    }
+
+    /// Settings asking the server to compress its blocks the same way.
+    pub(crate) fn settings(self) -> HashMap<String, SettingValue> {
+        let mut settings = HashMap::new();
+        if let CompressionMethod::Zstd(level) = self {
+            let value = SettingType::String("ZSTD".into());
+            settings.insert(
+                "network_compression_method".into(),
+                SettingValue {
+                    value,
+                    is_important: false,
+                },
+            );
+
+            if level > 0 {
+                let value = SettingType::UInt64(level as u64);
+                settings.insert(
+                    "network_zstd_compression_level".into(),
+                    SettingValue {
+                        value,
+                        is_important: false,
+                    },
+                );
+            }
+        }
+        settings
+    }
+}
+
 /// Clickhouse connection options.
 #[derive(Clone, PartialEq)]
 pub struct Options {
@@ -245,13 +310,23 @@ pub struct Options {
     /// Access password (defaults to `""`).
     pub(crate) password: String,
 
-    /// Enable compression (defaults to `false`).
-    pub(crate) compression: bool,
+    /// Compression method (defaults to `none`).
+    pub(crate) compression: CompressionMethod,
 
     /// Lower bound of opened connections for `Pool` (defaults to 10).
     pub(crate) pool_min: usize,
     /// Upper bound of opened connections for `Pool` (defaults to 20).
     pub(crate) pool_max: usize,
+    /// Idle connections of `Pool` are closed after this time (defaults to `None`).
+    pub(crate) idle_timeout: Option<Duration>,
+    /// Connections of `Pool` are closed after this time since opening (defaults to `None`).
+    pub(crate) max_lifetime: Option<Duration>,
+    /// Interval of pinging idle connections of `Pool` in background (defaults to `None`).
+    pub(crate) health_check_interval: Option<Duration>,
+    /// Timeout for getting a connection from `Pool` (defaults to `None`).
+    pub(crate) checkout_timeout: Option<Duration>,
+    /// Upper bound of tasks waiting for a connection from `Pool` (defaults to `None`).
+    pub(crate) max_waiters: Option<usize>,
 
     /// Whether to enable `TCP_NODELAY` (defaults to `true`).
     pub(crate) nodelay: bool,
@@ -296,6 +371,10 @@ pub struct Options {
 
     /// Comma separated list of single address host for load-balancing.
     pub(crate) alt_hosts: Vec<Url>,
+    /// Strategy of picking a host for a new connection (defaults to `RoundRobin`).
+    pub(crate) load_balancing: LoadBalancing,
+    /// Time a host is skipped for after a failed connect (defaults to `None`).
+    pub(crate) host_quarantine: Option<Duration>,
 }
 
 impl fmt::Debug for Options {
@@ -306,6 +385,11 @@ impl fmt::Debug for Options {
             .field("compression", &self.compression)
             .field("pool_min", &self.pool_min)
             .field("pool_max", &self.pool_max)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_lifetime", &self.max_lifetime)
+            .field("health_check_interval", &self.health_check_interval)
+            .field("checkout_timeout", &self.checkout_timeout)
+            .field("max_waiters", &self.max_waiters)
             .field("nodelay", &self.nodelay)
             .field("keepalive", &self.keepalive)
             .field("ping_before_query", &self.ping_before_query)
@@ -315,6 +399,8 @@ impl fmt::Debug for Options {
             .field("connection_timeout", &self.connection_timeout)
             .field("settings", &self.settings)
             .field("alt_hosts", &self.alt_hosts)
+            .field("load_balancing", &self.load_balancing)
+            .field("host_quarantine", &self.host_quarantine)
             .finish()
     }
 }
@@ -326,9 +412,14 @@ impl Default for Options {
             database: "default".into(),
             username: "default".into(),
             password: "".into(),
-            compression: false,
+            compression: CompressionMethod::None,
             pool_min: DEFAULT_MIN_CONNS,
             pool_max: DEFAULT_MAX_CONNS,
+            idle_timeout: None,
+            max_lifetime: None,
+            health_check_interval: None,
+            checkout_timeout: None,
+            max_waiters: None,
             nodelay: true,
             keepalive: None,
             ping_before_query: true,
@@ -347,6 +438,8 @@ impl Default for Options {
             certificate: None,
             settings: HashMap::new(),
             alt_hosts: Vec::new(),
+            load_balancing: LoadBalancing::RoundRobin,
+            host_quarantine: None,
         }
     }
 }
@@ -413,14 +506,19 @@ impl Options {
         => password: &str
     }
 
-    /// Enable compression (defaults to `false`).
+    /// Enable LZ4 compression (defaults to `none`).
     pub fn with_compression(self) -> Self {
         Self {
-            compression: true,
+            compression: CompressionMethod::Lz4,
             ..self
         }
     }
 
+    property! {
+        /// Compression method (defaults to `none`).
+        => compression: CompressionMethod
+    }
+
     property! {
         /// Lower bound of opened connections for `Pool` (defaults to `10`).
         => pool_min: usize
@@ -431,6 +529,38 @@ impl Options {
         => pool_max: usize
     }
 
+    property! {
+        /// Idle connections of `Pool` are closed after this time (defaults to `None`).
+        => idle_timeout: Option<Duration>
+    }
+
+    property! {
+        /// Connections of `Pool` are closed after this time since opening (defaults to `None`).
+        => max_lifetime: Option<Duration>
+    }
+
+    property! {
+        /// Interval of pinging idle connections of `Pool` in background (defaults to `None`).
+        ///
+        /// Broken and expired connections are closed and replaced up to `pool_min`.
+        => health_check_interval: Option<Duration>
+    }
+
+    property! {
+        /// Timeout for getting a connection from `Pool` (defaults to `None`).
+        ///
+        /// `get_handle` fails with `ConnectionError::CheckoutTimeout` once it's elapsed.
+        => checkout_timeout: Option<Duration>
+    }
+
+    property! {
+        /// Upper bound of tasks waiting for a connection from `Pool` (defaults to `None`).
+        ///
+        /// `get_handle` fails with `ConnectionError::TooManyWaiters` instead of waiting
+        /// if there are already so many waiting tasks.
+        => max_waiters: Option<usize>
+    }
+
     property! {
         /// Whether to enable `TCP_NODELAY` (defaults to `true`).
         => nodelay: bool
@@ -508,6 +638,19 @@ impl Options {
         /// Comma separated list of single address host for load-balancing.
         => alt_hosts: Vec<Url>
     }
+
+    property! {
+        /// Strategy of picking a host for a new connection (defaults to `RoundRobin`).
+        => load_balancing: LoadBalancing
+    }
+
+    property! {
+        /// Time a host is skipped for after a failed connect (defaults to `None`).
+        ///
+        /// It doubles with each consecutive failure up to 32 times the value and is reset
+        /// by a successful connect. Quarantined hosts are still used if all of them are.
+        => host_quarantine: Option<Duration>
+    }
 }
 
 impl FromStr for Options {
@@ -567,6 +710,15 @@ where
         match key.as_ref() {
             "pool_min" => options.pool_min = parse_param(key, value, usize::from_str)?,
             "pool_max" => options.pool_max = parse_param(key, value, usize::from_str)?,
+            "idle_timeout" => options.idle_timeout = parse_param(key, value, parse_opt_duration)?,
+            "max_lifetime" => options.max_lifetime = parse_param(key, value, parse_opt_duration)?,
+            "health_check_interval" => {
+                options.health_check_interval = parse_param(key, value, parse_opt_duration)?
+            }
+            "checkout_timeout" => {
+                options.checkout_timeout = parse_param(key, value, parse_opt_duration)?
+            }
+            "max_waiters" => options.max_waiters = parse_param(key, value, parse_opt_usize)?,
             "nodelay" => options.nodelay = parse_param(key, value, bool::from_str)?,
             "keepalive" => options.keepalive = parse_param(key, value, parse_opt_duration)?,
             "ping_before_query" => {
@@ -591,6 +743,12 @@ where
             #[cfg(feature = "_tls")]
             "skip_verify" => options.skip_verify = parse_param(key, value, bool::from_str)?,
             "alt_hosts" => options.alt_hosts = parse_param(key, value, parse_hosts)?,
+            "load_balancing" => {
+                options.load_balancing = parse_param(key, value, parse_load_balancing)?
+            }
+            "host_quarantine" => {
+                options.host_quarantine = parse_param(key, value, parse_opt_duration)?
+            }
             _ => {
                 let value = SettingType::String(value.to_string());
                 options.settings.insert(
@@ -683,10 +841,31 @@ fn parse_opt_duration(source: &str) -> std::result::Result<Option<Duration>, ()>
     Ok(Some(duration))
 }
 
-fn parse_compression(source: &str) -> std::result::Result<bool, ()> {
-    match source {
-        "none" => Ok(false),
-        "lz4" => Ok(true),
+fn parse_opt_usize(source: &str) -> std::result::Result<Option<usize>, ()> {
+    if source == "none" {
+        return Ok(None);
+    }
+
+    let value = usize::from_str(source).map_err(|_| ())?;
+    Ok(Some(value))
+}
+
+fn parse_compression(source: &str) -> std::result::Result<CompressionMethod, ()> {
+    let (method, level) = match source.strip_suffix(')').and_then(|s| s.split_once('(')) {
+        Some((method, level)) => match i32::from_str(level) {
+            Ok(level) => (method, Some(level)),
+            Err(_) => return Err(()),
+        },
+        None => (source, None),
+    };
+
+    match (method, level) {
+        ("none", None) => Ok(CompressionMethod::None),
+        ("lz4", None) => Ok(CompressionMethod::Lz4),
+        ("lz4hc", level) => Ok(CompressionMethod::Lz4Hc(
+            level.unwrap_or(DEFAULT_LZ4HC_LEVEL),
+        )),
+        ("zstd", level) => Ok(CompressionMethod::Zstd(level.unwrap_or(DEFAULT_ZSTD_LEVEL))),
         _ => Err(()),
     }
 }
@@ -702,6 +881,16 @@ fn parse_hosts(source: &str) -> std::result::Result<Vec<Url>, ()> {
     Ok(result)
 }
 
+fn parse_load_balancing(source: &str) -> std::result::Result<LoadBalancing, ()> {
+    match source {
+        "round_robin" => Ok(LoadBalancing::RoundRobin),
+        "random" => Ok(LoadBalancing::Random),
+        "in_order" => Ok(LoadBalancing::InOrder),
+        "least_connections" => Ok(LoadBalancing::LeastConnections),
+        _ => Err(()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -739,7 +928,7 @@ mod test {
                 keepalive: Some(Duration::from_secs(99)),
                 ping_timeout: Duration::from_millis(42),
                 connection_timeout: Duration::from_secs(10),
-                compression: true,
+                compression: CompressionMethod::Lz4,
                 secure: true,
                 skip_verify: true,
                 ..Options::default()
@@ -760,7 +949,7 @@ mod test {
                 keepalive: Some(Duration::from_secs(99)),
                 ping_timeout: Duration::from_millis(42),
                 connection_timeout: Duration::from_secs(10),
-                compression: true,
+                compression: CompressionMethod::Lz4,
                 ..Options::default()
             },
             from_url(url).unwrap(),
@@ -779,13 +968,46 @@ mod test {
                 keepalive: Some(Duration::from_secs(99)),
                 ping_timeout: Duration::from_millis(42),
                 connection_timeout: Duration::from_secs(10),
-                compression: true,
+                compression: CompressionMethod::Lz4,
                 ..Options::default()
             },
             from_url(url).unwrap(),
         );
     }
 
+    #[test]
+    fn test_parse_pool_options() {
+        let url = "tcp://localhost:9000?idle_timeout=30s&max_lifetime=3600s&health_check_interval=5s";
+        let options = from_url(url).unwrap();
+        assert_eq!(options.idle_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(options.max_lifetime, Some(Duration::from_secs(3600)));
+        assert_eq!(options.health_check_interval, Some(Duration::from_secs(5)));
+
+        let options = from_url("tcp://localhost:9000?idle_timeout=none").unwrap();
+        assert_eq!(options.idle_timeout, None);
+
+        let url = "tcp://localhost:9000?checkout_timeout=250ms&max_waiters=100";
+        let options = from_url(url).unwrap();
+        assert_eq!(options.checkout_timeout, Some(Duration::from_millis(250)));
+        assert_eq!(options.max_waiters, Some(100));
+
+        let options = from_url("tcp://localhost:9000?max_waiters=none").unwrap();
+        assert_eq!(options.max_waiters, None);
+        from_url("tcp://localhost:9000?max_waiters=many").unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_load_balancing() {
+        let url = "tcp://host1:9000?alt_hosts=host2:9000&load_balancing=least_connections&host_quarantine=1s";
+        let options = from_url(url).unwrap();
+        assert_eq!(options.load_balancing, LoadBalancing::LeastConnections);
+        assert_eq!(options.host_quarantine, Some(Duration::from_secs(1)));
+
+        let options = from_url("tcp://host1:9000?load_balancing=in_order").unwrap();
+        assert_eq!(options.load_balancing, LoadBalancing::InOrder);
+        from_url("tcp://host1:9000?load_balancing=fastest").unwrap_err();
+    }
+
     #[test]
     #[should_panic]
     fn test_parse_invalid_url() {
@@ -924,8 +1146,41 @@ mod test {
 
     #[test]
     fn test_parse_compression() {
-        assert!(!parse_compression("none").unwrap());
-        assert!(parse_compression("lz4").unwrap());
+        assert_eq!(parse_compression("none").unwrap(), CompressionMethod::None);
+        assert_eq!(parse_compression("lz4").unwrap(), CompressionMethod::Lz4);
+        assert_eq!(
+            parse_compression("lz4hc").unwrap(),
+            CompressionMethod::Lz4Hc(DEFAULT_LZ4HC_LEVEL)
+        );
+        assert_eq!(
+            parse_compression("lz4hc(12)").unwrap(),
+            CompressionMethod::Lz4Hc(12)
+        );
+        assert_eq!(
+            parse_compression("zstd").unwrap(),
+            CompressionMethod::Zstd(DEFAULT_ZSTD_LEVEL)
+        );
+        assert_eq!(
+            parse_compression("zstd(3)").unwrap(),
+            CompressionMethod::Zstd(3)
+        );
         parse_compression("?").unwrap_err();
+        parse_compression("lz4(1)").unwrap_err();
+        parse_compression("zstd(x)").unwrap_err();
+    }
+
+    #[test]
+    fn test_compression_settings() {
+        assert!(CompressionMethod::Lz4.settings().is_empty());
+
+        let settings = CompressionMethod::Zstd(3).settings();
+        assert_eq!(
+            settings["network_compression_method"].value,
+            SettingType::String("ZSTD".into())
+        );
+        assert_eq!(
+            settings["network_zstd_compression_level"].value,
+            SettingType::UInt64(3)
+        );
     }
 }
@@ -1,7 +1,23 @@
+use std::collections::HashMap;
+
+use either::Either;
+
+use crate::{
+    errors::{Error, Result},
+    types::{
+        column::geo::nested_value,
+        value::{decode_ipv4, decode_ipv6},
+        Block, SettingType, SettingValue, Value,
+    },
+};
+
 #[derive(Clone, Debug)]
 pub struct Query {
     sql: String,
     id: String,
+    params: Vec<(String, Value)>,
+    settings: HashMap<String, SettingValue>,
+    external_tables: Vec<(String, Block)>,
 }
 
 impl Query {
@@ -9,6 +25,9 @@ impl Query {
         Self {
             sql: sql.as_ref().to_string(),
             id: "".to_string(),
+            params: Vec::new(),
+            settings: HashMap::new(),
+            external_tables: Vec::new(),
         }
     }
 
@@ -19,6 +38,71 @@ impl Query {
         }
     }
 
+    /// Binds a value to the `{name:Type}` placeholder of the query.
+    /// Parameters are sent to the server separately from the query text.
+    ///
+    /// ```rust
+    /// # use clickhouse_rs::types::Query;
+    /// let query = Query::new("SELECT {id:UInt32} AS id, {name:String} AS name")
+    ///     .bind("id", 42_u32)
+    ///     .bind("name", "foo");
+    /// ```
+    pub fn bind<V>(mut self, name: impl AsRef<str>, value: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        let name = name.as_ref();
+        let value = value.into();
+        match self.params.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value,
+            None => self.params.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    /// Sets a setting for this query only, it overrides the connection-wide
+    /// setting with the same name.
+    ///
+    /// ```rust
+    /// # use clickhouse_rs::types::Query;
+    /// let query = Query::new("SELECT number FROM system.numbers LIMIT 10")
+    ///     .with_setting("max_threads", 1, false)
+    ///     .with_setting("max_execution_time", 5, true);
+    /// ```
+    pub fn with_setting<V>(mut self, name: &str, value: V, is_important: bool) -> Self
+    where
+        V: Into<SettingType>,
+    {
+        let value: SettingType = value.into();
+        self.settings.insert(
+            name.into(),
+            SettingValue {
+                value,
+                is_important,
+            },
+        );
+        self
+    }
+
+    /// Attaches a block of client-side data that is available in the query
+    /// as a temporary table with the given name. The query fails without
+    /// being sent if the name is empty or the block has no columns.
+    ///
+    /// ```rust
+    /// # use clickhouse_rs::{types::Query, Block};
+    /// let ids = Block::new().column("id", vec![1_u64, 5, 7]);
+    /// let query = Query::new("SELECT * FROM events WHERE id IN ids")
+    ///     .external_table("ids", ids);
+    /// ```
+    pub fn external_table(mut self, name: impl AsRef<str>, block: Block) -> Self {
+        let name = name.as_ref();
+        match self.external_tables.iter_mut().find(|(n, _)| n == name) {
+            Some((_, b)) => *b = block,
+            None => self.external_tables.push((name.to_string(), block)),
+        }
+        self
+    }
+
     pub(crate) fn get_sql(&self) -> &str {
         &self.sql
     }
@@ -27,6 +111,33 @@ impl Query {
         &self.id
     }
 
+    pub(crate) fn get_params(&self) -> &[(String, Value)] {
+        &self.params
+    }
+
+    pub(crate) fn get_settings(&self) -> &HashMap<String, SettingValue> {
+        &self.settings
+    }
+
+    pub(crate) fn get_external_tables(&self) -> &[(String, Block)] {
+        &self.external_tables
+    }
+
+    /// A data packet with an empty name or without columns ends the data of the query,
+    /// so such external tables can't be sent.
+    pub(crate) fn check_external_tables(&self) -> Result<()> {
+        for (name, block) in &self.external_tables {
+            if name.is_empty() {
+                return Err(Error::Other("External table name is empty.".into()));
+            }
+            if block.column_count() == 0 {
+                let message = format!("External table `{name}` has no columns.");
+                return Err(Error::Other(message.into()));
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn map_sql<F>(self, f: F) -> Self
     where
         F: Fn(&str) -> String,
@@ -46,3 +157,205 @@ where
         Self::new(source)
     }
 }
+
+/// Formats a parameter value the way the server parses it for `{name:Type}`.
+pub(crate) fn param_to_string(value: &Value) -> String {
+    let mut result = String::new();
+    write_param(&mut result, value, false);
+    result
+}
+
+fn write_param(out: &mut String, value: &Value, nested: bool) {
+    match value {
+        Value::Bool(v) => out.push_str(&v.to_string()),
+        Value::UInt8(v) => out.push_str(&v.to_string()),
+        Value::UInt16(v) => out.push_str(&v.to_string()),
+        Value::UInt32(v) => out.push_str(&v.to_string()),
+        Value::UInt64(v) => out.push_str(&v.to_string()),
+        Value::UInt128(v) => out.push_str(&v.to_string()),
+        Value::UInt256(v) => out.push_str(&v.to_string()),
+        Value::Int8(v) => out.push_str(&v.to_string()),
+        Value::Int16(v) => out.push_str(&v.to_string()),
+        Value::Int32(v) => out.push_str(&v.to_string()),
+        Value::Int64(v) => out.push_str(&v.to_string()),
+        Value::Int128(v) => out.push_str(&v.to_string()),
+        Value::Int256(v) => out.push_str(&v.to_string()),
+        Value::Float32(v) => out.push_str(&v.to_string()),
+        Value::Float64(v) => out.push_str(&v.to_string()),
+        Value::Decimal(v) => out.push_str(&v.to_string()),
+        Value::String(v) => write_str(out, &String::from_utf8_lossy(v), nested),
+        Value::Date(_) | Value::Date32(_) => write_str(out, &value.to_string(), nested),
+        Value::DateTime(v, _) => out.push_str(&v.to_string()),
+        Value::DateTime64(v, (precision, _)) => out.push_str(&decimal_to_string(*v, *precision)),
+        Value::ChronoDateTime(v) => out.push_str(&v.timestamp().to_string()),
+        Value::Ipv4(v) => write_str(out, &decode_ipv4(v).to_string(), nested),
+        Value::Ipv6(v) => write_str(out, &decode_ipv6(v).to_string(), nested),
+        Value::Uuid(_) => write_str(out, &value.to_string(), nested),
+        Value::Enum8(items, v) => match items.iter().find(|(_, i)| *i == v.internal()) {
+            Some((name, _)) => write_str(out, name, nested),
+            None => out.push_str(&v.internal().to_string()),
+        },
+        Value::Enum16(items, v) => match items.iter().find(|(_, i)| *i == v.internal()) {
+            Some((name, _)) => write_str(out, name, nested),
+            None => out.push_str(&v.internal().to_string()),
+        },
+        Value::Nullable(Either::Left(_)) if nested => out.push_str("NULL"),
+        Value::Nullable(Either::Left(_)) => out.push_str("\\N"),
+        Value::Nullable(Either::Right(v)) => write_param(out, v, nested),
+        Value::Json(v) => write_str(out, v, nested),
+        Value::Variant(_, None) if nested => out.push_str("NULL"),
+        Value::Variant(_, None) => out.push_str("\\N"),
+        Value::Variant(_, Some(v)) => write_param(out, v, nested),
+        Value::Array(_, vs) => {
+            out.push('[');
+            write_list(out, vs.iter());
+            out.push(']');
+        }
+        Value::Tuple(vs) => {
+            out.push('(');
+            write_list(out, vs.iter());
+            out.push(')');
+        }
+        Value::Point(_) | Value::Ring(_) | Value::Polygon(_) | Value::MultiPolygon(_) => {
+            write_param(out, &nested_value(value.clone()), nested)
+        }
+        Value::Map(_, _, hm) => {
+            out.push('{');
+            for (i, (k, v)) in hm.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_param(out, k, true);
+                out.push(':');
+                write_param(out, v, true);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_list<'a>(out: &mut String, values: impl Iterator<Item = &'a Value>) {
+    for (i, v) in values.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_param(out, v, true);
+    }
+}
+
+/// Top level values are in escaped format, nested ones are quoted literals.
+fn write_str(out: &mut String, s: &str, nested: bool) {
+    if nested {
+        out.push('\'');
+    }
+    for ch in s.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\'' if nested => out.push_str("\\'"),
+            _ => out.push(ch),
+        }
+    }
+    if nested {
+        out.push('\'');
+    }
+}
+
+fn decimal_to_string(value: i64, scale: u32) -> String {
+    if scale == 0 {
+        return value.to_string();
+    }
+
+    let base = 10_u64.pow(scale);
+    let sign = if value < 0 { "-" } else { "" };
+    let abs = value.unsigned_abs();
+    format!(
+        "{sign}{}.{:0width$}",
+        abs / base,
+        abs % base,
+        width = scale as usize
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::SqlType;
+    use chrono::prelude::*;
+    use chrono_tz::Tz;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bind() {
+        let query = Query::new("SELECT {a:UInt8}")
+            .bind("a", 1_u8)
+            .bind("b", "x")
+            .bind("a", 2_u8);
+
+        assert_eq!(query.get_params().len(), 2);
+        assert_eq!(query.get_params()[0], ("a".to_string(), Value::UInt8(2)));
+    }
+
+    #[test]
+    fn test_with_setting() {
+        let query = Query::new("SELECT 1")
+            .with_setting("max_threads", 1, false)
+            .with_setting("max_threads", 2, true);
+
+        assert_eq!(
+            query.get_settings().get("max_threads"),
+            Some(&SettingValue {
+                value: SettingType::UInt64(2),
+                is_important: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_external_tables() {
+        let ids = Block::new().column("id", vec![1_u64, 5, 7]);
+        let query = Query::new("SELECT * FROM ids").external_table("ids", ids.clone());
+        assert!(query.check_external_tables().is_ok());
+
+        let query = Query::new("SELECT 1").external_table("", ids);
+        assert!(query.check_external_tables().is_err());
+
+        let query = Query::new("SELECT 1").external_table("ids", Block::new());
+        assert_eq!(
+            query.check_external_tables().unwrap_err().to_string(),
+            "Other error: `External table `ids` has no columns.`"
+        );
+    }
+
+    #[test]
+    fn test_param_to_string() {
+        assert_eq!(param_to_string(&Value::from(42_i32)), "42");
+        assert_eq!(param_to_string(&Value::from("a'b\\c\td")), "a'b\\\\c\\td");
+        assert_eq!(
+            param_to_string(&Value::Array(
+                SqlType::String.into(),
+                Arc::new(vec![Value::from("a'b"), Value::from("c")])
+            )),
+            "['a\\'b','c']"
+        );
+        assert_eq!(
+            param_to_string(&Value::from(NaiveDate::from_ymd_opt(2020, 1, 2).unwrap())),
+            "2020-01-02"
+        );
+        assert_eq!(param_to_string(&Value::DateTime(1_000, Tz::UTC)), "1000");
+        assert_eq!(
+            param_to_string(&Value::DateTime64(-1_500, (3, Tz::UTC))),
+            "-1.500"
+        );
+        assert_eq!(param_to_string(&Value::from(None::<u8>)), "\\N");
+        assert_eq!(
+            param_to_string(&Value::Array(
+                SqlType::Nullable(SqlType::UInt8.into()).into(),
+                Arc::new(vec![Value::from(Some(1_u8)), Value::from(None::<u8>)])
+            )),
+            "[1,NULL]"
+        );
+        assert_eq!(param_to_string(&Value::from((1_u8, "x"))), "(1,'x')");
+    }
+}
@@ -0,0 +1,42 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::Waker,
+};
+
+use futures_util::task::AtomicWaker;
+
+/// Handle that cancels an in-flight query.
+///
+/// It can be obtained with [`QueryResult::cancel_handle`](crate::types::QueryResult::cancel_handle)
+/// and used from another task while the result is being read.
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl CancelHandle {
+    /// Requests the server to stop the query. The result stream drains the remaining
+    /// packets and finishes with an error, after that the connection can be reused.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        self.inner.waker.wake();
+    }
+
+    /// Returns `true` if `cancel` was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn register(&self, waker: &Waker) {
+        self.inner.waker.register(waker);
+    }
+}
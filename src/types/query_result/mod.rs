@@ -4,35 +4,121 @@ use futures_util::{
     TryStreamExt,
 };
 use log::info;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
 use std::{marker::PhantomData, sync::Arc};
 
 use crate::{
     errors::Result,
     try_opt,
     types::{
-        block::BlockRef, query_result::stream_blocks::BlockStream, Block, Cmd, Complex, Query, Row,
+        block::BlockRef,
+        query_result::{cancel_handle::CancelHandle, stream_blocks::BlockStream}, Block, Cmd, Complex, Query, Row,
         Rows, Simple,
     },
     with_timeout, ClientHandle,
 };
 
+pub(crate) mod cancel_handle;
 pub(crate) mod stream_blocks;
 
 /// Result of a query or statement execution.
 pub struct QueryResult<'a> {
     pub(crate) client: &'a mut ClientHandle,
     pub(crate) query: Query,
+    pub(crate) cancel: CancelHandle,
+}
+
+/// Rows of a query together with the `WITH TOTALS` row and extremes
+/// (`extremes = 1`) sent by the server.
+#[derive(Debug)]
+pub struct QueryData {
+    data: Block<Complex>,
+    totals: Option<Block<Complex>>,
+    extremes: Option<Block<Complex>>,
+}
+
+impl QueryData {
+    /// Returns a block that contains all rows.
+    pub fn data(&self) -> &Block<Complex> {
+        &self.data
+    }
+
+    /// Returns a block with the totals row if the query contains `WITH TOTALS`.
+    pub fn totals(&self) -> Option<&Block<Complex>> {
+        self.totals.as_ref()
+    }
+
+    /// Returns a block with minimum and maximum values if `extremes` setting is enabled.
+    pub fn extremes(&self) -> Option<&Block<Complex>> {
+        self.extremes.as_ref()
+    }
+
+    /// Consumes the result and returns a block that contains all rows.
+    pub fn into_data(self) -> Block<Complex> {
+        self.data
+    }
 }
 
 impl<'a> QueryResult<'a> {
+    /// Returns a handle that cancels the query from another task.
+    ///
+    /// ```rust,no_run
+    /// # use clickhouse_rs::{Pool, errors::Result};
+    /// # use futures_util::TryStreamExt;
+    /// # async fn example(pool: Pool) -> Result<()> {
+    /// let mut c = pool.get_handle().await?;
+    /// let result = c.query("SELECT number FROM system.numbers");
+    /// let cancel = result.cancel_handle();
+    ///
+    /// let mut stream = result.stream_blocks();
+    /// while let Some(block) = stream.try_next().await? {
+    ///     if block.row_count() > 0 {
+    ///         cancel.cancel();
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
     /// Fetch data from table. It returns a block that contains all rows.
+    ///
+    /// Totals and extremes are not included, use [`QueryResult::fetch_data`]
+    /// or [`ClientHandle::totals`] and [`ClientHandle::extremes`] to get them.
     pub async fn fetch_all(self) -> Result<Block<Complex>> {
+        Ok(self.fetch_data().await?.into_data())
+    }
+
+    /// Fetch data from table and deserialize every row into `T`.
+    ///
+    /// Columns are matched to the fields of `T` by name.
+    #[cfg(feature = "serde")]
+    pub async fn fetch_all_as<T: DeserializeOwned>(self) -> Result<Vec<T>> {
+        let block = self.fetch_all().await?;
+        block.rows().map(|row| row.deserialize()).collect()
+    }
+
+    /// Fetch data from table. Unlike [`QueryResult::fetch_all`] it also returns
+    /// the `WITH TOTALS` row and extremes as separate blocks.
+    pub async fn fetch_data(self) -> Result<QueryData> {
         let timeout = try_opt!(self.client.context.options.get()).query_timeout;
+        let QueryResult {
+            client,
+            query,
+            cancel,
+        } = self;
 
         with_timeout(
             async {
-                let stream = self
-                    .stream_blocks_(false);
+                let stream = QueryResult {
+                    client: &mut *client,
+                    query,
+                    cancel,
+                }
+                .stream_blocks_(false);
 
                 let blocks = stream
                     .try_fold(Vec::new(), |mut blocks, block| {
@@ -43,7 +129,11 @@ impl<'a> QueryResult<'a> {
                     })
                     .await?;
 
-                Ok(Block::concat(blocks.as_slice()))
+                Ok(QueryData {
+                    data: Block::concat(blocks.as_slice()),
+                    totals: client.totals.clone().map(|b| Block::concat(&[b])),
+                    extremes: client.extremes.clone().map(|b| Block::concat(&[b])),
+                })
             },
             timeout,
         )
@@ -52,6 +142,9 @@ impl<'a> QueryResult<'a> {
 
     /// Method that produces a stream of blocks containing rows
     ///
+    /// The `WITH TOTALS` row and extremes aren't yielded as blocks, they are available
+    /// via [`ClientHandle::totals`] and [`ClientHandle::extremes`] once the stream is finished.
+    ///
     /// example:
     ///
     /// ```rust
@@ -85,22 +178,36 @@ impl<'a> QueryResult<'a> {
 
     fn stream_blocks_(self, skip_first_block: bool) -> BoxStream<'a, Result<Block>> {
         let query = self.query.clone();
+        let cancel = self.cancel.clone();
 
         self.client
             .wrap_stream::<'a, _>(move |c: &'a mut ClientHandle| {
-                info!("[send query] {}", query.get_sql());
-                c.pool.detach();
+                Box::pin(async move {
+                    info!("[send query] {}", query.get_sql());
+                    query.check_external_tables()?;
+                    c.pool.detach();
 
-                let context = c.context.clone();
+                    let context = c.context.clone();
 
-                let inner = c.get_inner()?.call(Cmd::SendQuery(query, context));
+                    let transport = c.get_inner()?.clear().await?;
+                    let inner = transport.call(Cmd::SendQuery(query, context));
 
-                Ok(BlockStream::<'a>::new(c, inner, skip_first_block))
+                    Ok(BlockStream::<'a>::new(c, inner, skip_first_block, cancel))
+                })
             })
     }
 
 
 
+    /// Method that produces a stream of rows deserialized into `T`
+    #[cfg(feature = "serde")]
+    pub fn stream_as<T: DeserializeOwned + Send + 'a>(self) -> BoxStream<'a, Result<T>> {
+        Box::pin(
+            self.stream()
+                .and_then(|row| future::ready(row.deserialize())),
+        )
+    }
+
     /// Method that produces a stream of rows
     pub fn stream(self) -> BoxStream<'a, Result<Row<'static, Simple>>> {
         Box::pin(
@@ -10,11 +10,10 @@ use futures_util::StreamExt;
 
 use crate::{
     errors::{DriverError, Error, Result},
-    io::transport::PacketStream,
-    types::{Block, Packet},
+    io::{transport::PacketStream, ClickhouseTransport},
+    types::{query_result::cancel_handle::CancelHandle, Block, Packet},
     ClientHandle,
 };
-use crate::types::ProfileInfo;
 
 pub(crate) struct BlockStream<'a> {
     client: &'a mut ClientHandle,
@@ -22,12 +21,15 @@ pub(crate) struct BlockStream<'a> {
     state: BlockStreamState,
     block_index: usize,
     skip_first_block: bool,
+    cancel: CancelHandle,
 }
 
 #[derive(Clone, Copy)]
 pub(crate) enum BlockStreamState {
     /// Currently reading from block packet stream; some further packets may be pending
     Reading,
+    /// Query was cancelled; the rest of packets are being drained
+    Cancelling,
     /// Completely finished reading from block packet stream; connection is now idle
     Finished,
     /// There was an error reading packet; transport is broken
@@ -37,7 +39,7 @@ pub(crate) enum BlockStreamState {
 impl<'a> Drop for BlockStream<'a> {
     fn drop(&mut self) {
         match self.state {
-            BlockStreamState::Reading => {
+            BlockStreamState::Reading | BlockStreamState::Cancelling => {
                 if !self.client.pool.is_attached() {
                     self.client.pool.attach();
                 }
@@ -60,14 +62,40 @@ impl<'a> BlockStream<'a> {
         client: &mut ClientHandle,
         inner: PacketStream,
         skip_first_block: bool,
+        cancel: CancelHandle,
     ) -> BlockStream {
+        client.totals = None;
+        client.extremes = None;
+
         BlockStream {
             client,
             inner,
             state: BlockStreamState::Reading,
             block_index: 0,
             skip_first_block,
+            cancel,
+        }
+    }
+
+    fn restore_transport(&mut self, transport: ClickhouseTransport) {
+        self.client.inner = Some(transport);
+        if !self.client.pool.is_attached() {
+            self.client.pool.attach();
+        }
+    }
+
+    fn poll_cancelling(&mut self, packet: Packet<ClickhouseTransport>) -> Option<Result<Block>> {
+        match packet {
+            Packet::Eof(inner) => self.restore_transport(inner),
+            Packet::Exception(_) => match self.inner.take_transport() {
+                Some(inner) => self.restore_transport(inner),
+                None => return None,
+            },
+            _ => return None,
         }
+
+        self.state = BlockStreamState::Finished;
+        Some(Err(Error::Driver(DriverError::QueryCancelled)))
     }
 }
 
@@ -77,7 +105,14 @@ impl<'a> Stream for BlockStream<'a> {
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
             match self.state {
-                BlockStreamState::Reading => {}
+                BlockStreamState::Reading => {
+                    self.cancel.register(cx.waker());
+                    if self.cancel.is_cancelled() {
+                        self.inner.cancel();
+                        self.state = BlockStreamState::Cancelling;
+                    }
+                }
+                BlockStreamState::Cancelling => {}
                 BlockStreamState::Finished => return Poll::Ready(None),
                 BlockStreamState::Error => {
                     return Poll::Ready(Some(Err(Error::Other(Cow::Borrowed(
@@ -98,19 +133,27 @@ impl<'a> Stream for BlockStream<'a> {
                 Poll::Ready(Some(Ok(packet))) => packet,
             };
 
+            if let BlockStreamState::Cancelling = self.state {
+                if let Some(ret) = self.poll_cancelling(packet) {
+                    return Poll::Ready(Some(ret));
+                }
+                continue;
+            }
+
             match packet {
                 Packet::Eof(inner) => {
-                    self.client.inner = Some(inner);
-                    if !self.client.pool.is_attached() {
-                        self.client.pool.attach();
-                    }
+                    self.restore_transport(inner);
                     self.state = BlockStreamState::Finished;
                 }
                 Packet::ProfileInfo(profile_info) =>{
                     self.client.set_profile_info(profile_info);
                     //return Poll::Ready(Some(Ok(BlockStreamItem::ProfileInfo(profile_info))));
                 },
-                Packet::Progress(_) => {}
+                Packet::Progress(progress) => self.client.callbacks.on_progress(progress),
+                Packet::ServerLog(records) => self.client.callbacks.on_server_log(records),
+                Packet::ProfileEvents(_) => {}
+                Packet::Totals(block) => self.client.totals = Some(block),
+                Packet::Extremes(block) => self.client.extremes = Some(block),
                 Packet::Exception(exception) => {
                     self.state = BlockStreamState::Finished;
                     return Poll::Ready(Some(Err(Error::Server(exception))));
@@ -0,0 +1,309 @@
+use std::{str, sync::Arc, vec};
+
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use either::Either;
+use serde::{
+    de::{
+        self, value::BorrowedStrDeserializer, DeserializeSeed, IntoDeserializer, MapAccess,
+        SeqAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use crate::{
+    errors::Error,
+    types::{column::geo::nested_value_ref, ColumnType, Row, ValueRef},
+};
+
+/// Deserializes a row as a map from column names to values,
+/// or as a sequence of values when the target is a tuple.
+pub(crate) struct RowDeserializer<'a, K: ColumnType> {
+    row: &'a Row<'a, K>,
+}
+
+impl<'a, K: ColumnType> RowDeserializer<'a, K> {
+    pub(crate) fn new(row: &'a Row<'a, K>) -> Self {
+        Self { row }
+    }
+}
+
+impl<'de, K: ColumnType> de::Deserializer<'de> for RowDeserializer<'de, K> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(RowAccess {
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(RowAccess {
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'a, K: ColumnType> {
+    row: &'a Row<'a, K>,
+    index: usize,
+}
+
+impl<'de, K: ColumnType> MapAccess<'de> for RowAccess<'de, K> {
+    type Error = Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        if self.index >= self.row.len() {
+            return Ok(None);
+        }
+        let name = self.row.name(self.index)?;
+        seed.deserialize(BorrowedStrDeserializer::new(name))
+            .map(Some)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Error> {
+        let value = self.row.value(self.index)?;
+        self.index += 1;
+        seed.deserialize(ValueDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.index)
+    }
+}
+
+impl<'de, K: ColumnType> SeqAccess<'de> for RowAccess<'de, K> {
+    type Error = Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        if self.index >= self.row.len() {
+            return Ok(None);
+        }
+        let value = self.row.value(self.index)?;
+        self.index += 1;
+        seed.deserialize(ValueDeserializer::new(value)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.index)
+    }
+}
+
+/// Deserializes a single cell.
+///
+/// Dates are represented as `YYYY-MM-DD` strings and date-times as RFC 3339 strings (the formats
+/// accepted by chrono's serde implementations); enums as the names of their values; UUIDs,
+/// IP addresses and 256-bit integers as their string form; decimals as `f64`. JSON is
+/// represented as its text, `Variant` and `Dynamic` values as the value they hold.
+pub(crate) struct ValueDeserializer<'a> {
+    value: ValueRef<'a>,
+}
+
+impl<'a> ValueDeserializer<'a> {
+    pub(crate) fn new(value: ValueRef<'a>) -> Self {
+        Self { value }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            ValueRef::Bool(v) => visitor.visit_bool(v),
+            ValueRef::UInt8(v) => visitor.visit_u8(v),
+            ValueRef::UInt16(v) => visitor.visit_u16(v),
+            ValueRef::UInt32(v) => visitor.visit_u32(v),
+            ValueRef::UInt64(v) => visitor.visit_u64(v),
+            ValueRef::UInt128(v) => visitor.visit_u128(v),
+            ValueRef::Int8(v) => visitor.visit_i8(v),
+            ValueRef::Int16(v) => visitor.visit_i16(v),
+            ValueRef::Int32(v) => visitor.visit_i32(v),
+            ValueRef::Int64(v) => visitor.visit_i64(v),
+            ValueRef::Int128(v) => visitor.visit_i128(v),
+            ValueRef::Float32(v) => visitor.visit_f32(v),
+            ValueRef::Float64(v) => visitor.visit_f64(v),
+            ValueRef::String(v) => match str::from_utf8(v) {
+                Ok(s) => visitor.visit_borrowed_str(s),
+                Err(_) => visitor.visit_borrowed_bytes(v),
+            },
+            ValueRef::Decimal(v) => visitor.visit_f64(v.into()),
+            v @ (ValueRef::Date(_) | ValueRef::Date32(_)) => {
+                visitor.visit_string(NaiveDate::from(v).to_string())
+            }
+            v @ (ValueRef::DateTime(_, _) | ValueRef::DateTime64(_, _)) => {
+                visitor.visit_string(DateTime::<Tz>::from(v).to_rfc3339())
+            }
+            ValueRef::Nullable(Either::Left(_)) => visitor.visit_none(),
+            ValueRef::Nullable(Either::Right(v)) => visitor.visit_some(ValueDeserializer::new(*v)),
+            ValueRef::Json(v) => visitor.visit_borrowed_str(v),
+            v @ (ValueRef::Enum8(_, _) | ValueRef::Enum16(_, _)) => match v.as_str() {
+                Ok(name) => visitor.visit_borrowed_str(name),
+                Err(_) => visitor.visit_string(v.to_string()),
+            },
+            ValueRef::Variant(_, None) => visitor.visit_none(),
+            ValueRef::Variant(_, Some(v)) => ValueDeserializer::new(*v).deserialize_any(visitor),
+            ValueRef::Array(_, vs) | ValueRef::Tuple(vs) => visitor.visit_seq(ValueSeq::new(vs)),
+            v @ (ValueRef::Point(_)
+            | ValueRef::Ring(_)
+            | ValueRef::Polygon(_)
+            | ValueRef::MultiPolygon(_)) => {
+                ValueDeserializer::new(nested_value_ref(v)).deserialize_any(visitor)
+            }
+            ValueRef::Map(_, _, map) => visitor.visit_map(ValueMap {
+                iter: map
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                value: None,
+            }),
+            v @ (ValueRef::UInt256(_)
+            | ValueRef::Int256(_)
+            | ValueRef::Ipv4(_)
+            | ValueRef::Ipv6(_)
+            | ValueRef::Uuid(_)) => visitor.visit_string(v.to_string()),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            ValueRef::Nullable(Either::Left(_)) => visitor.visit_none(),
+            ValueRef::Nullable(Either::Right(v)) => visitor.visit_some(ValueDeserializer::new(*v)),
+            ValueRef::Variant(_, None) => visitor.visit_none(),
+            ValueRef::Variant(_, Some(v)) => visitor.visit_some(ValueDeserializer::new(*v)),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let variant = match self.value {
+            ValueRef::String(v) => String::from_utf8_lossy(v).into_owned(),
+            v @ (ValueRef::Enum8(_, _) | ValueRef::Enum16(_, _)) => {
+                v.as_string().unwrap_or_else(|_| v.to_string())
+            }
+            v => v.to_string(),
+        };
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(variant))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct ValueSeq<'a> {
+    iter: vec::IntoIter<ValueRef<'a>>,
+}
+
+impl<'a> ValueSeq<'a> {
+    fn new(values: Arc<Vec<ValueRef<'a>>>) -> Self {
+        let values = Arc::try_unwrap(values).unwrap_or_else(|values| values.as_ref().clone());
+        Self {
+            iter: values.into_iter(),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for ValueSeq<'de> {
+    type Error = Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        match self.iter.next() {
+            None => Ok(None),
+            Some(value) => seed.deserialize(ValueDeserializer::new(value)).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct ValueMap<'a> {
+    iter: vec::IntoIter<(ValueRef<'a>, ValueRef<'a>)>,
+    value: Option<ValueRef<'a>>,
+}
+
+impl<'de> MapAccess<'de> for ValueMap<'de> {
+    type Error = Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Error> {
+        match self.iter.next() {
+            None => Ok(None),
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(ValueDeserializer::new(key)).map(Some)
+            }
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Error> {
+        match self.value.take() {
+            None => Err(de::Error::custom("value is missing")),
+            Some(value) => seed.deserialize(ValueDeserializer::new(value)),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
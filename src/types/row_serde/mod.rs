@@ -0,0 +1,149 @@
+//! Conversions between rows and serde models, enabled by the `serde` feature.
+
+pub(crate) use self::{de::RowDeserializer, ser::serialize_columns};
+
+mod de;
+mod ser;
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::types::{Block, SqlType};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Status {
+        Active,
+        Blocked,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: u32,
+        name: String,
+        price: Option<f64>,
+        tags: Vec<String>,
+        status: Status,
+        point: Point,
+        attrs: HashMap<String, u8>,
+    }
+
+    fn items() -> Vec<Item> {
+        vec![
+            Item {
+                id: 1,
+                name: "foo".into(),
+                price: Some(1.5),
+                tags: vec!["a".into(), "b".into()],
+                status: Status::Active,
+                point: Point { x: 1, y: -1 },
+                attrs: HashMap::from([("k".to_string(), 1)]),
+            },
+            Item {
+                id: 2,
+                name: "bar".into(),
+                price: None,
+                tags: vec![],
+                status: Status::Blocked,
+                point: Point { x: 0, y: 2 },
+                attrs: HashMap::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let block = Block::from_rows(&items()).unwrap();
+
+        assert_eq!(block.row_count(), 2);
+        let types: Vec<_> = block
+            .columns()
+            .iter()
+            .map(|c| (c.name().to_string(), c.sql_type()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("id".to_string(), SqlType::UInt32),
+                ("name".to_string(), SqlType::String),
+                (
+                    "price".to_string(),
+                    SqlType::Nullable(SqlType::Float64.into())
+                ),
+                ("tags".to_string(), SqlType::Array(SqlType::String.into())),
+                ("status".to_string(), SqlType::String),
+                (
+                    "point".to_string(),
+                    SqlType::Tuple(vec![SqlType::Int32.into(), SqlType::Int32.into()])
+                ),
+                (
+                    "attrs".to_string(),
+                    SqlType::Map(SqlType::String.into(), SqlType::UInt8.into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let block = Block::from_rows(&items()).unwrap();
+
+        let actual: Vec<Item> = block
+            .rows()
+            .map(|row| row.deserialize())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(actual, items());
+    }
+
+    #[test]
+    fn test_deserialize_tuple() {
+        let block = Block::new()
+            .column("id", vec![7_u64])
+            .column("name", vec!["baz"]);
+        let row = block.rows().next().unwrap();
+
+        let actual: (u64, String) = row.deserialize().unwrap();
+        assert_eq!(actual, (7, "baz".to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_missing_column() {
+        let block = Block::new().column("id", vec![1_u32]);
+        let row = block.rows().next().unwrap();
+
+        assert!(row.deserialize::<Item>().is_err());
+    }
+
+    #[test]
+    fn test_from_rows_different_fields() {
+        let rows = vec![BTreeMap::from([("a", 1_u8)]), BTreeMap::from([("b", 2_u8)])];
+        assert!(Block::from_rows(&rows).is_err());
+    }
+
+    #[test]
+    fn test_placeholder_column_cast() {
+        let rows = vec![
+            BTreeMap::from([("value", None::<u32>)]),
+            BTreeMap::from([("value", None)]),
+        ];
+        let block = Block::from_rows(&rows).unwrap();
+        let column = block.columns()[0].clone();
+        assert_eq!(column.sql_type(), SqlType::Nullable(SqlType::String.into()));
+
+        let column = column
+            .cast_to(SqlType::Nullable(SqlType::UInt32.into()))
+            .unwrap();
+        assert_eq!(column.sql_type(), SqlType::Nullable(SqlType::UInt32.into()));
+        assert_eq!(column.len(), 2);
+    }
+}
@@ -0,0 +1,673 @@
+use std::{collections::HashMap, sync::Arc};
+
+use either::Either;
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::{
+    errors::{Error, Result},
+    types::{
+        column::{datetime64::DEFAULT_TZ, ArcColumnData, ArcColumnWrapper, ColumnData},
+        SqlType, Value,
+    },
+};
+
+/// Serializes `rows` into named columns.
+///
+/// Column types are inferred from the values. When a type can't be inferred (a column contains
+/// only `None` or empty sequences) `String` is used in its place, such columns take the server
+/// type on insert.
+pub(crate) fn serialize_columns<T: Serialize>(rows: &[T]) -> Result<Vec<(String, ArcColumnData)>> {
+    let mut names: Vec<String> = Vec::new();
+    let mut cells: Vec<Vec<Cell>> = Vec::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let fields = row.serialize(RowSerializer::default())?;
+
+        if i == 0 {
+            names = fields.iter().map(|(name, _)| name.clone()).collect();
+            cells = names
+                .iter()
+                .map(|_| Vec::with_capacity(rows.len()))
+                .collect();
+        } else if fields.len() != names.len()
+            || fields
+                .iter()
+                .zip(&names)
+                .any(|((name, _), expected)| name != expected)
+        {
+            return Err(Error::Other(
+                format!("row {i} has different fields than the first row").into(),
+            ));
+        }
+
+        for (column, (_, cell)) in cells.iter_mut().zip(fields) {
+            column.push(cell);
+        }
+    }
+
+    let mut columns = Vec::with_capacity(names.len());
+    for (name, column) in names.into_iter().zip(cells) {
+        let mut hint = Hint::Unknown;
+        for cell in &column {
+            hint = merge(hint, cell).map_err(|err| {
+                Error::Other(format!("column `{name}` has values of different types: {err}").into())
+            })?;
+        }
+        let sql_type = hint.into_sql_type();
+
+        let mut data = <dyn ColumnData>::from_type::<ArcColumnWrapper>(
+            sql_type.clone(),
+            *DEFAULT_TZ,
+            column.len(),
+        )?;
+        let target = Arc::get_mut(&mut data).unwrap();
+        for cell in column {
+            target.push(cell.into_value(&sql_type));
+        }
+
+        columns.push((name, data));
+    }
+
+    Ok(columns)
+}
+
+/// A serialized value whose exact type may not be known yet.
+#[derive(Debug)]
+enum Cell {
+    Value(Value),
+    Null,
+    Some(Box<Cell>),
+    Array(Vec<Cell>),
+    Tuple(Vec<Cell>),
+    Map(Vec<(Cell, Cell)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Hint {
+    Unknown,
+    Scalar(SqlType),
+    Nullable(Box<Hint>),
+    Array(Box<Hint>),
+    Tuple(Vec<Hint>),
+    Map(Box<Hint>, Box<Hint>),
+}
+
+fn merge(hint: Hint, cell: &Cell) -> std::result::Result<Hint, String> {
+    Ok(match (hint, cell) {
+        (Hint::Unknown, Cell::Value(value)) => Hint::Scalar(SqlType::from(value.clone())),
+        (Hint::Scalar(sql_type), Cell::Value(value)) => {
+            let other = SqlType::from(value.clone());
+            if sql_type != other {
+                return Err(format!("{sql_type} and {other}"));
+            }
+            Hint::Scalar(sql_type)
+        }
+        (Hint::Unknown, Cell::Null) => Hint::Nullable(Box::new(Hint::Unknown)),
+        (hint @ Hint::Nullable(_), Cell::Null) => hint,
+        (Hint::Unknown, Cell::Some(inner)) => {
+            Hint::Nullable(Box::new(merge(Hint::Unknown, inner)?))
+        }
+        (Hint::Nullable(hint), Cell::Some(inner)) => Hint::Nullable(Box::new(merge(*hint, inner)?)),
+        (Hint::Unknown, Cell::Array(items)) => {
+            Hint::Array(Box::new(merge_all(Hint::Unknown, items)?))
+        }
+        (Hint::Array(hint), Cell::Array(items)) => Hint::Array(Box::new(merge_all(*hint, items)?)),
+        (Hint::Unknown, Cell::Tuple(items)) => {
+            let hints = vec![Hint::Unknown; items.len()];
+            Hint::Tuple(merge_tuple(hints, items)?)
+        }
+        (Hint::Tuple(hints), Cell::Tuple(items)) if hints.len() == items.len() => {
+            Hint::Tuple(merge_tuple(hints, items)?)
+        }
+        (Hint::Unknown, Cell::Map(pairs)) => merge_map(Hint::Unknown, Hint::Unknown, pairs)?,
+        (Hint::Map(key, value), Cell::Map(pairs)) => merge_map(*key, *value, pairs)?,
+        (hint, cell) => return Err(format!("{hint:?} and {cell:?}")),
+    })
+}
+
+fn merge_all(mut hint: Hint, cells: &[Cell]) -> std::result::Result<Hint, String> {
+    for cell in cells {
+        hint = merge(hint, cell)?;
+    }
+    Ok(hint)
+}
+
+fn merge_tuple(hints: Vec<Hint>, cells: &[Cell]) -> std::result::Result<Vec<Hint>, String> {
+    hints
+        .into_iter()
+        .zip(cells)
+        .map(|(hint, cell)| merge(hint, cell))
+        .collect()
+}
+
+fn merge_map(
+    mut key: Hint,
+    mut value: Hint,
+    pairs: &[(Cell, Cell)],
+) -> std::result::Result<Hint, String> {
+    for (k, v) in pairs {
+        key = merge(key, k)?;
+        value = merge(value, v)?;
+    }
+    Ok(Hint::Map(Box::new(key), Box::new(value)))
+}
+
+impl Hint {
+    fn into_sql_type(self) -> SqlType {
+        match self {
+            Hint::Unknown => SqlType::String,
+            Hint::Scalar(sql_type) => sql_type,
+            Hint::Nullable(inner) => SqlType::Nullable(inner.into_sql_type().into()),
+            Hint::Array(inner) => SqlType::Array(inner.into_sql_type().into()),
+            Hint::Tuple(items) => SqlType::Tuple(
+                items
+                    .into_iter()
+                    .map(|item| item.into_sql_type().into())
+                    .collect(),
+            ),
+            Hint::Map(key, value) => {
+                SqlType::Map(key.into_sql_type().into(), value.into_sql_type().into())
+            }
+        }
+    }
+}
+
+impl Cell {
+    fn into_value(self, sql_type: &SqlType) -> Value {
+        match (self, sql_type) {
+            (Cell::Value(value), _) => value,
+            (Cell::Null, SqlType::Nullable(inner)) => Value::Nullable(Either::Left(inner)),
+            (Cell::Some(cell), SqlType::Nullable(inner)) => {
+                Value::Nullable(Either::Right(Box::new(cell.into_value(inner))))
+            }
+            (Cell::Array(cells), SqlType::Array(inner)) => Value::Array(
+                inner,
+                Arc::new(cells.into_iter().map(|c| c.into_value(inner)).collect()),
+            ),
+            (Cell::Tuple(cells), SqlType::Tuple(types)) => Value::Tuple(Arc::new(
+                cells
+                    .into_iter()
+                    .zip(types)
+                    .map(|(c, t)| c.into_value(t))
+                    .collect(),
+            )),
+            (Cell::Map(pairs), SqlType::Map(k, v)) => Value::Map(
+                k,
+                v,
+                Arc::new(
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| (key.into_value(k), value.into_value(v)))
+                        .collect::<HashMap<_, _>>(),
+                ),
+            ),
+            (cell, sql_type) => unreachable!("{cell:?} doesn't match {sql_type}"),
+        }
+    }
+}
+
+fn unsupported(what: &str) -> Error {
+    Error::Other(format!("{what} can't be serialized into a column").into())
+}
+
+/// Serializes a struct or a map with string keys into named cells.
+#[derive(Default)]
+struct RowSerializer {
+    fields: Vec<(String, Cell)>,
+    key: Option<String>,
+}
+
+impl ser::Serializer for RowSerializer {
+    type Ok = Vec<(String, Cell)>;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type SerializeTuple = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported("a row"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported("a row"))
+    }
+}
+
+impl ser::SerializeStruct for RowSerializer {
+    type Ok = Vec<(String, Cell)>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.fields
+            .push((key.to_string(), value.serialize(CellSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+impl ser::SerializeMap for RowSerializer {
+    type Ok = Vec<(String, Cell)>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        match key.serialize(CellSerializer)? {
+            Cell::Value(Value::String(name)) => {
+                self.key = Some(String::from_utf8(name.to_vec())?);
+                Ok(())
+            }
+            _ => Err(unsupported("a non-string map key of a row")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| unsupported("a map value without key"))?;
+        self.fields.push((key, value.serialize(CellSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.fields)
+    }
+}
+
+/// Serializes a single field value.
+struct CellSerializer;
+
+macro_rules! serialize_value {
+    ( $( $f:ident: $t:ty => $k:ident ),* ) => {
+        $(
+            fn $f(self, v: $t) -> Result<Cell> {
+                Ok(Cell::Value(Value::$k(v)))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for CellSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Cell, Error>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = SeqSerializer;
+    type SerializeStructVariant = Impossible<Cell, Error>;
+
+    serialize_value! {
+        serialize_bool: bool => Bool,
+        serialize_i8: i8 => Int8,
+        serialize_i16: i16 => Int16,
+        serialize_i32: i32 => Int32,
+        serialize_i64: i64 => Int64,
+        serialize_i128: i128 => Int128,
+        serialize_u8: u8 => UInt8,
+        serialize_u16: u16 => UInt16,
+        serialize_u32: u32 => UInt32,
+        serialize_u64: u64 => UInt64,
+        serialize_u128: u128 => UInt128,
+        serialize_f32: f32 => Float32,
+        serialize_f64: f64 => Float64
+    }
+
+    fn serialize_char(self, v: char) -> Result<Cell> {
+        Ok(Cell::Value(Value::from(v.to_string())))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Cell> {
+        Ok(Cell::Value(Value::from(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Cell> {
+        Ok(Cell::Value(Value::String(Arc::new(v.to_vec()))))
+    }
+
+    fn serialize_none(self) -> Result<Cell> {
+        Ok(Cell::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Cell> {
+        Ok(Cell::Some(Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_unit(self) -> Result<Cell> {
+        Err(unsupported("`()`"))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Cell> {
+        Err(unsupported(name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Cell> {
+        Ok(Cell::Value(Value::from(variant)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Cell> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> Result<Cell> {
+        Err(unsupported(&format!("{name}::{variant}")))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(false, len.unwrap_or_default()))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(true, len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(true, len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported(&format!("{name}::{variant}")))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer {
+            pairs: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(true, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported(&format!("{name}::{variant}")))
+    }
+}
+
+/// Collects sequences into arrays, and tuples and nested structs into tuples.
+struct SeqSerializer {
+    tuple: bool,
+    items: Vec<Cell>,
+}
+
+impl SeqSerializer {
+    fn new(tuple: bool, len: usize) -> Self {
+        Self {
+            tuple,
+            items: Vec::with_capacity(len),
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.items.push(value.serialize(CellSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Cell> {
+        if self.tuple {
+            Ok(Cell::Tuple(self.items))
+        } else {
+            Ok(Cell::Array(self.items))
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Cell> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Cell> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Cell> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SeqSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Cell> {
+        self.finish()
+    }
+}
+
+struct MapSerializer {
+    pairs: Vec<(Cell, Cell)>,
+    key: Option<Cell>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Cell;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(CellSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| unsupported("a map value without key"))?;
+        self.pairs.push((key, value.serialize(CellSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Cell> {
+        Ok(Cell::Map(self.pairs))
+    }
+}
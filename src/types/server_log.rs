@@ -0,0 +1,85 @@
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use log::Level;
+
+use crate::{errors::Result, types::Block};
+
+/// A log record sent by the server when `send_logs_level` setting is enabled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    pub event_time: DateTime<Tz>,
+    pub event_time_microseconds: u32,
+    pub host_name: String,
+    pub query_id: String,
+    pub thread_id: u64,
+    /// Server message priority: 1 (fatal) .. 8 (trace).
+    pub priority: i8,
+    pub source: String,
+    pub text: String,
+}
+
+impl LogRecord {
+    pub(crate) fn from_block(block: &Block) -> Result<Vec<Self>> {
+        let mut records = Vec::with_capacity(block.row_count());
+        for row in 0..block.row_count() {
+            records.push(Self {
+                event_time: block.get(row, "event_time")?,
+                event_time_microseconds: block.get(row, "event_time_microseconds")?,
+                host_name: block.get(row, "host_name")?,
+                query_id: block.get(row, "query_id")?,
+                thread_id: block.get(row, "thread_id")?,
+                priority: block.get(row, "priority")?,
+                source: block.get(row, "source")?,
+                text: block.get(row, "text")?,
+            });
+        }
+        Ok(records)
+    }
+
+    /// Maps the server priority to the `log` crate level.
+    pub fn level(&self) -> Level {
+        match self.priority {
+            i8::MIN..=3 => Level::Error,
+            4 => Level::Warn,
+            5 | 6 => Level::Info,
+            7 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_block() {
+        let time = Tz::UTC.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let block = Block::new()
+            .column("event_time", vec![time])
+            .column("event_time_microseconds", vec![42_u32])
+            .column("host_name", vec!["host"])
+            .column("query_id", vec!["id"])
+            .column("thread_id", vec![7_u64])
+            .column("priority", vec![4_i8])
+            .column("source", vec!["executeQuery"])
+            .column("text", vec!["Read 1 rows"]);
+
+        let records = LogRecord::from_block(&block).unwrap();
+
+        assert_eq!(
+            records,
+            vec![LogRecord {
+                event_time: time,
+                event_time_microseconds: 42,
+                host_name: "host".into(),
+                query_id: "id".into(),
+                thread_id: 7,
+                priority: 4,
+                source: "executeQuery".into(),
+                text: "Read 1 rows".into(),
+            }]
+        );
+        assert_eq!(records[0].level(), Level::Warn);
+    }
+}
@@ -1,3 +1,5 @@
+use ethnum::{I256, U256};
+
 use crate::types::SqlType;
 
 pub trait StatBuffer {
@@ -66,6 +68,18 @@ impl StatBuffer for u128 {
     }
 }
 
+impl StatBuffer for U256 {
+    type Buffer = [u8; 32];
+
+    fn buffer() -> Self::Buffer {
+        [0; 32]
+    }
+
+    fn sql_type() -> SqlType {
+        SqlType::UInt256
+    }
+}
+
 impl StatBuffer for i8 {
     type Buffer = [u8; 1];
 
@@ -126,6 +140,18 @@ impl StatBuffer for i128 {
     }
 }
 
+impl StatBuffer for I256 {
+    type Buffer = [u8; 32];
+
+    fn buffer() -> Self::Buffer {
+        [0; 32]
+    }
+
+    fn sql_type() -> SqlType {
+        SqlType::Int256
+    }
+}
+
 impl StatBuffer for f32 {
     type Buffer = [u8; 4];
 
@@ -1,3 +1,5 @@
+use ethnum::{I256, U256};
+
 pub trait Unmarshal<T: Copy> {
     fn unmarshal(scratch: &[u8]) -> T;
 }
@@ -31,7 +33,7 @@ macro_rules! float_unmarshals {
     };
 }
 
-int_unmarshals! { u8, u16, u32, u64, u128, i8, i16, i32, i64, i128 }
+int_unmarshals! { u8, u16, u32, u64, u128, U256, i8, i16, i32, i64, i128, I256 }
 float_unmarshals! { f32: u32, f64: u64 }
 
 impl Unmarshal<bool> for bool {
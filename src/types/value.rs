@@ -11,12 +11,15 @@ use std::{
 use chrono::{prelude::*, Duration};
 use chrono_tz::Tz;
 use either::Either;
+use ethnum::{I256, U256};
 use uuid::Uuid;
 
 use crate::types::{
     column::datetime64::{to_datetime, DEFAULT_TZ},
+    date_converter::{date_from_days, days_since_epoch, fits_date},
     decimal::{Decimal, NoBits},
-    DateConverter, DateTimeType, Enum16, Enum8, HasSqlType, SqlType,
+    DateConverter, DateTimeType, Enum16, Enum8, HasSqlType, MultiPolygon, Point, Polygon, Ring,
+    SqlType,
 };
 
 pub(crate) type AppDateTime = DateTime<Tz>;
@@ -31,15 +34,18 @@ pub enum Value {
     UInt32(u32),
     UInt64(u64),
     UInt128(u128),
+    UInt256(U256),
     Int8(i8),
     Int16(i16),
     Int32(i32),
     Int64(i64),
     Int128(i128),
+    Int256(I256),
     String(Arc<Vec<u8>>),
     Float32(f32),
     Float64(f64),
     Date(u16),
+    Date32(i32),
     DateTime(u32, Tz),
     DateTime64(i64, (u32, Tz)),
     ChronoDateTime(DateTime<Tz>),
@@ -56,6 +62,14 @@ pub enum Value {
         &'static SqlType,
         Arc<HashMap<Value, Value>>,
     ),
+    Tuple(Arc<Vec<Value>>),
+    Point(Point),
+    Ring(Arc<Ring>),
+    Polygon(Arc<Polygon>),
+    MultiPolygon(Arc<MultiPolygon>),
+    Json(Arc<String>),
+    /// Value of a `Variant(...)` or `Dynamic` column of the given type, `None` is `NULL`.
+    Variant(&'static SqlType, Option<Box<Value>>),
 }
 
 impl Hash for Value {
@@ -67,11 +81,13 @@ impl Hash for Value {
             Self::Int32(i) => i.hash(state),
             Self::Int64(i) => i.hash(state),
             Self::Int128(i) => i.hash(state),
+            Self::Int256(i) => i.hash(state),
             Self::UInt8(i) => i.hash(state),
             Self::UInt16(i) => i.hash(state),
             Self::UInt32(i) => i.hash(state),
             Self::UInt64(i) => i.hash(state),
             Self::UInt128(i) => i.hash(state),
+            Self::UInt256(i) => i.hash(state),
             Self::Date(d) => d.hash(state),
             Self::DateTime(t, _) => t.hash(state),
             Self::DateTime64(t, (prec_a, _)) => (*t, *prec_a).hash(state),
@@ -91,15 +107,18 @@ impl PartialEq for Value {
             (Value::UInt32(a), Value::UInt32(b)) => *a == *b,
             (Value::UInt64(a), Value::UInt64(b)) => *a == *b,
             (Value::UInt128(a), Value::UInt128(b)) => *a == *b,
+            (Value::UInt256(a), Value::UInt256(b)) => *a == *b,
             (Value::Int8(a), Value::Int8(b)) => *a == *b,
             (Value::Int16(a), Value::Int16(b)) => *a == *b,
             (Value::Int32(a), Value::Int32(b)) => *a == *b,
             (Value::Int64(a), Value::Int64(b)) => *a == *b,
             (Value::Int128(a), Value::Int128(b)) => *a == *b,
+            (Value::Int256(a), Value::Int256(b)) => *a == *b,
             (Value::String(a), Value::String(b)) => *a == *b,
             (Value::Float32(a), Value::Float32(b)) => *a == *b,
             (Value::Float64(a), Value::Float64(b)) => *a == *b,
             (Value::Date(a), Value::Date(b)) => *a == *b,
+            (Value::Date32(a), Value::Date32(b)) => *a == *b,
             (Value::DateTime(a, tz_a), Value::DateTime(b, tz_b)) => {
                 let time_a = tz_a.timestamp_opt(i64::from(*a), 0).unwrap();
                 let time_b = tz_b.timestamp_opt(i64::from(*b), 0).unwrap();
@@ -118,6 +137,13 @@ impl PartialEq for Value {
             (Value::Ipv4(a), Value::Ipv4(b)) => *a == *b,
             (Value::Ipv6(a), Value::Ipv6(b)) => *a == *b,
             (Value::Uuid(a), Value::Uuid(b)) => *a == *b,
+            (Value::Tuple(a), Value::Tuple(b)) => *a == *b,
+            (Value::Point(a), Value::Point(b)) => *a == *b,
+            (Value::Ring(a), Value::Ring(b)) => *a == *b,
+            (Value::Polygon(a), Value::Polygon(b)) => *a == *b,
+            (Value::MultiPolygon(a), Value::MultiPolygon(b)) => *a == *b,
+            (Value::Json(a), Value::Json(b)) => *a == *b,
+            (Value::Variant(ta, a), Value::Variant(tb, b)) => *ta == *tb && *a == *b,
             (Value::DateTime64(a, (prec_a, tz_a)), Value::DateTime64(b, (prec_b, tz_b))) => {
                 // chrono has no "variable-precision" offset method. As a
                 // fallback, we always use `timestamp_nanos` and multiply by
@@ -157,17 +183,20 @@ impl Value {
             SqlType::UInt32 => Value::UInt32(0),
             SqlType::UInt64 => Value::UInt64(0),
             SqlType::UInt128 => Value::UInt128(0),
+            SqlType::UInt256 => Value::UInt256(U256::ZERO),
             SqlType::Int8 => Value::Int8(0),
             SqlType::Int16 => Value::Int16(0),
             SqlType::Int32 => Value::Int32(0),
             SqlType::Int64 => Value::Int64(0),
             SqlType::Int128 => Value::Int128(0),
+            SqlType::Int256 => Value::Int256(I256::ZERO),
             SqlType::String => Value::String(Arc::new(Vec::default())),
             SqlType::LowCardinality(inner) => Value::default(inner.clone()),
             SqlType::FixedString(str_len) => Value::String(Arc::new(vec![0_u8; str_len])),
             SqlType::Float32 => Value::Float32(0.0),
             SqlType::Float64 => Value::Float64(0.0),
             SqlType::Date => 0_u16.to_date(*DEFAULT_TZ).into(),
+            SqlType::Date32 => Value::Date32(0),
             SqlType::DateTime(DateTimeType::DateTime64(_, _)) => {
                 Value::DateTime64(0, (1, *DEFAULT_TZ))
             }
@@ -176,10 +205,10 @@ impl Value {
             SqlType::Nullable(inner) => Value::Nullable(Either::Left(inner)),
             SqlType::Array(inner) => Value::Array(inner, Arc::new(Vec::default())),
             SqlType::Decimal(precision, scale) => Value::Decimal(Decimal {
-                underlying: 0,
+                underlying: I256::ZERO,
                 precision,
                 scale,
-                nobits: NoBits::N64,
+                nobits: NoBits::from_precision(precision).unwrap_or(NoBits::N64),
             }),
             SqlType::Ipv4 => Value::Ipv4([0_u8; 4]),
             SqlType::Ipv6 => Value::Ipv6([0_u8; 16]),
@@ -187,6 +216,18 @@ impl Value {
             SqlType::Enum8(values) => Value::Enum8(values, Enum8(0)),
             SqlType::Enum16(values) => Value::Enum16(values, Enum16(0)),
             SqlType::Map(k, v) => Value::Map(k, v, Arc::new(HashMap::default())),
+            SqlType::Tuple(types) => Value::Tuple(Arc::new(
+                types
+                    .into_iter()
+                    .map(|t| Value::default(t.clone()))
+                    .collect(),
+            )),
+            SqlType::Point => Value::Point((0.0, 0.0)),
+            SqlType::Ring => Value::Ring(Arc::default()),
+            SqlType::Polygon => Value::Polygon(Arc::default()),
+            SqlType::MultiPolygon => Value::MultiPolygon(Arc::default()),
+            SqlType::Json => Value::Json(Arc::new("{}".into())),
+            SqlType::Variant(_) | SqlType::Dynamic => Value::Variant(sql_type.into(), None),
         }
     }
 }
@@ -200,11 +241,13 @@ impl fmt::Display for Value {
             Value::UInt32(ref v) => fmt::Display::fmt(v, f),
             Value::UInt64(ref v) => fmt::Display::fmt(v, f),
             Value::UInt128(ref v) => fmt::Display::fmt(v, f),
+            Value::UInt256(ref v) => fmt::Display::fmt(v, f),
             Value::Int8(ref v) => fmt::Display::fmt(v, f),
             Value::Int16(ref v) => fmt::Display::fmt(v, f),
             Value::Int32(ref v) => fmt::Display::fmt(v, f),
             Value::Int64(ref v) => fmt::Display::fmt(v, f),
             Value::Int128(ref v) => fmt::Display::fmt(v, f),
+            Value::Int256(ref v) => fmt::Display::fmt(v, f),
             Value::String(ref v) => match str::from_utf8(v) {
                 Ok(s) => fmt::Display::fmt(s, f),
                 Err(_) => write!(f, "{v:?}"),
@@ -239,6 +282,11 @@ impl fmt::Display for Value {
                     .unwrap();
                 fmt::Display::fmt(&date.format("%Y-%m-%d"), f)
             }
+            Value::Date32(v) if f.alternate() => fmt::Display::fmt(&date_from_days((*v).into()), f),
+            Value::Date32(v) => {
+                let date = date_from_days((*v).into());
+                fmt::Display::fmt(&date.format("%Y-%m-%d"), f)
+            }
             Value::Nullable(v) => match v {
                 Either::Left(_) => write!(f, "NULL"),
                 Either::Right(data) => data.fmt(f),
@@ -272,6 +320,19 @@ impl fmt::Display for Value {
                     .collect();
                 write!(f, "[{}]", cells.join(", "))
             }
+            Value::Tuple(vs) => {
+                let cells: Vec<String> = vs.iter().map(|v| format!("{v}")).collect();
+                write!(f, "({})", cells.join(", "))
+            }
+            Value::Point(p) => write!(f, "{}", format_point(p)),
+            Value::Ring(r) => write!(f, "{}", format_ring(r)),
+            Value::Polygon(p) => write!(f, "{}", format_polygon(p)),
+            Value::MultiPolygon(mp) => write!(f, "{}", format_multi_polygon(mp)),
+            Value::Json(v) => fmt::Display::fmt(v, f),
+            Value::Variant(_, v) => match v {
+                None => write!(f, "NULL"),
+                Some(data) => data.fmt(f),
+            },
         }
     }
 }
@@ -285,15 +346,18 @@ impl From<Value> for SqlType {
             Value::UInt32(_) => SqlType::UInt32,
             Value::UInt64(_) => SqlType::UInt64,
             Value::UInt128(_) => SqlType::UInt128,
+            Value::UInt256(_) => SqlType::UInt256,
             Value::Int8(_) => SqlType::Int8,
             Value::Int16(_) => SqlType::Int16,
             Value::Int32(_) => SqlType::Int32,
             Value::Int64(_) => SqlType::Int64,
             Value::Int128(_) => SqlType::Int128,
+            Value::Int256(_) => SqlType::Int256,
             Value::String(_) => SqlType::String,
             Value::Float32(_) => SqlType::Float32,
             Value::Float64(_) => SqlType::Float64,
             Value::Date(_) => SqlType::Date,
+            Value::Date32(_) => SqlType::Date32,
             Value::DateTime(_, _) => SqlType::DateTime(DateTimeType::DateTime32),
             Value::ChronoDateTime(_) => SqlType::DateTime(DateTimeType::DateTime32),
             Value::Nullable(d) => match d {
@@ -315,10 +379,38 @@ impl From<Value> for SqlType {
                 SqlType::DateTime(DateTimeType::DateTime64(precision, tz))
             }
             Value::Map(k, v, _) => SqlType::Map(k, v),
+            Value::Tuple(vs) => {
+                SqlType::Tuple(vs.iter().map(|v| SqlType::from(v.clone()).into()).collect())
+            }
+            Value::Point(_) => SqlType::Point,
+            Value::Ring(_) => SqlType::Ring,
+            Value::Polygon(_) => SqlType::Polygon,
+            Value::MultiPolygon(_) => SqlType::MultiPolygon,
+            Value::Json(_) => SqlType::Json,
+            Value::Variant(t, _) => t.clone(),
         }
     }
 }
 
+pub(crate) fn format_point(point: &Point) -> String {
+    format!("({}, {})", point.0, point.1)
+}
+
+pub(crate) fn format_ring(ring: &Ring) -> String {
+    let cells: Vec<String> = ring.iter().map(format_point).collect();
+    format!("[{}]", cells.join(", "))
+}
+
+pub(crate) fn format_polygon(polygon: &Polygon) -> String {
+    let cells: Vec<String> = polygon.iter().map(format_ring).collect();
+    format!("[{}]", cells.join(", "))
+}
+
+pub(crate) fn format_multi_polygon(multi_polygon: &MultiPolygon) -> String {
+    let cells: Vec<String> = multi_polygon.iter().map(format_polygon).collect();
+    format!("[{}]", cells.join(", "))
+}
+
 impl<T> From<Option<T>> for Value
 where
     Value: From<T>,
@@ -364,7 +456,11 @@ macro_rules! value_array_from {
 
 impl From<AppDate> for Value {
     fn from(v: AppDate) -> Value {
-        Value::Date(u16::get_days(v))
+        if fits_date(v) {
+            Value::Date(u16::get_days(v))
+        } else {
+            Value::Date32(days_since_epoch(v) as i32)
+        }
     }
 }
 
@@ -447,6 +543,29 @@ where
     }
 }
 
+macro_rules! value_from_tuple {
+    ( $( ( $($t:ident: $i:tt),+ ) ),* ) => {
+        $(
+            impl<$($t: Into<Value>),+> convert::From<($($t,)+)> for Value {
+                fn from(v: ($($t,)+)) -> Value {
+                    Value::Tuple(Arc::new(vec![$(v.$i.into()),+]))
+                }
+            }
+        )*
+    };
+}
+
+value_from_tuple! {
+    (A: 0),
+    (A: 0, B: 1),
+    (A: 0, B: 1, C: 2),
+    (A: 0, B: 1, C: 2, D: 3),
+    (A: 0, B: 1, C: 2, D: 3, E: 4),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+}
+
 value_from! {
     bool: Bool,
     u8: UInt8,
@@ -454,12 +573,14 @@ value_from! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     f32: Float32,
     f64: Float64,
@@ -475,12 +596,14 @@ value_array_from! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     f32: Float32,
     f64: Float64
@@ -552,6 +675,9 @@ impl From<Value> for AppDate {
                 .map(|unix_epoch| unix_epoch + Duration::days(x.into()))
                 .unwrap();
         }
+        if let Value::Date32(x) = v {
+            return date_from_days(x.into());
+        }
         let from = SqlType::from(v);
         panic!("Can't convert Value::{} into {}", from, "AppDate")
     }
@@ -581,11 +707,13 @@ from_value! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
     f32: Float32,
     f64: Float64,
     [u8; 4]: Ipv4
@@ -714,6 +842,17 @@ mod test {
         assert_eq!(Value::ChronoDateTime(date_time_value), dt);
     }
 
+    #[test]
+    fn test_from_date32() {
+        let date_value = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+
+        let d: Value = Value::from(date_value);
+        assert_eq!(Value::Date32(-25567), d);
+        assert_eq!(SqlType::Date32, d.clone().into());
+        assert_eq!("1900-01-01", format!("{:#}", d));
+        assert_eq!(date_value, NaiveDate::from(d));
+    }
+
     #[test]
     fn test_boolean() {
         let v = Value::from(false);
@@ -750,12 +889,20 @@ mod test {
         assert_eq!("42".to_string(), format!("{}", Value::UInt32(42)));
         assert_eq!("42".to_string(), format!("{}", Value::UInt64(42)));
         assert_eq!("42".to_string(), format!("{}", Value::UInt128(42)));
+        assert_eq!(
+            "42".to_string(),
+            format!("{}", Value::UInt256(U256::new(42)))
+        );
 
         assert_eq!("42".to_string(), format!("{}", Value::Int8(42)));
         assert_eq!("42".to_string(), format!("{}", Value::Int16(42)));
         assert_eq!("42".to_string(), format!("{}", Value::Int32(42)));
         assert_eq!("42".to_string(), format!("{}", Value::Int64(42)));
         assert_eq!("42".to_string(), format!("{}", Value::Int128(42)));
+        assert_eq!(
+            "42".to_string(),
+            format!("{}", Value::Int256(I256::new(42)))
+        );
 
         assert_eq!(
             "text".to_string(),
@@ -791,6 +938,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_tuple() {
+        let v = Value::from((1_u8, "text", Some(2.5_f64)));
+        assert_eq!(
+            SqlType::from(v.clone()),
+            SqlType::Tuple(vec![
+                &SqlType::UInt8,
+                &SqlType::String,
+                SqlType::Nullable(&SqlType::Float64).into(),
+            ])
+        );
+        assert_eq!("(1, text, 2.5)".to_string(), format!("{v}"));
+    }
+
     #[test]
     fn test_default_fixed_str() {
         for n in 0_usize..1000_usize {
@@ -9,15 +9,20 @@ use std::{
 use chrono::{prelude::*, Duration};
 use chrono_tz::Tz;
 use either::Either;
+use ethnum::{I256, U256};
 use uuid::Uuid;
 
 use crate::{
     errors::{Error, FromSqlError, Result},
     types::{
         column::datetime64::to_datetime,
+        date_converter::date_from_days,
         decimal::Decimal,
-        value::{decode_ipv4, decode_ipv6, AppDate, AppDateTime},
-        DateTimeType, Enum16, Enum8, SqlType, Value,
+        value::{
+            decode_ipv4, decode_ipv6, format_multi_polygon, format_point, format_polygon,
+            format_ring, AppDate, AppDateTime,
+        },
+        DateTimeType, Enum16, Enum8, MultiPolygon, Point, Polygon, Ring, SqlType, Value,
     },
 };
 
@@ -29,15 +34,18 @@ pub enum ValueRef<'a> {
     UInt32(u32),
     UInt64(u64),
     UInt128(u128),
+    UInt256(U256),
     Int8(i8),
     Int16(i16),
     Int32(i32),
     Int64(i64),
     Int128(i128),
+    Int256(I256),
     String(&'a [u8]),
     Float32(f32),
     Float64(f64),
     Date(u16),
+    Date32(i32),
     DateTime(u32, Tz),
     DateTime64(i64, &'a (u32, Tz)),
     Nullable(Either<&'static SqlType, Box<ValueRef<'a>>>),
@@ -46,13 +54,20 @@ pub enum ValueRef<'a> {
     Ipv4([u8; 4]),
     Ipv6([u8; 16]),
     Uuid([u8; 16]),
-    Enum16(Vec<(String, i16)>, Enum16),
-    Enum8(Vec<(String, i8)>, Enum8),
+    Enum16(&'a [(String, i16)], Enum16),
+    Enum8(&'a [(String, i8)], Enum8),
     Map(
         &'static SqlType,
         &'static SqlType,
         Arc<HashMap<ValueRef<'a>, ValueRef<'a>>>,
     ),
+    Tuple(Arc<Vec<ValueRef<'a>>>),
+    Point(Point),
+    Ring(Arc<Ring>),
+    Polygon(Arc<Polygon>),
+    MultiPolygon(Arc<MultiPolygon>),
+    Json(&'a str),
+    Variant(&'static SqlType, Option<Box<ValueRef<'a>>>),
 }
 
 impl<'a> Hash for ValueRef<'a> {
@@ -64,11 +79,13 @@ impl<'a> Hash for ValueRef<'a> {
             Self::Int32(i) => i.hash(state),
             Self::Int64(i) => i.hash(state),
             Self::Int128(i) => i.hash(state),
+            Self::Int256(i) => i.hash(state),
             Self::UInt8(i) => i.hash(state),
             Self::UInt16(i) => i.hash(state),
             Self::UInt32(i) => i.hash(state),
             Self::UInt64(i) => i.hash(state),
             Self::UInt128(i) => i.hash(state),
+            Self::UInt256(i) => i.hash(state),
             _ => unimplemented!(),
         }
     }
@@ -84,15 +101,18 @@ impl<'a> PartialEq for ValueRef<'a> {
             (ValueRef::UInt32(a), ValueRef::UInt32(b)) => *a == *b,
             (ValueRef::UInt64(a), ValueRef::UInt64(b)) => *a == *b,
             (ValueRef::UInt128(a), ValueRef::UInt128(b)) => *a == *b,
+            (ValueRef::UInt256(a), ValueRef::UInt256(b)) => *a == *b,
             (ValueRef::Int8(a), ValueRef::Int8(b)) => *a == *b,
             (ValueRef::Int16(a), ValueRef::Int16(b)) => *a == *b,
             (ValueRef::Int32(a), ValueRef::Int32(b)) => *a == *b,
             (ValueRef::Int64(a), ValueRef::Int64(b)) => *a == *b,
             (ValueRef::Int128(a), ValueRef::Int128(b)) => *a == *b,
+            (ValueRef::Int256(a), ValueRef::Int256(b)) => *a == *b,
             (ValueRef::String(a), ValueRef::String(b)) => *a == *b,
             (ValueRef::Float32(a), ValueRef::Float32(b)) => *a == *b,
             (ValueRef::Float64(a), ValueRef::Float64(b)) => *a == *b,
             (ValueRef::Date(a), ValueRef::Date(b)) => *a == *b,
+            (ValueRef::Date32(a), ValueRef::Date32(b)) => *a == *b,
             (ValueRef::DateTime(a, tz_a), ValueRef::DateTime(b, tz_b)) => {
                 let time_a = tz_a.timestamp_opt(i64::from(*a), 0);
                 let time_b = tz_b.timestamp_opt(i64::from(*b), 0);
@@ -118,6 +138,13 @@ impl<'a> PartialEq for ValueRef<'a> {
                 }
                 map1 == map2
             }
+            (ValueRef::Tuple(a), ValueRef::Tuple(b)) => *a == *b,
+            (ValueRef::Point(a), ValueRef::Point(b)) => *a == *b,
+            (ValueRef::Ring(a), ValueRef::Ring(b)) => *a == *b,
+            (ValueRef::Polygon(a), ValueRef::Polygon(b)) => *a == *b,
+            (ValueRef::MultiPolygon(a), ValueRef::MultiPolygon(b)) => *a == *b,
+            (ValueRef::Json(a), ValueRef::Json(b)) => *a == *b,
+            (ValueRef::Variant(ta, a), ValueRef::Variant(tb, b)) => *ta == *tb && *a == *b,
             _ => false,
         }
     }
@@ -132,11 +159,13 @@ impl<'a> fmt::Display for ValueRef<'a> {
             ValueRef::UInt32(v) => fmt::Display::fmt(v, f),
             ValueRef::UInt64(v) => fmt::Display::fmt(v, f),
             ValueRef::UInt128(v) => fmt::Display::fmt(v, f),
+            ValueRef::UInt256(v) => fmt::Display::fmt(v, f),
             ValueRef::Int8(v) => fmt::Display::fmt(v, f),
             ValueRef::Int16(v) => fmt::Display::fmt(v, f),
             ValueRef::Int32(v) => fmt::Display::fmt(v, f),
             ValueRef::Int64(v) => fmt::Display::fmt(v, f),
             ValueRef::Int128(v) => fmt::Display::fmt(v, f),
+            ValueRef::Int256(v) => fmt::Display::fmt(v, f),
             ValueRef::String(v) => match str::from_utf8(v) {
                 Ok(s) => fmt::Display::fmt(s, f),
                 Err(_) => write!(f, "{:?}", *v),
@@ -155,6 +184,13 @@ impl<'a> fmt::Display for ValueRef<'a> {
                     .unwrap();
                 fmt::Display::fmt(&date.format("%Y-%m-%d"), f)
             }
+            ValueRef::Date32(v) if f.alternate() => {
+                fmt::Display::fmt(&date_from_days((*v).into()), f)
+            }
+            ValueRef::Date32(v) => {
+                let date = date_from_days((*v).into());
+                fmt::Display::fmt(&date.format("%Y-%m-%d"), f)
+            }
             ValueRef::DateTime(u, tz) if f.alternate() => {
                 let time = tz.timestamp_opt(i64::from(*u), 0).unwrap();
                 write!(f, "{}", time.to_rfc2822())
@@ -198,6 +234,19 @@ impl<'a> fmt::Display for ValueRef<'a> {
                 let cells: Vec<String> = vs.iter().map(|v| format!("{}-{}", v.0, v.1)).collect();
                 write!(f, "[{}]", cells.join(", "))
             }
+            ValueRef::Tuple(vs) => {
+                let cells: Vec<String> = vs.iter().map(|v| format!("{v}")).collect();
+                write!(f, "({})", cells.join(", "))
+            }
+            ValueRef::Point(p) => write!(f, "{}", format_point(p)),
+            ValueRef::Ring(r) => write!(f, "{}", format_ring(r)),
+            ValueRef::Polygon(p) => write!(f, "{}", format_polygon(p)),
+            ValueRef::MultiPolygon(mp) => write!(f, "{}", format_multi_polygon(mp)),
+            ValueRef::Json(v) => fmt::Display::fmt(v, f),
+            ValueRef::Variant(_, v) => match v {
+                None => write!(f, "NULL"),
+                Some(inner) => write!(f, "{inner}"),
+            },
         }
     }
 }
@@ -211,15 +260,18 @@ impl<'a> From<ValueRef<'a>> for SqlType {
             ValueRef::UInt32(_) => SqlType::UInt32,
             ValueRef::UInt64(_) => SqlType::UInt64,
             ValueRef::UInt128(_) => SqlType::UInt128,
+            ValueRef::UInt256(_) => SqlType::UInt256,
             ValueRef::Int8(_) => SqlType::Int8,
             ValueRef::Int16(_) => SqlType::Int16,
             ValueRef::Int32(_) => SqlType::Int32,
             ValueRef::Int64(_) => SqlType::Int64,
             ValueRef::Int128(_) => SqlType::Int128,
+            ValueRef::Int256(_) => SqlType::Int256,
             ValueRef::String(_) => SqlType::String,
             ValueRef::Float32(_) => SqlType::Float32,
             ValueRef::Float64(_) => SqlType::Float64,
             ValueRef::Date(_) => SqlType::Date,
+            ValueRef::Date32(_) => SqlType::Date32,
             ValueRef::DateTime(_, _) => SqlType::DateTime(DateTimeType::DateTime32),
             ValueRef::Nullable(u) => match u {
                 Either::Left(sql_type) => SqlType::Nullable(sql_type),
@@ -227,8 +279,8 @@ impl<'a> From<ValueRef<'a>> for SqlType {
             },
             ValueRef::Array(t, _) => SqlType::Array(t),
             ValueRef::Decimal(v) => SqlType::Decimal(v.precision, v.scale),
-            ValueRef::Enum8(values, _) => SqlType::Enum8(values),
-            ValueRef::Enum16(values, _) => SqlType::Enum16(values),
+            ValueRef::Enum8(values, _) => SqlType::Enum8(values.to_vec()),
+            ValueRef::Enum16(values, _) => SqlType::Enum16(values.to_vec()),
             ValueRef::Ipv4(_) => SqlType::Ipv4,
             ValueRef::Ipv6(_) => SqlType::Ipv6,
             ValueRef::Uuid(_) => SqlType::Uuid,
@@ -237,14 +289,35 @@ impl<'a> From<ValueRef<'a>> for SqlType {
                 SqlType::DateTime(DateTimeType::DateTime64(*precision, *tz))
             }
             ValueRef::Map(k, v, _) => SqlType::Map(k, v),
+            ValueRef::Tuple(vs) => {
+                SqlType::Tuple(vs.iter().map(|v| SqlType::from(v.clone()).into()).collect())
+            }
+            ValueRef::Point(_) => SqlType::Point,
+            ValueRef::Ring(_) => SqlType::Ring,
+            ValueRef::Polygon(_) => SqlType::Polygon,
+            ValueRef::MultiPolygon(_) => SqlType::MultiPolygon,
+            ValueRef::Json(_) => SqlType::Json,
+            ValueRef::Variant(t, _) => t.clone(),
         }
     }
 }
 
 impl<'a> ValueRef<'a> {
     pub fn as_str(&self) -> Result<&'a str> {
-        if let ValueRef::String(t) = self {
-            return Ok(str::from_utf8(t)?);
+        match self {
+            ValueRef::String(t) => return Ok(str::from_utf8(t)?),
+            ValueRef::Json(t) => return Ok(t),
+            ValueRef::Enum8(values, v) => {
+                if let Some((name, _)) = values.iter().find(|(_, value)| *value == v.internal()) {
+                    return Ok(name);
+                }
+            }
+            ValueRef::Enum16(values, v) => {
+                if let Some((name, _)) = values.iter().find(|(_, value)| *value == v.internal()) {
+                    return Ok(name);
+                }
+            }
+            _ => {}
         }
         let from = SqlType::from(self.clone()).to_string();
         Err(Error::FromSql(FromSqlError::InvalidType {
@@ -259,8 +332,10 @@ impl<'a> ValueRef<'a> {
     }
 
     pub fn as_bytes(&self) -> Result<&'a [u8]> {
-        if let ValueRef::String(t) = self {
-            return Ok(t);
+        match self {
+            ValueRef::String(t) => return Ok(t),
+            ValueRef::Json(t) => return Ok(t.as_bytes()),
+            _ => {}
         }
         let from = SqlType::from(self.clone()).to_string();
         Err(Error::FromSql(FromSqlError::InvalidType {
@@ -279,15 +354,18 @@ impl<'a> From<ValueRef<'a>> for Value {
             ValueRef::UInt32(v) => Value::UInt32(v),
             ValueRef::UInt64(v) => Value::UInt64(v),
             ValueRef::UInt128(v) => Value::UInt128(v),
+            ValueRef::UInt256(v) => Value::UInt256(v),
             ValueRef::Int8(v) => Value::Int8(v),
             ValueRef::Int16(v) => Value::Int16(v),
             ValueRef::Int32(v) => Value::Int32(v),
             ValueRef::Int64(v) => Value::Int64(v),
             ValueRef::Int128(v) => Value::Int128(v),
+            ValueRef::Int256(v) => Value::Int256(v),
             ValueRef::String(v) => Value::String(Arc::new(v.into())),
             ValueRef::Float32(v) => Value::Float32(v),
             ValueRef::Float64(v) => Value::Float64(v),
             ValueRef::Date(v) => Value::Date(v),
+            ValueRef::Date32(v) => Value::Date32(v),
             ValueRef::DateTime(v, tz) => Value::DateTime(v, tz),
             ValueRef::Nullable(u) => match u {
                 Either::Left(sql_type) => Value::Nullable(Either::Left((sql_type.clone()).into())),
@@ -305,8 +383,8 @@ impl<'a> From<ValueRef<'a>> for Value {
                 Value::Array(t, Arc::new(value_list))
             }
             ValueRef::Decimal(v) => Value::Decimal(v),
-            ValueRef::Enum8(e_v, v) => Value::Enum8(e_v, v),
-            ValueRef::Enum16(e_v, v) => Value::Enum16(e_v, v),
+            ValueRef::Enum8(e_v, v) => Value::Enum8(e_v.to_vec(), v),
+            ValueRef::Enum16(e_v, v) => Value::Enum16(e_v.to_vec(), v),
             ValueRef::Ipv4(v) => Value::Ipv4(v),
             ValueRef::Ipv6(v) => Value::Ipv6(v),
             ValueRef::Uuid(v) => Value::Uuid(v),
@@ -320,6 +398,16 @@ impl<'a> From<ValueRef<'a>> for Value {
                 }
                 Value::Map(k, v, Arc::new(value_list))
             }
+            ValueRef::Tuple(vs) => {
+                let value_list: Vec<Value> = vs.iter().map(|v| v.clone().into()).collect();
+                Value::Tuple(Arc::new(value_list))
+            }
+            ValueRef::Point(p) => Value::Point(p),
+            ValueRef::Ring(r) => Value::Ring(r),
+            ValueRef::Polygon(p) => Value::Polygon(p),
+            ValueRef::MultiPolygon(mp) => Value::MultiPolygon(mp),
+            ValueRef::Json(v) => Value::Json(Arc::new(v.to_string())),
+            ValueRef::Variant(t, v) => Value::Variant(t, v.map(|v| Box::new((*v).into()))),
         }
     }
 }
@@ -356,12 +444,14 @@ from_number! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     f32: Float32,
     f64: Float64
@@ -376,15 +466,18 @@ impl<'a> From<&'a Value> for ValueRef<'a> {
             Value::UInt32(v) => ValueRef::UInt32(*v),
             Value::UInt64(v) => ValueRef::UInt64(*v),
             Value::UInt128(v) => ValueRef::UInt128(*v),
+            Value::UInt256(v) => ValueRef::UInt256(*v),
             Value::Int8(v) => ValueRef::Int8(*v),
             Value::Int16(v) => ValueRef::Int16(*v),
             Value::Int32(v) => ValueRef::Int32(*v),
             Value::Int64(v) => ValueRef::Int64(*v),
             Value::Int128(v) => ValueRef::Int128(*v),
+            Value::Int256(v) => ValueRef::Int256(*v),
             Value::String(v) => ValueRef::String(v),
             Value::Float32(v) => ValueRef::Float32(*v),
             Value::Float64(v) => ValueRef::Float64(*v),
             Value::Date(v) => ValueRef::Date(*v),
+            Value::Date32(v) => ValueRef::Date32(*v),
             Value::DateTime(v, tz) => ValueRef::DateTime(*v, *tz),
             Value::DateTime64(v, params) => ValueRef::DateTime64(*v, params),
             Value::Nullable(u) => match u {
@@ -403,8 +496,8 @@ impl<'a> From<&'a Value> for ValueRef<'a> {
                 ValueRef::Array(t, Arc::new(ref_vec))
             }
             Value::Decimal(v) => ValueRef::Decimal(v.clone()),
-            Value::Enum8(values, v) => ValueRef::Enum8(values.to_vec(), *v),
-            Value::Enum16(values, v) => ValueRef::Enum16(values.to_vec(), *v),
+            Value::Enum8(values, v) => ValueRef::Enum8(values, *v),
+            Value::Enum16(values, v) => ValueRef::Enum16(values, *v),
             Value::Ipv4(v) => ValueRef::Ipv4(*v),
             Value::Ipv6(v) => ValueRef::Ipv6(*v),
             Value::Uuid(v) => ValueRef::Uuid(*v),
@@ -418,6 +511,18 @@ impl<'a> From<&'a Value> for ValueRef<'a> {
                 }
                 ValueRef::Map(k, v, Arc::new(ref_map))
             }
+            Value::Tuple(vs) => {
+                let ref_vec: Vec<ValueRef<'a>> = vs.iter().map(From::from).collect();
+                ValueRef::Tuple(Arc::new(ref_vec))
+            }
+            Value::Point(p) => ValueRef::Point(*p),
+            Value::Ring(r) => ValueRef::Ring(r.clone()),
+            Value::Polygon(p) => ValueRef::Polygon(p.clone()),
+            Value::MultiPolygon(mp) => ValueRef::MultiPolygon(mp.clone()),
+            Value::Json(v) => ValueRef::Json(v.as_str()),
+            Value::Variant(t, v) => {
+                ValueRef::Variant(t, v.as_ref().map(|v| Box::new(v.as_ref().into())))
+            }
         }
     }
 }
@@ -446,6 +551,9 @@ impl<'a> From<ValueRef<'a>> for AppDate {
                 .map(|unix_epoch| unix_epoch + Duration::days(v.into()))
                 .unwrap();
         }
+        if let ValueRef::Date32(v) = value {
+            return date_from_days(v.into());
+        }
         let from = format!("{}", SqlType::from(value.clone()));
         panic!("Can't convert ValueRef::{} into {}.", from, stringify!($t))
     }
@@ -495,12 +603,14 @@ value_from! {
     u32: UInt32,
     u64: UInt64,
     u128: UInt128,
+    U256: UInt256,
 
     i8: Int8,
     i16: Int16,
     i32: Int32,
     i64: Int64,
     i128: Int128,
+    I256: Int256,
 
     f32: Float32,
     f64: Float64
@@ -525,12 +635,20 @@ mod test {
         assert_eq!("42".to_string(), format!("{}", ValueRef::UInt32(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::UInt64(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::UInt128(42)));
+        assert_eq!(
+            "42".to_string(),
+            format!("{}", ValueRef::UInt256(U256::new(42)))
+        );
 
         assert_eq!("42".to_string(), format!("{}", ValueRef::Int8(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::Int16(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::Int32(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::Int64(42)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::Int128(42)));
+        assert_eq!(
+            "42".to_string(),
+            format!("{}", ValueRef::Int256(I256::new(42)))
+        );
 
         assert_eq!("42".to_string(), format!("{}", ValueRef::Float32(42.0)));
         assert_eq!("42".to_string(), format!("{}", ValueRef::Float64(42.0)));
@@ -591,7 +709,7 @@ mod test {
     #[test]
     fn test_size_of() {
         use std::mem;
-        assert_eq!(32, mem::size_of::<[ValueRef<'_>; 1]>());
+        assert_eq!(48, mem::size_of::<[ValueRef<'_>; 1]>());
     }
 
     #[test]
@@ -601,12 +719,20 @@ mod test {
         assert_eq!(Value::from(ValueRef::UInt32(42)), Value::UInt32(42));
         assert_eq!(Value::from(ValueRef::UInt64(42)), Value::UInt64(42));
         assert_eq!(Value::from(ValueRef::UInt128(42)), Value::UInt128(42));
+        assert_eq!(
+            Value::from(ValueRef::UInt256(U256::new(42))),
+            Value::UInt256(U256::new(42))
+        );
 
         assert_eq!(Value::from(ValueRef::Int8(42)), Value::Int8(42));
         assert_eq!(Value::from(ValueRef::Int16(42)), Value::Int16(42));
         assert_eq!(Value::from(ValueRef::Int32(42)), Value::Int32(42));
         assert_eq!(Value::from(ValueRef::Int64(42)), Value::Int64(42));
         assert_eq!(Value::from(ValueRef::Int128(42)), Value::Int128(42));
+        assert_eq!(
+            Value::from(ValueRef::Int256(I256::new(42))),
+            Value::Int256(I256::new(42))
+        );
 
         assert_eq!(Value::from(ValueRef::Float32(42.0)), Value::Float32(42.0));
         assert_eq!(Value::from(ValueRef::Float64(42.0)), Value::Float64(42.0));
@@ -655,12 +781,20 @@ mod test {
         assert_eq!(SqlType::from(ValueRef::UInt32(42)), SqlType::UInt32);
         assert_eq!(SqlType::from(ValueRef::UInt64(42)), SqlType::UInt64);
         assert_eq!(SqlType::from(ValueRef::UInt128(42)), SqlType::UInt128);
+        assert_eq!(
+            SqlType::from(ValueRef::UInt256(U256::new(42))),
+            SqlType::UInt256
+        );
 
         assert_eq!(SqlType::from(ValueRef::Int8(42)), SqlType::Int8);
         assert_eq!(SqlType::from(ValueRef::Int16(42)), SqlType::Int16);
         assert_eq!(SqlType::from(ValueRef::Int32(42)), SqlType::Int32);
         assert_eq!(SqlType::from(ValueRef::Int64(42)), SqlType::Int64);
         assert_eq!(SqlType::from(ValueRef::Int128(42)), SqlType::Int128);
+        assert_eq!(
+            SqlType::from(ValueRef::Int256(I256::new(42))),
+            SqlType::Int256
+        );
 
         assert_eq!(SqlType::from(ValueRef::Float32(42.0)), SqlType::Float32);
         assert_eq!(SqlType::from(ValueRef::Float64(42.0)), SqlType::Float64);
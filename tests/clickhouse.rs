@@ -11,8 +11,8 @@ use chrono_tz::Tz;
 use clickhouse_rs::{
     errors::Error,
     row,
-    types::{Complex, Decimal, Enum16, Enum8, FromSql, SqlType, Value},
-    Block, Options, Pool,
+    types::{Complex, Decimal, Enum16, Enum8, FromRow, FromSql, Query, SqlType, Value, I256, U256},
+    Block, Options, Pool, Row,
 };
 use futures_util::{
     future,
@@ -254,6 +254,325 @@ async fn test_insert() -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(feature = "tokio_io")]
+#[derive(Debug, PartialEq, Row)]
+struct Payment {
+    customer_id: u32,
+    amount: u32,
+    #[clickhouse(rename = "account_name")]
+    name: Option<String>,
+    #[clickhouse(skip)]
+    note: String,
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_derive_row() -> Result<(), Error> {
+    let db = "clickhouse_test_derive_row";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            customer_id  UInt32,
+            amount       UInt32,
+            account_name Nullable(String)
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let payments = vec![
+        Payment {
+            customer_id: 1,
+            amount: 2,
+            name: Some("foo".into()),
+            note: String::new(),
+        },
+        Payment {
+            customer_id: 3,
+            amount: 4,
+            name: None,
+            note: String::new(),
+        },
+    ];
+    c.insert(db, Block::from(payments)).await?;
+
+    let block = c
+        .query(format!("SELECT * FROM {db} ORDER BY customer_id"))
+        .fetch_all()
+        .await?;
+    let actual = block
+        .rows()
+        .map(|row| Payment::from_row(&row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let expected = vec![
+        Payment {
+            customer_id: 1,
+            amount: 2,
+            name: Some("foo".into()),
+            note: String::new(),
+        },
+        Payment {
+            customer_id: 3,
+            amount: 4,
+            name: None,
+            note: String::new(),
+        },
+    ];
+    assert_eq!(actual, expected);
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(all(feature = "tokio_io", feature = "serde"))]
+#[tokio::test]
+async fn test_serde_rows() -> Result<(), Error> {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        id: u32,
+        kind: String,
+        value: Option<u64>,
+        tags: Vec<String>,
+    }
+
+    let db = "clickhouse_test_serde_rows";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            id    UInt32,
+            kind  String,
+            value Nullable(UInt64),
+            tags  Array(String)
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let events = vec![
+        Event {
+            id: 1,
+            kind: "click".into(),
+            value: None,
+            tags: vec!["a".into()],
+        },
+        Event {
+            id: 2,
+            kind: "view".into(),
+            value: None,
+            tags: vec![],
+        },
+    ];
+    c.insert(db, Block::from_rows(&events)?).await?;
+
+    let actual: Vec<Event> = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .fetch_all_as()
+        .await?;
+    assert_eq!(actual, events);
+
+    let streamed: Vec<Event> = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .stream_as()
+        .try_collect()
+        .await?;
+    assert_eq!(streamed, events);
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(all(feature = "tokio_io", feature = "arrow"))]
+#[tokio::test]
+async fn test_arrow_record_batch() -> Result<(), Error> {
+    use arrow_array::{cast::AsArray, types::Int32Type, Array};
+
+    let db = "clickhouse_test_arrow_record_batch";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            id     UInt32,
+            color  LowCardinality(String),
+            amount Nullable(Float64),
+            tags   Array(String),
+            day    Date,
+            price  Decimal(9, 2)
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    c.execute(format!(
+        "INSERT INTO {db} VALUES
+            (1, 'red', 1.5, ['a'], '2020-01-01', 1.25),
+            (2, 'green', NULL, [], '2021-06-15', 2.5),
+            (3, 'red', 3, ['b', 'c'], '2022-12-31', -3.75)"
+    ))
+    .await?;
+
+    let block = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .fetch_all()
+        .await?;
+    let batch = block.to_record_batch()?;
+    assert_eq!(batch.num_rows(), 3);
+
+    let colors = batch.column(1).as_dictionary::<Int32Type>();
+    assert_eq!(colors.values().len(), 2);
+    assert!(batch.column(2).is_null(1));
+
+    c.execute(format!("TRUNCATE TABLE {db}")).await?;
+    c.insert(db, Block::from_record_batch(&batch)?).await?;
+
+    let actual = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .fetch_all()
+        .await?;
+    assert_eq!(actual.to_record_batch()?, batch);
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_inserter() -> Result<(), Error> {
+    use futures_util::SinkExt;
+
+    let db = "clickhouse_test_inserter";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            id   UInt64,
+            name String
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let mut inserter = c.inserter(db).max_rows(10).max_bytes(1024);
+    for id in 0_u64..25 {
+        let name = format!("name {id}");
+        inserter.write(row! { id: id, name: name }).await?;
+    }
+
+    let block = Block::new()
+        .column("id", vec![25_u64, 26, 27])
+        .column("name", vec!["a", "b", "c"]);
+    inserter.feed(block).await?;
+    inserter.flush().await?;
+    inserter.feed(row! { id: 28_u64, name: "d" }).await?;
+    inserter.end().await?;
+
+    let block = c
+        .query(format!("SELECT count(), sum(id) FROM {db}"))
+        .fetch_all()
+        .await?;
+    let count: u64 = block.get(0, 0)?;
+    let sum: u64 = block.get(0, 1)?;
+    assert_eq!(count, 29);
+    assert_eq!(sum, (0..29).sum::<u64>());
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_compression_methods() -> Result<(), Error> {
+    let db = "clickhouse_test_compression_methods";
+    let values: Vec<String> = (0..1000).map(|i| format!("value {}", i % 10)).collect();
+
+    for method in ["none", "lz4hc", "lz4hc(12)", "zstd", "zstd(5)"] {
+        let url = database_url().replace("compression=lz4", &format!("compression={method}"));
+        let pool = Pool::new(url);
+        let mut c = pool.get_handle().await?;
+
+        c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+        c.execute(format!("CREATE TABLE {db} (s String) Engine=Memory"))
+            .await?;
+
+        let block = Block::new().column("s", values.clone());
+        c.insert(db, block).await?;
+
+        let block = c.query(format!("SELECT s FROM {db}")).fetch_all().await?;
+        let actual: Vec<String> = collect_values(&block, "s");
+        assert_eq!(actual, values, "{method}");
+
+        c.execute(format!("DROP TABLE {db}")).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_date32() -> Result<(), Error> {
+    let db = "clickhouse_test_date32";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            date Date32,
+            recent Date32,
+            date_opt Nullable(Date32)
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let dates = vec![
+        NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2016, 10, 22).unwrap(),
+    ];
+    let recent = vec![
+        NaiveDate::from_ymd_opt(2016, 10, 22).unwrap(),
+        NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+    ];
+    let date_opt = vec![Some(NaiveDate::from_ymd_opt(1925, 5, 1).unwrap()), None];
+
+    let block = Block::new()
+        .column("date", dates.clone())
+        .column("recent", recent.clone())
+        .column("date_opt", date_opt.clone());
+    c.insert(db, block).await?;
+
+    let block = c
+        .query(format!("SELECT date, recent, date_opt FROM {db}"))
+        .fetch_all()
+        .await?;
+
+    let actual: Vec<NaiveDate> = block.get_column("date")?.iter::<NaiveDate>()?.collect();
+    assert_eq!(actual, dates);
+    for (i, expected) in recent.iter().enumerate() {
+        let date: NaiveDate = block.get(i, "recent")?;
+        assert_eq!(date, *expected);
+    }
+    for (i, expected) in date_opt.iter().enumerate() {
+        let date: Option<NaiveDate> = block.get(i, "date_opt")?;
+        assert_eq!(date, *expected);
+    }
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
 #[cfg(feature = "tokio_io")]
 #[tokio::test]
 async fn test_datetime_read_write() -> Result<(), Error> {
@@ -1059,8 +1378,12 @@ async fn test_with_totals() -> Result<(), Error> {
     let block = Block::new().column("country", vec!["RU", "EN", "RU", "RU", "EN", "RU"]);
 
     let expected = Block::new()
-        .column("country", vec!["EN", "RU", ""])
-        .column("country", vec![2_u64, 4, 6]);
+        .column("country", vec!["EN", "RU"])
+        .column("country", vec![2_u64, 4]);
+
+    let expected_totals = Block::new()
+        .column("country", vec![""])
+        .column("country", vec![6_u64]);
 
     let pool = Pool::new(database_url());
     let mut c = pool.get_handle().await?;
@@ -1068,9 +1391,263 @@ async fn test_with_totals() -> Result<(), Error> {
         .await?;
     c.execute(ddl).await?;
     c.insert("clickhouse_test_with_totals", block).await?;
-    let block = c.query(query).fetch_all().await?;
 
+    let block = c.query(query).fetch_all().await?;
     assert_eq!(expected, block);
+
+    let result = c.query(query).fetch_data().await?;
+    assert_eq!(&expected, result.data());
+    assert_eq!(&expected_totals, result.totals().unwrap());
+    assert!(result.extremes().is_none());
+
+    let mut rows = 0;
+    let mut stream = c.query(query).stream();
+    while let Some(row) = stream.next().await {
+        row?;
+        rows += 1;
+    }
+    drop(stream);
+    assert_eq!(rows, 2);
+    assert_eq!(expected_totals, *c.totals().unwrap());
+    assert!(c.extremes().is_none());
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_extremes() -> Result<(), Error> {
+    let options = Options::from_str(&database_url())?.with_setting(
+        "extremes",
+        1,
+        /* is_important= */ true,
+    );
+    let pool = Pool::new(options);
+    let mut c = pool.get_handle().await?;
+
+    let result = c
+        .query("SELECT number AS n FROM system.numbers LIMIT 10")
+        .fetch_data()
+        .await?;
+
+    let expected_extremes = Block::new().column("n", vec![0_u64, 9]);
+
+    assert_eq!(result.data().row_count(), 10);
+    assert!(result.totals().is_none());
+    assert_eq!(&expected_extremes, result.extremes().unwrap());
+
+    let blocks: Vec<_> = c
+        .query("SELECT number AS n FROM system.numbers LIMIT 10")
+        .stream_blocks()
+        .try_collect()
+        .await?;
+    assert_eq!(blocks.iter().map(Block::row_count).sum::<usize>(), 10);
+    assert_eq!(expected_extremes, *c.extremes().unwrap());
+    assert!(c.totals().is_none());
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_progress_callback() -> Result<(), Error> {
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    let rows = Arc::new(AtomicUsize::new(0));
+    let counter = rows.clone();
+    c.set_progress_callback(move |progress| {
+        counter.fetch_add(progress.rows as usize, Ordering::SeqCst);
+    });
+
+    let block = c
+        .query("SELECT number FROM system.numbers LIMIT 100000")
+        .fetch_all()
+        .await?;
+
+    assert_eq!(block.row_count(), 100_000);
+    assert!(rows.load(Ordering::SeqCst) >= 100_000);
+
+    c.clear_progress_callback();
+    rows.store(0, Ordering::SeqCst);
+    c.query("SELECT number FROM system.numbers LIMIT 10")
+        .fetch_all()
+        .await?;
+    assert_eq!(rows.load(Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_server_logs() -> Result<(), Error> {
+    let options = Options::from_str(&database_url())?.with_setting(
+        "send_logs_level",
+        "trace",
+        /* is_important= */ true,
+    );
+    let pool = Pool::new(options);
+    let mut c = pool.get_handle().await?;
+
+    let records = Arc::new(AtomicUsize::new(0));
+    let counter = records.clone();
+    c.set_server_log_callback(move |record| {
+        assert!(!record.text.is_empty());
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let block = c.query("SELECT 1").fetch_all().await?;
+    c.execute("SELECT 1").await?;
+
+    assert_eq!(block.row_count(), 1);
+    assert!(records.load(Ordering::SeqCst) > 0);
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_cancel_query() -> Result<(), Error> {
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    let result = c.query("SELECT number FROM system.numbers");
+    let cancel = result.cancel_handle();
+
+    let mut stream = result.stream_blocks();
+    let mut err = None;
+    while let Some(ret) = stream.next().await {
+        match ret {
+            Ok(_) => cancel.cancel(),
+            Err(e) => err = Some(e),
+        }
+    }
+    drop(stream);
+
+    assert!(cancel.is_cancelled());
+    assert!(matches!(
+        err,
+        Some(Error::Driver(clickhouse_rs::errors::DriverError::QueryCancelled))
+    ));
+
+    let block = c.query("SELECT 1 AS one").fetch_all().await?;
+    assert_eq!(block.get::<u8, _>(0, "one")?, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_cancelled_query_keeps_connection() -> Result<(), Error> {
+    let options = Options::from_str(&database_url())?.pool_min(1).pool_max(1);
+    let pool = Pool::new(options);
+
+    {
+        let mut c = pool.get_handle().await?;
+        let mut stream = c.query("SELECT number FROM system.numbers").stream_blocks();
+        stream.next().await.unwrap()?;
+    }
+
+    {
+        let mut c = pool.get_handle().await?;
+        let block = c.query("SELECT 1 AS one").fetch_all().await?;
+        assert_eq!(block.get::<u8, _>(0, "one")?, 1);
+    }
+
+    let status = pool.status();
+    assert_eq!(status.idle, 1);
+    assert_eq!(status.created, 1);
+    assert_eq!(status.closed, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_query_after_dropped_stream() -> Result<(), Error> {
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    {
+        let mut stream = c
+            .query("SELECT number FROM system.numbers LIMIT 10000000")
+            .stream_blocks();
+        stream.next().await.unwrap()?;
+    }
+
+    let block = c.query("SELECT 1 AS one").fetch_all().await?;
+    assert_eq!(block.get::<u8, _>(0, "one")?, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_query_params() -> Result<(), Error> {
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    let query = Query::new(
+        "SELECT {id:UInt32} AS id, {name:String} AS name, {tags:Array(String)} AS tags",
+    )
+    .bind("id", 42_u32)
+    .bind("name", "it's \\ a\tname")
+    .bind("tags", vec!["a'b".to_string(), "c".to_string()]);
+
+    let block = c.query(query).fetch_all().await?;
+
+    assert_eq!(block.get::<u32, _>(0, "id")?, 42);
+    assert_eq!(block.get::<String, _>(0, "name")?, "it's \\ a\tname");
+    assert_eq!(
+        block.get::<Vec<String>, _>(0, "tags")?,
+        vec!["a'b".to_string(), "c".to_string()]
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_query_settings() -> Result<(), Error> {
+    let options = Options::from_str(&database_url())?.with_setting("max_block_size", 2, false);
+    let pool = Pool::new(options);
+    let mut c = pool.get_handle().await?;
+
+    let sql = "SELECT getSetting('max_block_size') AS value";
+
+    let query = Query::new(sql).with_setting("max_block_size", 3, false);
+    let block = c.query(query).fetch_all().await?;
+    assert_eq!(block.get::<u64, _>(0, "value")?, 3);
+
+    let block = c.query(sql).fetch_all().await?;
+    assert_eq!(block.get::<u64, _>(0, "value")?, 2);
+
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_external_tables() -> Result<(), Error> {
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    let ids = Block::new().column("id", vec![1_u64, 5, 7]);
+    let names = Block::new()
+        .column("id", vec![5_u64, 7])
+        .column("name", vec!["five", "seven"]);
+
+    let query = Query::new(
+        "SELECT number, name FROM numbers(10) \
+         INNER JOIN names ON number = names.id \
+         WHERE number IN ids \
+         ORDER BY number",
+    )
+    .external_table("ids", ids)
+    .external_table("names", names);
+
+    let block = c.query(query).fetch_all().await?;
+
+    assert_eq!(block.row_count(), 2);
+    assert_eq!(block.get::<u64, _>(0, "number")?, 5);
+    assert_eq!(block.get::<String, _>(1, "name")?, "seven");
+
     Ok(())
 }
 
@@ -1641,6 +2218,87 @@ async fn test_enum_8_array() -> Result<(), Error> {
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, clickhouse_rs::Enum)]
+enum Level {
+    Debug = 1,
+    Info = 2,
+    #[clickhouse(rename = "warn")]
+    Warning = 3,
+}
+
+#[derive(Debug, PartialEq, Row)]
+struct LogLine {
+    id: u32,
+    level: Level,
+    prev_level: Option<Level>,
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_enum_names() -> Result<(), Error> {
+    let ddl = "
+        CREATE TABLE IF NOT EXISTS clickhouse_enum_names (
+            id         UInt32,
+            level      Enum8('warn' = -1, 'Info' = 0, 'Debug' = 1),
+            prev_level Nullable(Enum8('warn' = -1, 'Info' = 0, 'Debug' = 1))
+        ) Engine=Memory";
+
+    let lines = vec![
+        LogLine {
+            id: 1,
+            level: Level::Warning,
+            prev_level: None,
+        },
+        LogLine {
+            id: 2,
+            level: Level::Info,
+            prev_level: Some(Level::Warning),
+        },
+    ];
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+    c.execute("DROP TABLE IF EXISTS clickhouse_enum_names")
+        .await?;
+    c.execute(ddl).await?;
+    c.insert("clickhouse_enum_names", Block::from(lines))
+        .await?;
+
+    let block = c
+        .query("SELECT * FROM clickhouse_enum_names ORDER BY id")
+        .fetch_all()
+        .await?;
+
+    let name: &str = block.get(0, "level")?;
+    assert_eq!(name, "warn");
+    let name: Option<String> = block.get(1, "prev_level")?;
+    assert_eq!(name, Some("warn".to_string()));
+    let raw: Enum8 = block.get(0, "level")?;
+    assert_eq!(raw, Enum8::of(-1));
+
+    let actual: Vec<LogLine> = block
+        .rows()
+        .map(|row| LogLine::from_row(&row))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        actual,
+        vec![
+            LogLine {
+                id: 1,
+                level: Level::Warning,
+                prev_level: None,
+            },
+            LogLine {
+                id: 2,
+                level: Level::Info,
+                prev_level: Some(Level::Warning),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "tokio_io")]
 #[tokio::test]
 async fn test_array() -> Result<(), Error> {
@@ -1734,13 +2392,69 @@ async fn test_decimal() -> Result<(), Error> {
     let ox0: Option<Decimal> = block.get(0, "ox")?;
 
     assert_eq!(2, block.row_count());
-    assert_eq!(1.234, x.into());
+    assert_eq!(1.234, f64::from(x));
     assert_eq!(Some(1.23), ox.map(|v| v.into()));
     assert_eq!(None, ox0);
 
     Ok(())
 }
 
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_wide_decimal() -> Result<(), Error> {
+    let ddl = "
+        CREATE TABLE clickhouse_wide_decimal (
+            x  Decimal(38, 10),
+            ox Nullable(Decimal128(4)),
+            y  Decimal256(20)
+        ) Engine=Memory";
+
+    let query = "SELECT x, ox, y FROM clickhouse_wide_decimal";
+
+    let big = Decimal::new(123_456_789_012_345_678_901_234_567_i128, 10);
+    let block = Block::new()
+        .column("x", vec![big.clone(), Decimal::of(5, 10)])
+        .column("ox", vec![None, Some(Decimal::of(1.5, 4))])
+        .column("y", vec![Decimal::new(-1_i128, 20), big.clone()]);
+
+    let pool = Pool::new(database_url());
+
+    let mut c = pool.get_handle().await?;
+    c.execute("DROP TABLE IF EXISTS clickhouse_wide_decimal")
+        .await?;
+    c.execute(ddl).await?;
+    c.insert("clickhouse_wide_decimal", block).await?;
+    let block = c.query(query).fetch_all().await?;
+
+    let x: Decimal = block.get(0, "x")?;
+    let ox: Option<Decimal> = block.get(1, "ox")?;
+    let y: Vec<Decimal> = block.get_column("y")?.iter::<Decimal>()?.collect();
+
+    assert_eq!(x, big);
+    assert_eq!(x.to_string(), "12345678901234567.8901234567");
+    assert_eq!(ox, Some(Decimal::of(1.5, 4)));
+    assert_eq!(y, vec![Decimal::new(-1_i128, 20), big]);
+
+    let block = c
+        .query("SELECT toDecimal128('-12345678901234567890.123', 3) AS x")
+        .fetch_all()
+        .await?;
+    let x: i128 = block.get::<Decimal, _>(0, "x")?.internal();
+    assert_eq!(x, -12_345_678_901_234_567_890_123);
+
+    let block = c
+        .query("SELECT toDecimal256('-123456789012345678901234567890123456789012345.6', 20) AS y")
+        .fetch_all()
+        .await?;
+    let y: Decimal = block.get(0, "y")?;
+    assert_eq!(
+        y.to_string(),
+        "-123456789012345678901234567890123456789012345.60000000000000000000"
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "tokio_io")]
 #[tokio::test]
 async fn test_column_iter() -> Result<(), Error> {
@@ -2548,6 +3262,56 @@ async fn test_iter_int_128() -> Result<(), Error> {
     Ok(())
 }
 
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_int_256() -> Result<(), Error> {
+    let ddl = "
+        CREATE TABLE clickhouse_test_int_256 (
+            i  Int256,
+            u  UInt256,
+            oi Nullable(Int256)
+        ) Engine=Memory";
+
+    let query = "SELECT i, u, oi FROM clickhouse_test_int_256";
+
+    let big = U256::from_words(1, 2);
+    let block = Block::new()
+        .column("i", vec![I256::new(-1_000), I256::MAX])
+        .column("u", vec![big, U256::ZERO])
+        .column("oi", vec![Some(I256::new(1_000)), None]);
+
+    let pool = Pool::new(database_url());
+
+    let mut c = pool.get_handle().await?;
+    c.execute("DROP TABLE IF EXISTS clickhouse_test_int_256")
+        .await?;
+    c.execute(ddl).await?;
+    c.insert("clickhouse_test_int_256", block).await?;
+    let block = c.query(query).fetch_all().await?;
+
+    let i: I256 = block.get(0, "i")?;
+    let u: U256 = block.get(0, "u")?;
+    let oi: Option<I256> = block.get(1, "oi")?;
+
+    assert_eq!(i, I256::new(-1_000));
+    assert_eq!(u, big);
+    assert_eq!(oi, None);
+
+    let is: Vec<_> = block.get_column("i")?.iter::<I256>()?.copied().collect();
+    assert_eq!(is, vec![I256::new(-1_000), I256::MAX]);
+
+    let ois: Vec<_> = block.get_column("oi")?.iter::<Option<I256>>()?.collect();
+    assert_eq!(ois, vec![Some(&I256::new(1_000)), None]);
+
+    let block = c
+        .query("SELECT toString(u) AS s FROM clickhouse_test_int_256 LIMIT 1")
+        .fetch_all()
+        .await?;
+    assert_eq!(block.get::<String, _>(0, "s")?, big.to_string());
+
+    Ok(())
+}
+
 #[cfg(feature = "tokio_io")]
 #[tokio::test]
 async fn test_insert_big_block() -> Result<(), Error> {
@@ -2574,3 +3338,184 @@ async fn test_insert_big_block() -> Result<(), Error> {
     assert_eq!(format!("{:?}", expected.as_ref()), format!("{:?}", &actual));
     Ok(())
 }
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_geo_types() -> Result<(), Error> {
+    use clickhouse_rs::types::{MultiPolygon, Point, Polygon, Ring};
+
+    let db = "clickhouse_test_geo_types";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            p  Point,
+            r  Ring,
+            pg Polygon,
+            mp MultiPolygon
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let points: Vec<Point> = vec![(1.0, 2.0), (3.5, -4.0)];
+    let rings: Vec<Ring> = vec![vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)], vec![]];
+    let polygons: Vec<Polygon> = vec![
+        vec![vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]],
+        vec![
+            vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0)],
+            vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0)],
+        ],
+    ];
+    let multi_polygons: Vec<MultiPolygon> = vec![polygons.clone(), vec![]];
+
+    let block = Block::new()
+        .column("p", points.clone())
+        .column("r", rings.clone())
+        .column("pg", polygons.clone())
+        .column("mp", multi_polygons.clone());
+    c.insert(db, block).await?;
+
+    let block = c.query(format!("SELECT * FROM {db}")).fetch_all().await?;
+    assert_eq!(block.get_column("pg")?.sql_type().to_string(), "Polygon");
+
+    for (i, row) in block.rows().enumerate() {
+        let p: Point = row.get("p")?;
+        let r: Ring = row.get("r")?;
+        let pg: Polygon = row.get("pg")?;
+        let mp: MultiPolygon = row.get("mp")?;
+
+        assert_eq!(p, points[i]);
+        assert_eq!(r, rings[i]);
+        assert_eq!(pg, polygons[i]);
+        assert_eq!(mp, multi_polygons[i]);
+    }
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_nested() -> Result<(), Error> {
+    use clickhouse_rs::Row;
+
+    #[derive(Debug, PartialEq, Row)]
+    struct Line {
+        sku: String,
+        qty: u32,
+    }
+
+    let db = "clickhouse_test_nested";
+
+    let pool = Pool::new(database_url());
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            id    UInt32,
+            lines Nested(sku String, qty UInt32)
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let block = Block::new().column("id", vec![1_u32, 2]).nested_column(
+        "lines",
+        vec![
+            vec![
+                Line {
+                    sku: "a".into(),
+                    qty: 1,
+                },
+                Line {
+                    sku: "b".into(),
+                    qty: 2,
+                },
+            ],
+            vec![],
+        ],
+    );
+    c.insert(db, block).await?;
+
+    let block = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .fetch_all()
+        .await?;
+
+    let lines: Vec<(String, u32)> = block.get_nested(0, "lines")?;
+    assert_eq!(lines, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+
+    let row = block.rows().nth(1).unwrap();
+    assert!(row.get_nested::<Line>("lines")?.is_empty());
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
+
+#[cfg(feature = "tokio_io")]
+#[tokio::test]
+async fn test_variant_dynamic_json() -> Result<(), Error> {
+    let db = "clickhouse_test_variant_dynamic_json";
+
+    let options = Options::from_str(&database_url())?
+        .with_setting("allow_experimental_variant_type", 1, false)
+        .with_setting("allow_experimental_dynamic_type", 1, false)
+        .with_setting("allow_experimental_json_type", 1, false)
+        .with_setting("output_format_native_write_json_as_string", 1, true);
+    let pool = Pool::new(options);
+    let mut c = pool.get_handle().await?;
+
+    c.execute(format!("DROP TABLE IF EXISTS {db}")).await?;
+    c.execute(format!(
+        "CREATE TABLE {db} (
+            id  UInt32,
+            v   Variant(UInt64, String),
+            d   Dynamic,
+            doc JSON
+        ) Engine=Memory"
+    ))
+    .await?;
+
+    let variant_type: &'static SqlType =
+        SqlType::Variant(vec![&SqlType::UInt64, &SqlType::String]).into();
+
+    let mut block = Block::new();
+    block.push(row! {
+        id: 1_u32,
+        v: Value::Variant(variant_type, Some(Box::new(Value::UInt64(42)))),
+        d: Value::Variant(&SqlType::Dynamic, Some(Box::new(Value::from("foo")))),
+        doc: Value::Json(Arc::new(r#"{"a":1}"#.into())),
+    })?;
+    block.push(row! {
+        id: 2_u32,
+        v: Value::Variant(variant_type, None),
+        d: Value::Variant(&SqlType::Dynamic, Some(Box::new(Value::Float64(0.5)))),
+        doc: Value::Json(Arc::new(r#"{"b":"x"}"#.into())),
+    })?;
+    c.insert(db, block).await?;
+
+    let block = c
+        .query(format!("SELECT * FROM {db} ORDER BY id"))
+        .fetch_all()
+        .await?;
+    assert_eq!(block.get_column("d")?.sql_type(), SqlType::Dynamic);
+
+    let v: Option<u64> = block.get(0, "v")?;
+    assert_eq!(v, Some(42));
+    let v: Option<String> = block.get(1, "v")?;
+    assert_eq!(v, None);
+
+    let d: String = block.get(0, "d")?;
+    assert_eq!(d, "foo");
+    let d: f64 = block.get(1, "d")?;
+    assert_eq!(d, 0.5);
+
+    let doc: String = block.get(0, "doc")?;
+    assert_eq!(doc, r#"{"a":1}"#);
+
+    c.execute(format!("DROP TABLE {db}")).await?;
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use clickhouse_rs::{
+    errors::Error,
+    row,
+    types::{Enum8, EnumType, FromRow, HasSqlType, SqlType, Value},
+    Block, Enum, Row,
+};
+
+#[derive(Debug, Clone, PartialEq, Row)]
+struct Item {
+    id: u32,
+    #[clickhouse(rename = "item_name")]
+    name: String,
+    price: Option<f64>,
+    tags: Vec<String>,
+    #[clickhouse(skip)]
+    cached: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Row)]
+struct Wrapper<T> {
+    r#type: T,
+    rows: u8,
+}
+
+fn items() -> Vec<Item> {
+    vec![
+        Item {
+            id: 1,
+            name: "foo".into(),
+            price: Some(1.5),
+            tags: vec!["a".into(), "b".into()],
+            cached: Some(42),
+        },
+        Item {
+            id: 2,
+            name: "bar".into(),
+            price: None,
+            tags: vec![],
+            cached: None,
+        },
+    ]
+}
+
+#[test]
+fn test_into_block() {
+    let block = Block::from(items());
+
+    assert_eq!(block.row_count(), 2);
+    let names: Vec<_> = block.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["id", "item_name", "price", "tags"]);
+    assert_eq!(block.columns()[0].sql_type(), SqlType::UInt32);
+    assert_eq!(
+        block.columns()[2].sql_type(),
+        SqlType::Nullable(SqlType::Float64.into())
+    );
+}
+
+#[test]
+fn test_from_row() -> Result<(), Error> {
+    let block = Block::from(items());
+
+    let actual = block
+        .rows()
+        .map(|row| Item::from_row(&row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut expected = items();
+    for item in &mut expected {
+        item.cached = None;
+    }
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_from_row_missing_column() {
+    let block = Block::new().column("id", vec![1_u32]);
+    let row = block.rows().next().unwrap();
+    assert!(Item::from_row(&row).is_err());
+}
+
+#[test]
+fn test_generic_and_raw_fields() -> Result<(), Error> {
+    let source = vec![Wrapper {
+        r#type: "x".to_string(),
+        rows: 3,
+    }];
+    let block = Block::from(source);
+
+    let names: Vec<_> = block.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["type", "rows"]);
+
+    let row = block.rows().next().unwrap();
+    let actual: Wrapper<String> = Wrapper::from_row(&row)?;
+    assert_eq!(actual.r#type, "x");
+    assert_eq!(actual.rows, 3);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Row)]
+struct Line {
+    sku: String,
+    qty: u32,
+}
+
+#[test]
+fn test_nested_column() -> Result<(), Error> {
+    let lines = vec![
+        vec![
+            Line {
+                sku: "a".into(),
+                qty: 1,
+            },
+            Line {
+                sku: "b".into(),
+                qty: 2,
+            },
+        ],
+        vec![],
+    ];
+    let block = Block::new()
+        .column("id", vec![1_u32, 2])
+        .nested_column("lines", lines);
+
+    let names: Vec<_> = block.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["id", "lines.sku", "lines.qty"]);
+    assert_eq!(
+        block.columns()[2].sql_type(),
+        SqlType::Array(SqlType::UInt32.into())
+    );
+
+    let rows: Vec<Vec<Line>> = block
+        .rows()
+        .map(|row| row.get_nested("lines"))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        rows[0],
+        vec![
+            Line {
+                sku: "a".into(),
+                qty: 1
+            },
+            Line {
+                sku: "b".into(),
+                qty: 2
+            },
+        ]
+    );
+    assert!(rows[1].is_empty());
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Enum)]
+enum Level {
+    Debug = 1,
+    Info,
+    #[clickhouse(rename = "warn")]
+    Warning = -1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Enum)]
+#[clickhouse(enum16)]
+enum Size {
+    Small,
+    Large = 1000,
+}
+
+#[derive(Debug, PartialEq, Row)]
+struct Event {
+    level: Level,
+    prev_level: Option<Level>,
+}
+
+#[test]
+fn test_enum_sql_type() {
+    assert_eq!(
+        Level::get_sql_type(),
+        SqlType::Enum8(vec![
+            ("Debug".into(), 1),
+            ("Info".into(), 2),
+            ("warn".into(), -1)
+        ])
+    );
+    assert_eq!(
+        Size::get_sql_type(),
+        SqlType::Enum16(vec![("Small".into(), 0), ("Large".into(), 1000)])
+    );
+    assert_eq!(Level::from_name("warn"), Some(Level::Warning));
+    assert_eq!(Level::from_name("Warning"), None);
+    assert_eq!(Size::Large.name(), "Large");
+}
+
+#[test]
+fn test_enum_fields() -> Result<(), Error> {
+    let events = vec![
+        Event {
+            level: Level::Warning,
+            prev_level: None,
+        },
+        Event {
+            level: Level::Info,
+            prev_level: Some(Level::Debug),
+        },
+    ];
+    let block = Block::from(events);
+
+    assert_eq!(block.columns()[0].sql_type(), Level::get_sql_type());
+    let name: &str = block.get(0, "level")?;
+    assert_eq!(name, "warn");
+    let name: Option<String> = block.get(1, "prev_level")?;
+    assert_eq!(name.as_deref(), Some("Debug"));
+
+    let actual = block
+        .rows()
+        .map(|row| Event::from_row(&row))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        actual,
+        vec![
+            Event {
+                level: Level::Warning,
+                prev_level: None,
+            },
+            Event {
+                level: Level::Info,
+                prev_level: Some(Level::Debug),
+            },
+        ]
+    );
+
+    let block = Block::new().column("size", vec![Size::Small]);
+    assert!(block.get::<Level, _>(0, "size").is_err());
+    assert_eq!(block.get::<Size, _>(0, "size")?, Size::Small);
+    Ok(())
+}
+
+#[test]
+fn test_enum_values() -> Result<(), Error> {
+    let mut block = Block::new();
+    block.push(row! { level: Level::Info, prev_level: Some(Level::Warning) })?;
+
+    let value: Value = Level::Warning.into();
+    let values = vec![
+        ("Debug".to_string(), 1),
+        ("Info".to_string(), 2),
+        ("warn".to_string(), -1),
+    ];
+    assert_eq!(value, Value::Enum8(values, Enum8::of(-1)));
+
+    let level: Level = block.get(0, "level")?;
+    assert_eq!(level, Level::Info);
+    let prev_level: Option<Level> = block.get(0, "prev_level")?;
+    assert_eq!(prev_level, Some(Level::Warning));
+    Ok(())
+}